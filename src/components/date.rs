@@ -1,6 +1,6 @@
 use crate::tokens::TOKEN_HYPHEN;
 use crate::{collect_day_and_validate, parse_format, parse_month_component};
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
 
 /// Parse a [proleptic-Gregorian date][proleptic-greg], in the format of `YYYY-MM-DD`
 ///
@@ -65,9 +65,27 @@ pub fn parse_date_component(s: &str, position: &mut usize) -> Option<NaiveDate>
 	NaiveDate::from_ymd_opt(year, month, day)
 }
 
+/// Serialize a [proleptic-Gregorian date][proleptic-greg] as a
+/// [valid date string][whatwg-html-valid], in the format of `YYYY-MM-DD`
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::serialize_date;
+///
+/// let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+/// assert_eq!(serialize_date(date), "2011-11-18");
+/// ```
+///
+/// [proleptic-greg]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#proleptic-gregorian-date
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-date-string
+pub fn serialize_date(date: NaiveDate) -> String {
+	format!("{:04}-{:02}-{:02}", date.year(), date.month(), date.day())
+}
+
 #[cfg(test)]
 mod tests {
-	use super::parse_date;
+	use super::{parse_date, serialize_date};
 	use chrono::NaiveDate;
 
 	#[test]
@@ -110,4 +128,16 @@ mod tests {
 	fn test_parse_date_fails_invalid_separator() {
 		assert_eq!(parse_date("2011-11/19"), None);
 	}
+
+	#[test]
+	fn test_serialize_date() {
+		let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+		assert_eq!(serialize_date(date), "2011-11-18");
+	}
+
+	#[test]
+	fn test_serialize_date_pads_year() {
+		let date = NaiveDate::from_ymd_opt(12, 1, 2).unwrap();
+		assert_eq!(serialize_date(date), "0012-01-02");
+	}
 }
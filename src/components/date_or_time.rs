@@ -0,0 +1,216 @@
+use crate::tokens::{TOKEN_SPACE, TOKEN_T};
+use crate::{
+	parse_date_component, parse_time_component, parse_timezone_offset_component,
+	to_utc_datetime,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use whatwg_infra::trim_ascii_whitespace;
+
+/// Where a date or time string was obtained from, which decides
+/// how strictly surrounding whitespace is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ParseMode {
+	/// The string is the value of an attribute, such as the `datetime`
+	/// attribute of a `<time>` element. No whitespace is allowed.
+	#[default]
+	Attribute,
+	/// The string is the text content of an element, such as a `<time>`
+	/// element without a `datetime` attribute. Leading and trailing
+	/// ASCII whitespace is skipped.
+	Content,
+}
+
+impl ParseMode {
+	/// Applies the whitespace rules of this mode to a string.
+	#[inline]
+	pub(crate) fn prepare(self, s: &str) -> &str {
+		match self {
+			ParseMode::Attribute => s,
+			ParseMode::Content => trim_ascii_whitespace(s),
+		}
+	}
+}
+
+/// The result of [parsing a date or time string][whatwg-html-parse], which
+/// is either a date, a time, or a global datetime.
+///
+/// [whatwg-html-parse]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#parse-a-date-or-time-string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateOrTime {
+	Date(NaiveDate),
+	Time(NaiveTime),
+	GlobalDateTime(DateTime<Utc>),
+}
+
+/// Parse a string that is either a date, a time, or a global datetime
+///
+/// This follows the rules for [parsing a date or time string][whatwg-html-parse]
+/// per [WHATWG HTML Standard § 2.3.5.10 Dates and times][whatwg-html-dates-times].
+///
+/// With [`ParseMode::Content`], leading and trailing ASCII whitespace is skipped,
+/// which is how the text content of a `<time>` element without
+/// a `datetime` attribute is interpreted.
+///
+/// # Examples
+/// ```
+/// use chrono::{NaiveDate, NaiveTime};
+/// use whatwg_datetime::{parse_date_or_time, DateOrTime, ParseMode};
+///
+/// assert_eq!(
+///     parse_date_or_time("2011-11-18", ParseMode::Attribute),
+///     Some(DateOrTime::Date(NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()))
+/// );
+/// assert_eq!(
+///     parse_date_or_time(" 14:54 ", ParseMode::Content),
+///     Some(DateOrTime::Time(NaiveTime::from_hms_opt(14, 54, 0).unwrap()))
+/// );
+/// assert_eq!(parse_date_or_time(" 14:54 ", ParseMode::Attribute), None);
+/// ```
+///
+/// [whatwg-html-dates-times]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#dates-and-times
+/// [whatwg-html-parse]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#parse-a-date-or-time-string
+pub fn parse_date_or_time(s: &str, mode: ParseMode) -> Option<DateOrTime> {
+	let s = mode.prepare(s);
+
+	// Step 1, 2, 3
+	let mut position = 0usize;
+	let start_position = position;
+
+	// Step 4, 5
+	let mut time_present = true;
+	let date = parse_date_component(s, &mut position);
+	let date_present = date.is_some();
+
+	// Step 6, 7
+	if date_present {
+		if matches!(s.chars().nth(position), Some(TOKEN_T) | Some(TOKEN_SPACE)) {
+			position += 1;
+		} else {
+			time_present = false;
+		}
+	} else {
+		position = start_position;
+	}
+
+	// Step 8
+	let time = if time_present {
+		Some(parse_time_component(s, &mut position)?)
+	} else {
+		None
+	};
+
+	// Step 9, 10
+	let timezone_offset = match (date, time) {
+		(Some(_), Some(_)) => {
+			if position >= s.len() {
+				return None;
+			}
+			Some(parse_timezone_offset_component(s, &mut position)?)
+		}
+		_ => None,
+	};
+
+	// Step 11
+	if position < s.len() {
+		return None;
+	}
+
+	// Step 12, 13, 14
+	match (date, time, timezone_offset) {
+		(Some(date), None, _) => Some(DateOrTime::Date(date)),
+		(None, Some(time), _) => Some(DateOrTime::Time(time)),
+		(Some(date), Some(time), Some(timezone_offset)) => {
+			to_utc_datetime(date, time, timezone_offset).map(DateOrTime::GlobalDateTime)
+		}
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_date_or_time, DateOrTime, ParseMode};
+	use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+	#[test]
+	fn test_parse_date_or_time_date() {
+		assert_eq!(
+			parse_date_or_time("2011-11-18", ParseMode::Attribute),
+			Some(DateOrTime::Date(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+			))
+		);
+	}
+
+	#[test]
+	fn test_parse_date_or_time_time() {
+		assert_eq!(
+			parse_date_or_time("14:54:39", ParseMode::Attribute),
+			Some(DateOrTime::Time(
+				NaiveTime::from_hms_opt(14, 54, 39).unwrap()
+			))
+		);
+	}
+
+	#[test]
+	fn test_parse_date_or_time_global_datetime() {
+		assert_eq!(
+			parse_date_or_time("2011-11-18T14:54-08:00", ParseMode::Attribute),
+			Some(DateOrTime::GlobalDateTime(
+				DateTime::<Utc>::from_naive_utc_and_offset(
+					NaiveDateTime::new(
+						NaiveDate::from_ymd_opt(2011, 11, 18).unwrap(),
+						NaiveTime::from_hms_opt(22, 54, 0).unwrap(),
+					),
+					Utc
+				)
+			))
+		);
+	}
+
+	#[test]
+	fn test_parse_date_or_time_fails_local_datetime() {
+		assert_eq!(
+			parse_date_or_time("2011-11-18T14:54", ParseMode::Attribute),
+			None
+		);
+	}
+
+	#[test]
+	fn test_parse_date_or_time_fails_trailing_garbage() {
+		assert_eq!(
+			parse_date_or_time("2011-11-18x", ParseMode::Attribute),
+			None
+		);
+		assert_eq!(parse_date_or_time("14:54x", ParseMode::Attribute), None);
+	}
+
+	#[test]
+	fn test_parse_date_or_time_content_skips_whitespace() {
+		assert_eq!(
+			parse_date_or_time("\n\t2011-11-18 \r\n", ParseMode::Content),
+			Some(DateOrTime::Date(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+			))
+		);
+	}
+
+	#[test]
+	fn test_parse_date_or_time_attribute_rejects_whitespace() {
+		assert_eq!(
+			parse_date_or_time(" 2011-11-18", ParseMode::Attribute),
+			None
+		);
+		assert_eq!(
+			parse_date_or_time("2011-11-18 ", ParseMode::Attribute),
+			None
+		);
+	}
+
+	#[test]
+	fn test_parse_date_or_time_content_rejects_inner_whitespace() {
+		assert_eq!(
+			parse_date_or_time("2011-11-18  14:54Z", ParseMode::Content),
+			None
+		);
+	}
+}
@@ -0,0 +1,259 @@
+use crate::utils::collect_ascii_digits;
+use crate::{
+	parse_date, parse_duration, parse_format, parse_global_datetime, parse_local_datetime,
+	parse_month, parse_time, parse_timezone_offset, parse_week, parse_yearless_date,
+	serialize_date, serialize_duration, serialize_global_datetime, serialize_local_datetime,
+	serialize_month, serialize_time, serialize_timezone_offset, serialize_week,
+	serialize_yearless_date, DateOrTime, InputValue, ParseMode, TimeZoneOffset, YearMonth,
+	YearWeek, YearlessDate,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+/// Any value that can be described by the datetime microsyntaxes,
+/// such as the [datetime value][whatwg-html-datetime-value] of a `<time>` element.
+///
+/// [whatwg-html-datetime-value]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#concept-time-datetime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WhatwgDateTime {
+	Month(YearMonth),
+	Date(NaiveDate),
+	YearlessDate(YearlessDate),
+	Time(NaiveTime),
+	LocalDateTime(NaiveDateTime),
+	TimeZoneOffset(TimeZoneOffset),
+	GlobalDateTime(DateTime<Utc>),
+	Week(YearWeek),
+	Year(i32),
+	Duration(Duration),
+}
+
+impl WhatwgDateTime {
+	/// Serializes this value into the normalized string of its microsyntax.
+	///
+	/// Global datetimes are normalized to UTC, years are zero-padded
+	/// to at least four digits, and durations use the ISO 8601 based form.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::{parse_datetime_value, ParseMode};
+	///
+	/// let value = parse_datetime_value("2011-11-18 14:54", ParseMode::Attribute).unwrap();
+	/// assert_eq!(value.serialize(), "2011-11-18T14:54");
+	///
+	/// let value = parse_datetime_value("+0530", ParseMode::Attribute).unwrap();
+	/// assert_eq!(value.serialize(), "+05:30");
+	/// ```
+	pub fn serialize(&self) -> String {
+		match *self {
+			WhatwgDateTime::Month(year_month) => serialize_month(year_month),
+			WhatwgDateTime::Date(date) => serialize_date(date),
+			WhatwgDateTime::YearlessDate(yearless_date) => {
+				serialize_yearless_date(yearless_date)
+			}
+			WhatwgDateTime::Time(time) => serialize_time(time),
+			WhatwgDateTime::LocalDateTime(datetime) => {
+				serialize_local_datetime(datetime)
+			}
+			WhatwgDateTime::TimeZoneOffset(offset) => serialize_timezone_offset(offset),
+			WhatwgDateTime::GlobalDateTime(datetime) => {
+				serialize_global_datetime(datetime)
+			}
+			WhatwgDateTime::Week(year_week) => serialize_week(year_week),
+			WhatwgDateTime::Year(year) => format!("{:04}", year),
+			WhatwgDateTime::Duration(duration) => serialize_duration(duration),
+		}
+	}
+}
+
+impl From<InputValue> for WhatwgDateTime {
+	fn from(value: InputValue) -> Self {
+		match value {
+			InputValue::Date(date) => WhatwgDateTime::Date(date),
+			InputValue::Month(year_month) => WhatwgDateTime::Month(year_month),
+			InputValue::Week(year_week) => WhatwgDateTime::Week(year_week),
+			InputValue::Time(time) => WhatwgDateTime::Time(time),
+			InputValue::DatetimeLocal(datetime) => {
+				WhatwgDateTime::LocalDateTime(datetime)
+			}
+		}
+	}
+}
+
+impl From<DateOrTime> for WhatwgDateTime {
+	fn from(value: DateOrTime) -> Self {
+		match value {
+			DateOrTime::Date(date) => WhatwgDateTime::Date(date),
+			DateOrTime::Time(time) => WhatwgDateTime::Time(time),
+			DateOrTime::GlobalDateTime(datetime) => {
+				WhatwgDateTime::GlobalDateTime(datetime)
+			}
+		}
+	}
+}
+
+/// Parse the [datetime value][whatwg-html-datetime-value] of a `<time>` element
+///
+/// This tries each of the datetime microsyntaxes in the order given by
+/// the [machine-readable equivalent][whatwg-html-time] algorithm of
+/// the `<time>` element, and returns the first one that matches.
+///
+/// Use [`ParseMode::Attribute`] for the `datetime` attribute,
+/// and [`ParseMode::Content`] for the text content of the element.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_datetime_value, ParseMode, WhatwgDateTime, YearMonth};
+///
+/// assert_eq!(
+///     parse_datetime_value("2011-11", ParseMode::Attribute),
+///     Some(WhatwgDateTime::Month(YearMonth::new_opt(2011, 11).unwrap()))
+/// );
+/// assert_eq!(
+///     parse_datetime_value(" 2011 ", ParseMode::Content),
+///     Some(WhatwgDateTime::Year(2011))
+/// );
+/// ```
+///
+/// [whatwg-html-datetime-value]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#concept-time-datetime
+/// [whatwg-html-time]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-time-element
+pub fn parse_datetime_value(s: &str, mode: ParseMode) -> Option<WhatwgDateTime> {
+	let s = mode.prepare(s);
+	if s.is_empty() {
+		return None;
+	}
+
+	parse_month(s)
+		.map(WhatwgDateTime::Month)
+		.or_else(|| parse_date(s).map(WhatwgDateTime::Date))
+		.or_else(|| parse_yearless_date(s).map(WhatwgDateTime::YearlessDate))
+		.or_else(|| parse_time(s).map(WhatwgDateTime::Time))
+		.or_else(|| parse_local_datetime(s).map(WhatwgDateTime::LocalDateTime))
+		.or_else(|| parse_timezone_offset(s).map(WhatwgDateTime::TimeZoneOffset))
+		.or_else(|| parse_global_datetime(s).map(WhatwgDateTime::GlobalDateTime))
+		.or_else(|| parse_week(s).map(WhatwgDateTime::Week))
+		.or_else(|| parse_year(s).map(WhatwgDateTime::Year))
+		.or_else(|| parse_duration(s).map(WhatwgDateTime::Duration))
+}
+
+/// Parses a year, consisting of four or more ASCII digits,
+/// at least one of which is not `0`.
+#[inline]
+fn parse_year(s: &str) -> Option<i32> {
+	parse_format(s, parse_year_component)
+}
+
+/// Parses a year component at a given position, as in [`parse_year`].
+pub(crate) fn parse_year_component(s: &str, position: &mut usize) -> Option<i32> {
+	let parsed_year = collect_ascii_digits(s, position);
+	if parsed_year.len() < 4 {
+		return None;
+	}
+
+	let year = parsed_year.parse::<i32>().ok()?;
+	if year == 0 {
+		return None;
+	}
+
+	Some(year)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_datetime_value, WhatwgDateTime};
+	use crate::{ParseMode, TimeZoneOffset, YearMonth, YearWeek, YearlessDate};
+	use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+	#[test]
+	fn test_parse_datetime_value_each_kind() {
+		let parse = |s| parse_datetime_value(s, ParseMode::Attribute);
+
+		assert_eq!(
+			parse("2011-11"),
+			Some(WhatwgDateTime::Month(YearMonth::new(2011, 11)))
+		);
+		assert_eq!(
+			parse("2011-11-18"),
+			Some(WhatwgDateTime::Date(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+			))
+		);
+		assert_eq!(
+			parse("11-18"),
+			Some(WhatwgDateTime::YearlessDate(YearlessDate::new(11, 18)))
+		);
+		assert_eq!(
+			parse("14:54"),
+			Some(WhatwgDateTime::Time(
+				NaiveTime::from_hms_opt(14, 54, 0).unwrap()
+			))
+		);
+		assert_eq!(
+			parse("2011-11-18T14:54"),
+			Some(WhatwgDateTime::LocalDateTime(NaiveDateTime::new(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap(),
+				NaiveTime::from_hms_opt(14, 54, 0).unwrap(),
+			)))
+		);
+		assert_eq!(
+			parse("+05:30"),
+			Some(WhatwgDateTime::TimeZoneOffset(TimeZoneOffset::new(5, 30)))
+		);
+		assert!(matches!(
+			parse("2011-11-18T14:54Z"),
+			Some(WhatwgDateTime::GlobalDateTime(_))
+		));
+		assert_eq!(
+			parse("2011-W47"),
+			Some(WhatwgDateTime::Week(YearWeek::new(2011, 47)))
+		);
+		assert_eq!(parse("0011"), Some(WhatwgDateTime::Year(11)));
+		assert_eq!(
+			parse("4h 18m 3s"),
+			Some(WhatwgDateTime::Duration(Duration::seconds(15483)))
+		);
+	}
+
+	#[test]
+	fn test_serialize_datetime_value_round_trips() {
+		for s in [
+			"2011-11",
+			"2011-11-18",
+			"11-18",
+			"14:54:39.929",
+			"2011-11-18T14:54",
+			"-08:00",
+			"2011-11-18T14:54Z",
+			"2011-W47",
+			"0011",
+			"PT4H18M3S",
+		] {
+			let value = parse_datetime_value(s, ParseMode::Attribute).unwrap();
+			assert_eq!(value.serialize(), s);
+		}
+	}
+
+	#[test]
+	fn test_parse_datetime_value_fails() {
+		assert_eq!(parse_datetime_value("", ParseMode::Attribute), None);
+		assert_eq!(parse_datetime_value("0000", ParseMode::Attribute), None);
+		assert_eq!(parse_datetime_value("201", ParseMode::Attribute), None);
+		assert_eq!(
+			parse_datetime_value("999999-W01", ParseMode::Attribute),
+			None
+		);
+		assert_eq!(
+			parse_datetime_value("next tuesday", ParseMode::Content),
+			None
+		);
+	}
+
+	#[test]
+	fn test_parse_datetime_value_content_mode() {
+		assert_eq!(
+			parse_datetime_value("\n  2011-11  \n", ParseMode::Content),
+			Some(WhatwgDateTime::Month(YearMonth::new(2011, 11)))
+		);
+		assert_eq!(parse_datetime_value(" 2011-11", ParseMode::Attribute), None);
+		assert_eq!(parse_datetime_value("   ", ParseMode::Content), None);
+	}
+}
@@ -0,0 +1,369 @@
+use crate::parse_format;
+use crate::tokens::{
+	TOKEN_ABBR_DAY, TOKEN_ABBR_HOUR, TOKEN_ABBR_MIN, TOKEN_ABBR_SEC, TOKEN_ABBR_WEEK,
+	TOKEN_DOT, TOKEN_P, TOKEN_T,
+};
+use crate::utils::collect_ascii_digits;
+use chrono::Duration;
+use whatwg_infra::collect_codepoints;
+
+/// Parse a duration, written either in the ISO 8601 based form (`PT4H18M3S`),
+/// or as one or more duration time components (`4h 18m 3s`)
+///
+/// This only accepts [valid duration strings][whatwg-html-valid]
+/// per [WHATWG HTML Standard § 2.3.5.11 Durations][whatwg-html-durations].
+/// Fractions of a second are limited to milliseconds.
+///
+/// # Examples
+/// ```
+/// use chrono::Duration;
+/// use whatwg_datetime::parse_duration;
+///
+/// assert_eq!(parse_duration("PT4H18M3S"), Some(Duration::seconds(15483)));
+/// assert_eq!(parse_duration("4h 18m 3s"), Some(Duration::seconds(15483)));
+/// assert_eq!(parse_duration("P1D"), Some(Duration::days(1)));
+/// assert_eq!(parse_duration("1w 2.5s"), Some(Duration::milliseconds(604_802_500)));
+///
+/// assert_eq!(parse_duration("P1M"), None); // months have no fixed length
+/// assert_eq!(parse_duration("3h 4h"), None); // each scale may only be used once
+/// ```
+///
+/// [whatwg-html-durations]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#durations
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-duration-string
+#[inline]
+pub fn parse_duration(s: &str) -> Option<Duration> {
+	parse_format(s, parse_duration_component)
+}
+
+/// Low-level function for parsing a duration at a given position
+///
+/// This consumes the longest [valid duration string][whatwg-html-valid]
+/// starting at the given position.
+///
+/// > **Note**:
+/// > This function exposes a lower-level API than [`parse_duration`]. More than likely,
+/// > you will want to use [`parse_duration`] instead.
+///
+/// # Examples
+/// ```
+/// use chrono::Duration;
+/// use whatwg_datetime::parse_duration_component;
+///
+/// let mut position = 0usize;
+/// let duration = parse_duration_component("2h 30m left", &mut position);
+///
+/// assert_eq!(duration, Some(Duration::minutes(150)));
+/// assert_eq!(position, 6);
+/// ```
+///
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-duration-string
+pub fn parse_duration_component(s: &str, position: &mut usize) -> Option<Duration> {
+	let milliseconds = if s.chars().nth(*position) == Some(TOKEN_P) {
+		parse_iso_duration(s, position)?
+	} else {
+		parse_duration_time_components(s, position)?
+	};
+
+	Some(Duration::milliseconds(milliseconds))
+}
+
+/// Serialize a [`Duration`] as a [valid duration string][whatwg-html-valid],
+/// in the ISO 8601 based form
+///
+/// Zero fields are left out, and the seconds are serialized as the shortest
+/// possible string. Durations are truncated to milliseconds, and as a valid
+/// duration string can not be negative, the sign of a duration is ignored.
+///
+/// # Examples
+/// ```
+/// use chrono::Duration;
+/// use whatwg_datetime::serialize_duration;
+///
+/// assert_eq!(serialize_duration(Duration::seconds(15483)), "PT4H18M3S");
+/// assert_eq!(serialize_duration(Duration::milliseconds(90_061_500)), "P1DT1H1M1.5S");
+/// assert_eq!(serialize_duration(Duration::zero()), "PT0S");
+/// ```
+///
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-duration-string
+pub fn serialize_duration(duration: Duration) -> String {
+	let milliseconds = duration.num_milliseconds().unsigned_abs();
+	let seconds = milliseconds / 1000;
+	let (days, hours, minutes) = (
+		seconds / 86_400,
+		seconds % 86_400 / 3600,
+		seconds % 3600 / 60,
+	);
+	let (seconds, milliseconds) = (seconds % 60, milliseconds % 1000);
+
+	let mut serialized = String::from(TOKEN_P);
+	if days != 0 {
+		serialized.push_str(&format!("{}{}", days, TOKEN_ABBR_DAY));
+	}
+	if hours == 0 && minutes == 0 && seconds == 0 && milliseconds == 0 {
+		if days == 0 {
+			serialized.push_str(&format!("{}0{}", TOKEN_T, TOKEN_ABBR_SEC));
+		}
+		return serialized;
+	}
+
+	serialized.push(TOKEN_T);
+	if hours != 0 {
+		serialized.push_str(&format!("{}{}", hours, TOKEN_ABBR_HOUR));
+	}
+	if minutes != 0 {
+		serialized.push_str(&format!("{}{}", minutes, TOKEN_ABBR_MIN));
+	}
+	if seconds != 0 || milliseconds != 0 {
+		serialized.push_str(&seconds.to_string());
+		if milliseconds != 0 {
+			let fraction = format!("{:03}", milliseconds);
+			serialized.push(TOKEN_DOT);
+			serialized.push_str(fraction.trim_end_matches('0'));
+		}
+		serialized.push(TOKEN_ABBR_SEC);
+	}
+
+	serialized
+}
+
+/// The unit of a field of a duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DurationScale {
+	Weeks,
+	Days,
+	Hours,
+	Minutes,
+	Seconds,
+}
+
+impl DurationScale {
+	fn from_char(c: char) -> Option<Self> {
+		match c.to_ascii_uppercase() {
+			TOKEN_ABBR_WEEK => Some(DurationScale::Weeks),
+			TOKEN_ABBR_DAY => Some(DurationScale::Days),
+			TOKEN_ABBR_HOUR => Some(DurationScale::Hours),
+			TOKEN_ABBR_MIN => Some(DurationScale::Minutes),
+			TOKEN_ABBR_SEC => Some(DurationScale::Seconds),
+			_ => None,
+		}
+	}
+
+	fn designator(self) -> char {
+		match self {
+			DurationScale::Weeks => TOKEN_ABBR_WEEK,
+			DurationScale::Days => TOKEN_ABBR_DAY,
+			DurationScale::Hours => TOKEN_ABBR_HOUR,
+			DurationScale::Minutes => TOKEN_ABBR_MIN,
+			DurationScale::Seconds => TOKEN_ABBR_SEC,
+		}
+	}
+
+	fn milliseconds(self) -> i64 {
+		match self {
+			DurationScale::Weeks => 604_800_000,
+			DurationScale::Days => 86_400_000,
+			DurationScale::Hours => 3_600_000,
+			DurationScale::Minutes => 60_000,
+			DurationScale::Seconds => 1000,
+		}
+	}
+}
+
+/// Parses `P`, followed by an optional number of days, then an optional `T`
+/// followed by hours, minutes and seconds, each of which are also optional.
+/// At least one field must be present.
+fn parse_iso_duration(s: &str, position: &mut usize) -> Option<i64> {
+	let mut cursor = *position + 1;
+	let mut milliseconds = 0i64;
+	let mut fields = 0usize;
+
+	let mut field_end = cursor;
+	if let Some(days) = parse_iso_duration_field(s, &mut field_end, DurationScale::Days) {
+		milliseconds = days;
+		fields += 1;
+		cursor = field_end;
+	}
+
+	if s.chars().nth(cursor) == Some(TOKEN_T) {
+		let mut time_end = cursor + 1;
+		let mut time_fields = 0usize;
+		for scale in [
+			DurationScale::Hours,
+			DurationScale::Minutes,
+			DurationScale::Seconds,
+		] {
+			let mut field_end = time_end;
+			if let Some(value) = parse_iso_duration_field(s, &mut field_end, scale) {
+				milliseconds = milliseconds.checked_add(value)?;
+				time_fields += 1;
+				time_end = field_end;
+			}
+		}
+
+		// A `T` must be followed by at least one field
+		if time_fields > 0 {
+			fields += time_fields;
+			cursor = time_end;
+		}
+	}
+
+	if fields == 0 {
+		return None;
+	}
+
+	*position = cursor;
+	Some(milliseconds)
+}
+
+fn parse_iso_duration_field(s: &str, position: &mut usize, scale: DurationScale) -> Option<i64> {
+	let whole = collect_ascii_digits(s, position);
+	let fraction = match scale {
+		DurationScale::Seconds => collect_fraction(s, position)?,
+		_ => None,
+	};
+
+	if s.chars().nth(*position) != Some(scale.designator()) {
+		return None;
+	}
+	*position += 1;
+
+	to_milliseconds(&whole, fraction.as_deref(), scale)
+}
+
+/// Parses one or more duration time components, separated by
+/// optional ASCII whitespace, each with a different scale.
+fn parse_duration_time_components(s: &str, position: &mut usize) -> Option<i64> {
+	let mut milliseconds = 0i64;
+	let mut scales = Vec::with_capacity(5);
+	loop {
+		let mut component_end = *position;
+		match parse_duration_time_component(s, &mut component_end) {
+			Some((scale, value)) if !scales.contains(&scale) => {
+				milliseconds = milliseconds.checked_add(value)?;
+				scales.push(scale);
+				*position = component_end;
+			}
+			_ => break,
+		}
+	}
+
+	if scales.is_empty() {
+		return None;
+	}
+
+	Some(milliseconds)
+}
+
+fn parse_duration_time_component(s: &str, position: &mut usize) -> Option<(DurationScale, i64)> {
+	collect_codepoints(s, position, |c| c.is_ascii_whitespace());
+	let whole = collect_ascii_digits(s, position);
+	let fraction = collect_fraction(s, position)?;
+	collect_codepoints(s, position, |c| c.is_ascii_whitespace());
+
+	let scale = DurationScale::from_char(s.chars().nth(*position)?)?;
+	*position += 1;
+
+	// Only seconds may have a fraction
+	if fraction.is_some() && scale != DurationScale::Seconds {
+		return None;
+	}
+
+	Some((scale, to_milliseconds(&whole, fraction.as_deref(), scale)?))
+}
+
+/// Collects an optional `.` followed by one, two or three ASCII digits.
+///
+/// This returns `None` if there is a `.` not followed by a valid fraction,
+/// and `Some(None)` if there is no fraction at all.
+fn collect_fraction(s: &str, position: &mut usize) -> Option<Option<String>> {
+	if s.chars().nth(*position) != Some(TOKEN_DOT) {
+		return Some(None);
+	}
+
+	*position += 1;
+	let fraction = collect_ascii_digits(s, position);
+	if !(1..=3).contains(&fraction.len()) {
+		return None;
+	}
+
+	Some(Some(fraction))
+}
+
+fn to_milliseconds(whole: &str, fraction: Option<&str>, scale: DurationScale) -> Option<i64> {
+	if whole.is_empty() {
+		return None;
+	}
+
+	let whole = whole
+		.parse::<i64>()
+		.ok()?
+		.checked_mul(scale.milliseconds())?;
+	let fraction = match fraction {
+		Some(fraction) => format!("{:0<3}", fraction).parse::<i64>().ok()?,
+		None => 0,
+	};
+
+	whole.checked_add(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_duration, parse_duration_component, serialize_duration};
+	use chrono::Duration;
+
+	#[test]
+	fn test_parse_duration_iso_form() {
+		assert_eq!(parse_duration("PT0S"), Some(Duration::zero()));
+		assert_eq!(parse_duration("P3D"), Some(Duration::days(3)));
+		assert_eq!(parse_duration("PT2H"), Some(Duration::hours(2)));
+		assert_eq!(
+			parse_duration("P1DT2H3M4.05S"),
+			Some(Duration::milliseconds(93_784_050))
+		);
+	}
+
+	#[test]
+	fn test_parse_duration_time_components() {
+		assert_eq!(parse_duration("4h18m3s"), Some(Duration::seconds(15483)));
+		assert_eq!(parse_duration(" 3 S 4 h"), Some(Duration::seconds(14403)));
+		assert_eq!(parse_duration("2W"), Some(Duration::weeks(2)));
+		assert_eq!(parse_duration("0.5s"), Some(Duration::milliseconds(500)));
+	}
+
+	#[test]
+	fn test_parse_duration_fails() {
+		assert_eq!(parse_duration(""), None);
+		assert_eq!(parse_duration("P"), None);
+		assert_eq!(parse_duration("PT"), None);
+		assert_eq!(parse_duration("P1DT"), None);
+		assert_eq!(parse_duration("P1W"), None);
+		assert_eq!(parse_duration("PT1S1M"), None);
+		assert_eq!(parse_duration("pt1s"), None);
+		assert_eq!(parse_duration("PT1.5M"), None);
+		assert_eq!(parse_duration("1.5h"), None);
+		assert_eq!(parse_duration("1.0005s"), None);
+		assert_eq!(parse_duration("1s "), None);
+		assert_eq!(parse_duration("5"), None);
+		assert_eq!(parse_duration("99999999999999999999s"), None);
+	}
+
+	#[test]
+	fn test_parse_duration_component_stops_at_longest_match() {
+		let mut position = 0usize;
+		let duration = parse_duration_component("P1DT next", &mut position);
+		assert_eq!(duration, Some(Duration::days(1)));
+		assert_eq!(position, 3);
+
+		let mut position = 0usize;
+		let duration = parse_duration_component("1h 2h", &mut position);
+		assert_eq!(duration, Some(Duration::hours(1)));
+		assert_eq!(position, 2);
+	}
+
+	#[test]
+	fn test_serialize_duration_round_trips() {
+		for s in ["PT0S", "P3D", "PT2H", "P1DT2H3M4.05S", "PT0.5S", "P10DT1M"] {
+			assert_eq!(serialize_duration(parse_duration(s).unwrap()), s);
+		}
+	}
+}
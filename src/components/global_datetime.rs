@@ -1,6 +1,9 @@
-use crate::tokens::{TOKEN_SPACE, TOKEN_T};
-use crate::{parse_date_component, parse_time_component, parse_timezone_offset_component};
-use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use crate::tokens::{TOKEN_SPACE, TOKEN_T, TOKEN_Z};
+use crate::{
+	parse_date_component, parse_time_component, parse_timezone_offset_component,
+	serialize_local_datetime, TimeZoneOffset,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
 /// Parse a [proleptic-Gregorian date][proleptic-greg] consisting
 /// of a date, time, and an optional time-zone offset
@@ -16,7 +19,7 @@ use chrono::{DateTime, Duration, NaiveDateTime, Utc};
 ///
 /// assert_eq!(
 ///     parse_global_datetime("2011-11-18T14:54Z"),
-///     Some(DateTime::<Utc>::from_utc(
+///     Some(DateTime::<Utc>::from_naive_utc_and_offset(
 ///         NaiveDateTime::new(
 ///             NaiveDate::from_ymd_opt(2011, 11, 18).unwrap(),
 ///             NaiveTime::from_hms_opt(14, 54, 0).unwrap(),
@@ -50,26 +53,63 @@ pub fn parse_global_datetime(s: &str) -> Option<DateTime<Utc>> {
 		return None;
 	}
 
-	let timezone_offset_as_duration =
-		Duration::minutes(timezone_offset.minute as i64 + timezone_offset.hour as i64 * 60);
-	let naive_datetime = NaiveDateTime::new(
-		date,
-		time.overflowing_sub_signed(timezone_offset_as_duration).0,
-	);
+	to_utc_datetime(date, time, timezone_offset)
+}
+
+/// Combines a date and a time with a time-zone offset into a datetime in UTC,
+/// carrying over into the previous or next day as needed.
+pub(crate) fn to_utc_datetime(
+	date: NaiveDate,
+	time: NaiveTime,
+	timezone_offset: TimeZoneOffset,
+) -> Option<DateTime<Utc>> {
+	let timezone_offset_as_duration = Duration::minutes(timezone_offset.total_minutes() as i64);
+	let naive_datetime =
+		NaiveDateTime::new(date, time).checked_sub_signed(timezone_offset_as_duration)?;
 
-	Some(DateTime::<Utc>::from_utc(naive_datetime, Utc))
+	Some(DateTime::<Utc>::from_naive_utc_and_offset(
+		naive_datetime,
+		Utc,
+	))
+}
+
+/// Serialize a global datetime as a
+/// [valid normalized forced-UTC global date and time string][whatwg-html-valid]
+///
+/// # Examples
+/// ```
+/// use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+/// use whatwg_datetime::serialize_global_datetime;
+///
+/// let datetime = DateTime::<Utc>::from_naive_utc_and_offset(
+///     NaiveDateTime::new(
+///         NaiveDate::from_ymd_opt(2011, 11, 18).unwrap(),
+///         NaiveTime::from_hms_opt(14, 54, 0).unwrap(),
+///     ),
+///     Utc,
+/// );
+/// assert_eq!(serialize_global_datetime(datetime), "2011-11-18T14:54Z");
+/// ```
+///
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-normalized-forced-utc-global-date-and-time-string
+pub fn serialize_global_datetime(datetime: DateTime<Utc>) -> String {
+	format!(
+		"{}{}",
+		serialize_local_datetime(datetime.naive_utc()),
+		TOKEN_Z
+	)
 }
 
 #[cfg(test)]
 mod tests {
-	use super::parse_global_datetime;
+	use super::{parse_global_datetime, serialize_global_datetime};
 	use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
 	#[test]
 	fn test_parse_global_datetime_t_hm() {
 		assert_eq!(
 			parse_global_datetime("2004-12-31T12:31"),
-			Some(DateTime::<Utc>::from_utc(
+			Some(DateTime::<Utc>::from_naive_utc_and_offset(
 				NaiveDateTime::new(
 					NaiveDate::from_ymd_opt(2004, 12, 31).unwrap(),
 					NaiveTime::from_hms_opt(12, 31, 0).unwrap(),
@@ -83,7 +123,7 @@ mod tests {
 	fn test_parse_global_datetime_t_hms() {
 		assert_eq!(
 			parse_global_datetime("2004-12-31T12:31:59"),
-			Some(DateTime::<Utc>::from_utc(
+			Some(DateTime::<Utc>::from_naive_utc_and_offset(
 				NaiveDateTime::new(
 					NaiveDate::from_ymd_opt(2004, 12, 31).unwrap(),
 					NaiveTime::from_hms_opt(12, 31, 59).unwrap(),
@@ -97,7 +137,7 @@ mod tests {
 	fn test_parse_global_datetime_t_hms_milliseconds() {
 		assert_eq!(
 			parse_global_datetime("2027-11-29T12:31:59.123"),
-			Some(DateTime::<Utc>::from_utc(
+			Some(DateTime::<Utc>::from_naive_utc_and_offset(
 				NaiveDateTime::new(
 					NaiveDate::from_ymd_opt(2027, 11, 29).unwrap(),
 					NaiveTime::from_hms_milli_opt(12, 31, 59, 123).unwrap(),
@@ -111,7 +151,7 @@ mod tests {
 	fn test_parse_global_datetime_t_hms_z() {
 		assert_eq!(
 			parse_global_datetime("2004-12-31T12:31:59Z"),
-			Some(DateTime::<Utc>::from_utc(
+			Some(DateTime::<Utc>::from_naive_utc_and_offset(
 				NaiveDateTime::new(
 					NaiveDate::from_ymd_opt(2004, 12, 31).unwrap(),
 					NaiveTime::from_hms_opt(12, 31, 59).unwrap(),
@@ -125,7 +165,7 @@ mod tests {
 	fn test_parse_global_datetime_space_hm() {
 		assert_eq!(
 			parse_global_datetime("2004-12-31 12:31"),
-			Some(DateTime::<Utc>::from_utc(
+			Some(DateTime::<Utc>::from_naive_utc_and_offset(
 				NaiveDateTime::new(
 					NaiveDate::from_ymd_opt(2004, 12, 31).unwrap(),
 					NaiveTime::from_hms_opt(12, 31, 0).unwrap(),
@@ -139,7 +179,7 @@ mod tests {
 	fn test_parse_global_datetime_space_hms() {
 		assert_eq!(
 			parse_global_datetime("2004-12-31 12:31:59"),
-			Some(DateTime::<Utc>::from_utc(
+			Some(DateTime::<Utc>::from_naive_utc_and_offset(
 				NaiveDateTime::new(
 					NaiveDate::from_ymd_opt(2004, 12, 31).unwrap(),
 					NaiveTime::from_hms_opt(12, 31, 59).unwrap(),
@@ -153,7 +193,7 @@ mod tests {
 	fn test_parse_global_datetime_space_hms_milliseconds() {
 		assert_eq!(
 			parse_global_datetime("2004-12-31 12:31:59.123"),
-			Some(DateTime::<Utc>::from_utc(
+			Some(DateTime::<Utc>::from_naive_utc_and_offset(
 				NaiveDateTime::new(
 					NaiveDate::from_ymd_opt(2004, 12, 31).unwrap(),
 					NaiveTime::from_hms_milli_opt(12, 31, 59, 123).unwrap(),
@@ -192,4 +232,43 @@ mod tests {
 	fn test_parse_global_datetime_fails_invalid_timezone_offset_2() {
 		assert_eq!(parse_global_datetime("1456-02-24T11:17C"), None);
 	}
+
+	#[test]
+	fn test_serialize_global_datetime() {
+		let datetime = parse_global_datetime("2004-12-31 12:31:59+05:30").unwrap();
+		assert_eq!(serialize_global_datetime(datetime), "2004-12-31T07:01:59Z");
+	}
+
+	#[test]
+	fn test_parse_global_datetime_offset_crosses_day() {
+		// The offset used to be subtracted from the time alone, which wrapped
+		// around midnight without changing the date, so these were parsed as
+		// 2004-12-31T03:00Z and 2005-01-01T19:00Z.
+		assert_eq!(
+			parse_global_datetime("2004-12-31T22:00-05:00"),
+			Some(DateTime::<Utc>::from_naive_utc_and_offset(
+				NaiveDateTime::new(
+					NaiveDate::from_ymd_opt(2005, 1, 1).unwrap(),
+					NaiveTime::from_hms_opt(3, 0, 0).unwrap(),
+				),
+				Utc
+			))
+		);
+		assert_eq!(
+			parse_global_datetime("2005-01-01T00:30+05:30"),
+			Some(DateTime::<Utc>::from_naive_utc_and_offset(
+				NaiveDateTime::new(
+					NaiveDate::from_ymd_opt(2004, 12, 31).unwrap(),
+					NaiveTime::from_hms_opt(19, 0, 0).unwrap(),
+				),
+				Utc
+			))
+		);
+	}
+
+	#[test]
+	fn test_parse_global_datetime_fails_offset_crosses_out_of_range() {
+		// The last day that `NaiveDate` can represent has no next day
+		assert_eq!(parse_global_datetime("262142-12-31T23:00-05:00"), None);
+	}
 }
@@ -0,0 +1,193 @@
+use crate::tokens::{TOKEN_SOLIDUS, TOKEN_SPACE};
+use crate::utils::collect_ascii_digits;
+use crate::{
+	collect_day_and_validate, collect_month_and_validate, parse_format, parse_time_component,
+};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+
+/// Parse a [`document.lastModified`][whatwg-html-last-modified] string,
+/// in the format of `MM/DD/YYYY hh:mm:ss`
+///
+/// Every component is zero-padded, the year has exactly four digits, and the
+/// hour is from `00` to `23`. The string is in the user's local time zone, which
+/// it does not include, so the result is a [`NaiveDateTime`].
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::parse_last_modified;
+///
+/// assert_eq!(
+///     parse_last_modified("11/18/2011 14:54:39"),
+///     NaiveDate::from_ymd_opt(2011, 11, 18).and_then(|date| date.and_hms_opt(14, 54, 39))
+/// );
+/// assert_eq!(parse_last_modified("02/29/2012 00:00:00").is_some(), true);
+/// assert_eq!(parse_last_modified("02/29/2011 00:00:00"), None); // 2011 is not a leap year
+/// assert_eq!(parse_last_modified("18/11/2011 14:54:39"), None); // invalid month
+/// assert_eq!(parse_last_modified("11/18/2011 14:54"), None);    // seconds are required
+/// assert_eq!(parse_last_modified("2011-11-18 14:54:39"), None); // not `MM/DD/YYYY`
+/// ```
+///
+/// [whatwg-html-last-modified]: https://html.spec.whatwg.org/multipage/dom.html#dom-document-lastmodified
+pub fn parse_last_modified(s: &str) -> Option<NaiveDateTime> {
+	let mut position = 0usize;
+	let month = collect_month_and_validate(s, &mut position)?;
+	if s.chars().nth(position) != Some(TOKEN_SOLIDUS) {
+		return None;
+	}
+	position += 1;
+
+	let day = collect_day_and_validate(s, &mut position, month)?;
+	if s.chars().nth(position) != Some(TOKEN_SOLIDUS) {
+		return None;
+	}
+	position += 1;
+
+	let parsed_year = collect_ascii_digits(s, &mut position);
+	if parsed_year.len() != 4 {
+		return None;
+	}
+	let year = parsed_year.parse::<i32>().ok()?;
+	if s.chars().nth(position) != Some(TOKEN_SPACE) {
+		return None;
+	}
+	position += 1;
+
+	// The time must be exactly `hh:mm:ss`, without the fractional seconds
+	// that a time component would otherwise accept.
+	let time = &s[position..];
+	let is_time_shape = time.len() == 8
+		&& time.bytes()
+			.enumerate()
+			.all(|(index, b)| index == 2 || index == 5 || b.is_ascii_digit());
+	if !is_time_shape {
+		return None;
+	}
+
+	let time = parse_format(time, parse_time_component)?;
+	Some(NaiveDate::from_ymd_opt(year, month, day)?.and_time(time))
+}
+
+/// Serialize a [`NaiveDateTime`] as a [`document.lastModified`][whatwg-html-last-modified]
+/// string, in the format of `MM/DD/YYYY hh:mm:ss`
+///
+/// Fractions of a second are truncated. This returns `None` for years
+/// outside of 0000 to 9999, which can not be written in this format.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::serialize_last_modified;
+///
+/// let datetime = NaiveDate::from_ymd_opt(2011, 11, 18)
+///     .unwrap()
+///     .and_hms_milli_opt(14, 54, 39, 929)
+///     .unwrap();
+/// assert_eq!(
+///     serialize_last_modified(datetime),
+///     Some(String::from("11/18/2011 14:54:39"))
+/// );
+///
+/// let datetime = NaiveDate::from_ymd_opt(10000, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// assert_eq!(serialize_last_modified(datetime), None);
+/// ```
+///
+/// [whatwg-html-last-modified]: https://html.spec.whatwg.org/multipage/dom.html#dom-document-lastmodified
+pub fn serialize_last_modified(datetime: NaiveDateTime) -> Option<String> {
+	if !(0..=9999).contains(&datetime.year()) {
+		return None;
+	}
+
+	Some(format!(
+		"{:02}/{:02}/{:04} {:02}:{:02}:{:02}",
+		datetime.month(),
+		datetime.day(),
+		datetime.year(),
+		datetime.hour(),
+		datetime.minute(),
+		datetime.second(),
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_last_modified, serialize_last_modified};
+	use chrono::{NaiveDate, NaiveDateTime};
+
+	fn datetime(year: i32, month: u32, day: u32, h: u32, m: u32, s: u32) -> NaiveDateTime {
+		NaiveDate::from_ymd_opt(year, month, day)
+			.unwrap()
+			.and_hms_opt(h, m, s)
+			.unwrap()
+	}
+
+	#[test]
+	fn test_parse_last_modified() {
+		assert_eq!(
+			parse_last_modified("11/18/2011 14:54:39"),
+			Some(datetime(2011, 11, 18, 14, 54, 39))
+		);
+		assert_eq!(
+			parse_last_modified("01/01/0000 00:00:00"),
+			Some(datetime(0, 1, 1, 0, 0, 0))
+		);
+	}
+
+	#[test]
+	fn test_parse_last_modified_fails() {
+		for s in [
+			"",
+			"1/18/2011 14:54:39",
+			"11/8/2011 14:54:39",
+			"11/18/11 14:54:39",
+			"11/18/12011 14:54:39",
+			"13/18/2011 14:54:39",
+			"11/31/2011 14:54:39",
+			"11/18/2011 24:00:00",
+			"11/18/2011 14:60:39",
+			"11/18/2011 14:54:60",
+			"11/18/2011 14:54:3.",
+			"11/18/2011 14:54:39.929",
+			"11/18/2011T14:54:39",
+			"11/18/2011  14:54:39",
+			"11-18-2011 14:54:39",
+			"11/18/2011 14:54:39 ",
+		] {
+			assert_eq!(parse_last_modified(s), None, "{}", s);
+		}
+	}
+
+	#[test]
+	fn test_serialize_last_modified() {
+		assert_eq!(
+			serialize_last_modified(datetime(12, 1, 2, 3, 4, 5)),
+			Some(String::from("01/02/0012 03:04:05"))
+		);
+	}
+
+	#[test]
+	fn test_serialize_last_modified_fails_out_of_range_years() {
+		for year in [-1, -12, 10000, 262142] {
+			assert_eq!(
+				serialize_last_modified(datetime(year, 1, 2, 3, 4, 5)),
+				None,
+				"{}",
+				year
+			);
+		}
+		assert!(serialize_last_modified(datetime(0, 1, 1, 0, 0, 0)).is_some());
+		assert!(serialize_last_modified(datetime(9999, 12, 31, 23, 59, 59)).is_some());
+	}
+
+	#[test]
+	fn test_serialize_last_modified_round_trips() {
+		for s in [
+			"11/18/2011 14:54:39",
+			"02/29/2012 23:59:59",
+			"12/31/9999 00:00:00",
+		] {
+			let parsed = parse_last_modified(s).unwrap();
+			assert_eq!(serialize_last_modified(parsed).as_deref(), Some(s));
+		}
+	}
+}
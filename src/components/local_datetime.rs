@@ -1,5 +1,5 @@
 use crate::tokens::{TOKEN_SPACE, TOKEN_T};
-use crate::{parse_date_component, parse_time_component};
+use crate::{parse_date_component, parse_time_component, serialize_date, serialize_time};
 use chrono::NaiveDateTime;
 
 /// Parse a [proleptic-Gregorian date][proleptic-greg] consisting
@@ -46,9 +46,37 @@ pub fn parse_local_datetime(s: &str) -> Option<NaiveDateTime> {
 	Some(NaiveDateTime::new(date, time))
 }
 
+/// Serialize a local datetime as a
+/// [valid normalized local date and time string][whatwg-html-valid]
+///
+/// The date and time are always delimited by a `T`, and the time
+/// is serialized as the shortest possible string.
+///
+/// # Examples
+/// ```
+/// use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+/// use whatwg_datetime::serialize_local_datetime;
+///
+/// let datetime = NaiveDateTime::new(
+///     NaiveDate::from_ymd_opt(2011, 11, 18).unwrap(),
+///     NaiveTime::from_hms_opt(14, 54, 0).unwrap(),
+/// );
+/// assert_eq!(serialize_local_datetime(datetime), "2011-11-18T14:54");
+/// ```
+///
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-normalized-local-date-and-time-string
+pub fn serialize_local_datetime(datetime: NaiveDateTime) -> String {
+	format!(
+		"{}{}{}",
+		serialize_date(datetime.date()),
+		TOKEN_T,
+		serialize_time(datetime.time())
+	)
+}
+
 #[cfg(test)]
 mod tests {
-	use super::parse_local_datetime;
+	use super::{parse_local_datetime, serialize_local_datetime};
 	use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
 	#[test]
@@ -131,4 +159,16 @@ mod tests {
 	pub fn test_parse_local_datetime_fails_invalid_time() {
 		assert_eq!(parse_local_datetime("2011-11-18T14/54/39"), None);
 	}
+
+	#[test]
+	pub fn test_serialize_local_datetime() {
+		let datetime = NaiveDateTime::new(
+			NaiveDate::from_ymd_opt(2011, 11, 18).unwrap(),
+			NaiveTime::from_hms_milli_opt(14, 54, 39, 929).unwrap(),
+		);
+		assert_eq!(
+			serialize_local_datetime(datetime),
+			"2011-11-18T14:54:39.929"
+		);
+	}
 }
@@ -1,5 +1,9 @@
 mod date;
+mod date_or_time;
+mod datetime_value;
+mod duration;
 mod global_datetime;
+mod last_modified;
 mod local_datetime;
 mod month;
 mod time;
@@ -9,7 +13,11 @@ mod week;
 mod yearless_date;
 
 pub use self::date::*;
+pub use self::date_or_time::*;
+pub use self::datetime_value::*;
+pub use self::duration::*;
 pub use self::global_datetime::*;
+pub use self::last_modified::*;
 pub use self::local_datetime::*;
 pub use self::month::*;
 pub use self::time::*;
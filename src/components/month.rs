@@ -1,6 +1,9 @@
 use crate::tokens::TOKEN_HYPHEN;
-use crate::utils::{collect_ascii_digits, is_valid_month};
-use crate::{collect_month_and_validate, parse_format};
+use crate::utils::{collect_ascii_digits, is_valid_month, is_valid_year, max_days_in_month_year};
+use crate::{collect_month_and_validate, parse_format, ParseError};
+use chrono::{Datelike, NaiveDate};
+use std::fmt;
+use std::str::FromStr;
 
 /// A [proleptic-Gregorian date][proleptic-greg] consisting of a year and a month,
 /// with no time-zone or date information.
@@ -13,7 +16,7 @@ use crate::{collect_month_and_validate, parse_format};
 /// ```
 ///
 /// [proleptic-greg]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#proleptic-gregorian-date
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct YearMonth {
 	pub(crate) year: i32,
 	pub(crate) month: u32,
@@ -27,7 +30,7 @@ impl YearMonth {
 	/// Creates a new `YearMonth` from a year and a month number.
 	///
 	/// This asserts that:
-	/// - the year is greater than 0
+	/// - the year is greater than 0, and no later than the year of [`NaiveDate::MAX`]
 	/// - that the month number is between 1 and 12, inclusive
 	///
 	/// # Examples
@@ -37,9 +40,11 @@ impl YearMonth {
 	/// assert!(YearMonth::new_opt(2011, 11).is_some());
 	/// assert!(YearMonth::new_opt(2011, 0).is_none()); // Month number must be at least 1
 	/// assert!(YearMonth::new_opt(0, 1).is_none()); // Year number must be greater than 0
+	/// assert!(YearMonth::new_opt(-1, 1).is_none());
+	/// assert!(YearMonth::new_opt(300_000, 1).is_none()); // Year must have dates in chrono
 	/// ```
 	pub fn new_opt(year: i32, month: u32) -> Option<Self> {
-		if year == 0 {
+		if !is_valid_year(year) {
 			return None;
 		}
 
@@ -50,7 +55,8 @@ impl YearMonth {
 		Some(Self::new(year, month))
 	}
 
-	/// A year component. This is a number greater than 0.
+	/// A year component. This is a number greater than 0, and no later
+	/// than the year of [`NaiveDate::MAX`].
 	///
 	/// # Examples
 	/// ```
@@ -77,8 +83,287 @@ impl YearMonth {
 	pub const fn month(&self) -> u32 {
 		self.month
 	}
+
+	/// Creates a new `YearMonth` containing the given date.
+	///
+	/// This returns `None` if the year of the date is not greater than 0.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::YearMonth;
+	///
+	/// let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+	/// assert_eq!(YearMonth::from_date(date), YearMonth::new_opt(2011, 11));
+	/// ```
+	pub fn from_date(date: NaiveDate) -> Option<Self> {
+		Self::new_opt(date.year(), date.month())
+	}
+
+	/// The number of days in this month, taking leap years into account.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearMonth;
+	///
+	/// assert_eq!(YearMonth::new_opt(2012, 2).unwrap().days_in_month(), 29);
+	/// assert_eq!(YearMonth::new_opt(2011, 2).unwrap().days_in_month(), 28);
+	/// assert_eq!(YearMonth::new_opt(2011, 11).unwrap().days_in_month(), 30);
+	/// ```
+	pub fn days_in_month(&self) -> u32 {
+		// We call unwrap() here since a `YearMonth` can only be constructed
+		// with a valid month number, and a year greater than 0.
+		max_days_in_month_year(self.month, self.year as u32).unwrap()
+	}
+
+	/// The first day of this month.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::YearMonth;
+	///
+	/// let year_month = YearMonth::new_opt(2011, 11).unwrap();
+	/// assert_eq!(year_month.first_day(), NaiveDate::from_ymd_opt(2011, 11, 1).unwrap());
+	/// ```
+	#[inline]
+	pub fn first_day(&self) -> NaiveDate {
+		// We call unwrap() here since a `YearMonth` can only be constructed
+		// with a year that `NaiveDate` can represent, and a valid month number.
+		NaiveDate::from_ymd_opt(self.year, self.month, 1).unwrap()
+	}
+
+	/// The last day of this month.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::YearMonth;
+	///
+	/// let year_month = YearMonth::new_opt(2012, 2).unwrap();
+	/// assert_eq!(year_month.last_day(), NaiveDate::from_ymd_opt(2012, 2, 29).unwrap());
+	/// ```
+	#[inline]
+	pub fn last_day(&self) -> NaiveDate {
+		// We call unwrap() here for the same reasons as in `first_day()`.
+		NaiveDate::from_ymd_opt(self.year, self.month, self.days_in_month()).unwrap()
+	}
+
+	/// An iterator over every date in this month, in order.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::YearMonth;
+	///
+	/// let year_month = YearMonth::new_opt(2011, 2).unwrap();
+	/// let days: Vec<NaiveDate> = year_month.days().collect();
+	///
+	/// assert_eq!(days.len(), 28);
+	/// assert_eq!(days[27], NaiveDate::from_ymd_opt(2011, 2, 28).unwrap());
+	/// ```
+	pub fn days(&self) -> impl Iterator<Item = NaiveDate> {
+		// Each date is constructed directly, as in `first_day()`; see
+		// `utils::is_valid_year`.
+		let (year, month) = (self.year, self.month);
+		(1..=self.days_in_month())
+			.map(move |day| NaiveDate::from_ymd_opt(year, month, day).unwrap())
+	}
+
+	/// Adds a number of months, carrying over into following years as needed.
+	///
+	/// This returns `None` if the result would be out of range.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearMonth;
+	///
+	/// let year_month = YearMonth::new_opt(2011, 11).unwrap();
+	/// assert_eq!(year_month.checked_add_months(3), YearMonth::new_opt(2012, 2));
+	/// ```
+	pub fn checked_add_months(&self, months: u32) -> Option<Self> {
+		Self::from_months_since_epoch(
+			self.months_since_epoch().checked_add(i64::from(months))?,
+		)
+	}
+
+	/// Subtracts a number of months, carrying over into preceding years as needed.
+	///
+	/// This returns `None` if the result would be before January of year 1.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearMonth;
+	///
+	/// let year_month = YearMonth::new_opt(2012, 2).unwrap();
+	/// assert_eq!(year_month.checked_sub_months(3), YearMonth::new_opt(2011, 11));
+	/// assert_eq!(YearMonth::new_opt(1, 1).unwrap().checked_sub_months(1), None);
+	/// ```
+	pub fn checked_sub_months(&self, months: u32) -> Option<Self> {
+		Self::from_months_since_epoch(
+			self.months_since_epoch().checked_sub(i64::from(months))?,
+		)
+	}
+
+	/// The signed number of months from this month to another month.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearMonth;
+	///
+	/// let start = YearMonth::new_opt(2011, 11).unwrap();
+	/// let end = YearMonth::new_opt(2012, 2).unwrap();
+	///
+	/// assert_eq!(start.months_between(&end), 3);
+	/// assert_eq!(end.months_between(&start), -3);
+	/// ```
+	#[inline]
+	pub fn months_between(&self, other: &Self) -> i64 {
+		other.months_since_epoch() - self.months_since_epoch()
+	}
+
+	/// An iterator over every month from this month through `end`, inclusive.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearMonth;
+	///
+	/// let start = YearMonth::new_opt(2011, 11).unwrap();
+	/// let end = YearMonth::new_opt(2012, 1).unwrap();
+	///
+	/// assert_eq!(start.range_to(end).count(), 3);
+	/// ```
+	#[inline]
+	pub fn range_to(self, end: Self) -> YearMonthRange {
+		YearMonthRange::new(self, end)
+	}
+
+	/// The number of months since January of year 0, which makes
+	/// carrying between years a matter of integer arithmetic.
+	#[inline]
+	fn months_since_epoch(&self) -> i64 {
+		i64::from(self.year) * 12 + i64::from(self.month) - 1
+	}
+
+	fn from_months_since_epoch(months: i64) -> Option<Self> {
+		let year = i32::try_from(months.div_euclid(12)).ok()?;
+		let month = months.rem_euclid(12) as u32 + 1;
+		Self::new_opt(year, month)
+	}
+}
+
+impl Default for YearMonth {
+	/// January of year 1, `0001-01`.
+	#[inline]
+	fn default() -> Self {
+		Self::new(1, 1)
+	}
+}
+
+impl fmt::Display for YearMonth {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&serialize_month(*self))
+	}
+}
+
+impl FromStr for YearMonth {
+	type Err = ParseError;
+
+	/// Parses a string with [`parse_month`].
+	#[inline]
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		parse_month(s).ok_or(ParseError::Invalid)
+	}
+}
+
+impl TryFrom<&str> for YearMonth {
+	type Error = ParseError;
+
+	#[inline]
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		value.parse()
+	}
 }
 
+impl TryFrom<NaiveDate> for YearMonth {
+	type Error = ParseError;
+
+	#[inline]
+	fn try_from(value: NaiveDate) -> Result<Self, Self::Error> {
+		Self::from_date(value).ok_or(ParseError::Invalid)
+	}
+}
+
+/// An iterator over an inclusive range of [`YearMonth`]s.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{YearMonth, YearMonthRange};
+///
+/// let start = YearMonth::new_opt(2011, 11).unwrap();
+/// let end = YearMonth::new_opt(2012, 2).unwrap();
+/// let months: Vec<YearMonth> = YearMonthRange::new(start, end).collect();
+///
+/// assert_eq!(months.len(), 4);
+/// assert_eq!(months[2], YearMonth::new_opt(2012, 1).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YearMonthRange {
+	front: Option<YearMonth>,
+	back: YearMonth,
+}
+
+impl YearMonthRange {
+	/// Creates a new range from `start` through `end`, inclusive.
+	///
+	/// The range is empty if `start` comes after `end`.
+	pub fn new(start: YearMonth, end: YearMonth) -> Self {
+		Self {
+			front: if start <= end { Some(start) } else { None },
+			back: end,
+		}
+	}
+}
+
+impl Iterator for YearMonthRange {
+	type Item = YearMonth;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let current = self.front?;
+		if current == self.back {
+			self.front = None;
+		} else {
+			self.front = current.checked_add_months(1);
+		}
+
+		Some(current)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = match self.front {
+			Some(front) => front.months_between(&self.back) as usize + 1,
+			None => 0,
+		};
+		(len, Some(len))
+	}
+}
+
+impl DoubleEndedIterator for YearMonthRange {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let front = self.front?;
+		let current = self.back;
+		if current == front {
+			self.front = None;
+		} else {
+			self.back = current.checked_sub_months(1)?;
+		}
+
+		Some(current)
+	}
+}
+
+impl ExactSizeIterator for YearMonthRange {}
+
 /// Parse a [proleptic-Gregorian date][proleptic-greg] consisting of a year and a month,
 /// with no time-zone or date information
 ///
@@ -128,7 +413,7 @@ pub fn parse_month_component(s: &str, position: &mut usize) -> Option<YearMonth>
 	}
 
 	let year = parsed_year.parse::<i32>().ok()?;
-	if year == 0 {
+	if !is_valid_year(year) {
 		return None;
 	}
 
@@ -142,9 +427,30 @@ pub fn parse_month_component(s: &str, position: &mut usize) -> Option<YearMonth>
 	Some(YearMonth::new(year, month))
 }
 
+/// Serialize a [`YearMonth`] as a [valid month string][whatwg-html-valid],
+/// in the format of `YYYY-MM`
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{serialize_month, YearMonth};
+///
+/// let year_month = YearMonth::new_opt(2011, 11).unwrap();
+/// assert_eq!(serialize_month(year_month), "2011-11");
+/// ```
+///
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-month-string
+pub fn serialize_month(year_month: YearMonth) -> String {
+	format!("{:04}-{:02}", year_month.year, year_month.month)
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{parse_month, parse_month_component, YearMonth};
+	use super::{
+		parse_month, parse_month_component, serialize_month, YearMonth, YearMonthRange,
+	};
+	use crate::ParseError;
+	use chrono::{Datelike, NaiveDate};
+	use std::collections::HashMap;
 
 	#[test]
 	fn test_parse_month_string() {
@@ -211,4 +517,182 @@ mod tests {
 
 		assert_eq!(parsed, None);
 	}
+
+	#[test]
+	fn test_serialize_month() {
+		assert_eq!(serialize_month(YearMonth::new(2004, 12)), "2004-12");
+		assert_eq!(serialize_month(YearMonth::new(987, 1)), "0987-01");
+	}
+
+	#[test]
+	fn test_year_month_new_opt_fails_negative_year() {
+		assert_eq!(YearMonth::new_opt(-1, 1), None);
+	}
+
+	#[test]
+	fn test_year_month_new_opt_fails_year_out_of_chrono_range() {
+		let max_year = NaiveDate::MAX.year();
+		assert!(YearMonth::new_opt(max_year, 12).is_some());
+		assert_eq!(YearMonth::new_opt(max_year + 1, 1), None);
+		assert_eq!(YearMonth::new_opt(i32::MAX, 1), None);
+	}
+
+	#[test]
+	fn test_parse_month_fails_year_out_of_chrono_range() {
+		assert_eq!(parse_month("300000-01"), None);
+		assert_eq!(parse_month("2147483647-01"), None);
+	}
+
+	#[test]
+	fn test_year_month_last_month_of_chrono_range() {
+		let year_month = YearMonth::new_opt(NaiveDate::MAX.year(), 12).unwrap();
+		assert_eq!(year_month.first_day(), NaiveDate::MAX.with_day(1).unwrap());
+		assert_eq!(year_month.last_day(), NaiveDate::MAX);
+		assert_eq!(year_month.days().count(), 31);
+		assert_eq!(year_month.checked_add_months(1), None);
+	}
+
+	#[test]
+	fn test_year_month_from_date() {
+		let date = NaiveDate::from_ymd_opt(2004, 12, 31).unwrap();
+		assert_eq!(YearMonth::from_date(date), Some(YearMonth::new(2004, 12)));
+	}
+
+	#[test]
+	fn test_year_month_first_and_last_day() {
+		let year_month = YearMonth::new(2004, 2);
+		assert_eq!(
+			year_month.first_day(),
+			NaiveDate::from_ymd_opt(2004, 2, 1).unwrap()
+		);
+		assert_eq!(
+			year_month.last_day(),
+			NaiveDate::from_ymd_opt(2004, 2, 29).unwrap()
+		);
+	}
+
+	#[test]
+	fn test_year_month_days_in_month() {
+		assert_eq!(YearMonth::new(1900, 2).days_in_month(), 28);
+		assert_eq!(YearMonth::new(2000, 2).days_in_month(), 29);
+		assert_eq!(YearMonth::new(2004, 4).days_in_month(), 30);
+		assert_eq!(YearMonth::new(2004, 12).days_in_month(), 31);
+	}
+
+	#[test]
+	fn test_year_month_days() {
+		let days: Vec<NaiveDate> = YearMonth::new(2004, 12).days().collect();
+		assert_eq!(days.len(), 31);
+		assert_eq!(days.first(), NaiveDate::from_ymd_opt(2004, 12, 1).as_ref());
+		assert_eq!(days.last(), NaiveDate::from_ymd_opt(2004, 12, 31).as_ref());
+	}
+
+	#[test]
+	fn test_year_month_checked_add_months() {
+		let year_month = YearMonth::new(2004, 12);
+		assert_eq!(year_month.checked_add_months(0), Some(year_month));
+		assert_eq!(
+			year_month.checked_add_months(1),
+			Some(YearMonth::new(2005, 1))
+		);
+		assert_eq!(
+			year_month.checked_add_months(25),
+			Some(YearMonth::new(2007, 1))
+		);
+	}
+
+	#[test]
+	fn test_year_month_checked_sub_months() {
+		let year_month = YearMonth::new(2005, 1);
+		assert_eq!(
+			year_month.checked_sub_months(1),
+			Some(YearMonth::new(2004, 12))
+		);
+		assert_eq!(
+			year_month.checked_sub_months(12),
+			Some(YearMonth::new(2004, 1))
+		);
+		assert_eq!(YearMonth::new(1, 1).checked_sub_months(1), None);
+	}
+
+	#[test]
+	fn test_year_month_months_between() {
+		let start = YearMonth::new(2004, 12);
+		assert_eq!(start.months_between(&YearMonth::new(2005, 1)), 1);
+		assert_eq!(start.months_between(&YearMonth::new(2003, 12)), -12);
+	}
+
+	#[test]
+	fn test_year_month_ord() {
+		assert!(YearMonth::new(2004, 12) < YearMonth::new(2005, 1));
+		assert!(YearMonth::new(2005, 2) > YearMonth::new(2005, 1));
+	}
+
+	#[test]
+	fn test_year_month_range() {
+		let start = YearMonth::new(2004, 11);
+		let end = YearMonth::new(2005, 2);
+		let range = YearMonthRange::new(start, end);
+		assert_eq!(range.len(), 4);
+
+		let months: Vec<YearMonth> = range.collect();
+		assert_eq!(
+			months,
+			vec![
+				YearMonth::new(2004, 11),
+				YearMonth::new(2004, 12),
+				YearMonth::new(2005, 1),
+				YearMonth::new(2005, 2),
+			]
+		);
+	}
+
+	#[test]
+	fn test_year_month_range_rev() {
+		let start = YearMonth::new(2004, 12);
+		let end = YearMonth::new(2005, 1);
+		let months: Vec<YearMonth> = start.range_to(end).rev().collect();
+		assert_eq!(months, vec![end, start]);
+	}
+
+	#[test]
+	fn test_year_month_range_empty() {
+		let start = YearMonth::new(2005, 1);
+		let end = YearMonth::new(2004, 12);
+		assert_eq!(YearMonthRange::new(start, end).count(), 0);
+	}
+
+	#[test]
+	fn test_year_month_from_str() {
+		assert_eq!("2004-12".parse::<YearMonth>(), Ok(YearMonth::new(2004, 12)));
+		assert_eq!(YearMonth::try_from("2004-13"), Err(ParseError::Invalid));
+	}
+
+	#[test]
+	fn test_year_month_display_round_trips() {
+		let year_month = YearMonth::new(2004, 2);
+		assert_eq!(year_month.to_string(), "2004-02");
+		assert_eq!(year_month.to_string().parse::<YearMonth>(), Ok(year_month));
+	}
+
+	#[test]
+	fn test_year_month_default() {
+		assert_eq!(YearMonth::default(), YearMonth::new(1, 1));
+	}
+
+	#[test]
+	fn test_year_month_try_from_naive_date() {
+		let date = NaiveDate::from_ymd_opt(2004, 12, 31).unwrap();
+		assert_eq!(YearMonth::try_from(date), Ok(YearMonth::new(2004, 12)));
+
+		let date = NaiveDate::from_ymd_opt(0, 12, 31).unwrap();
+		assert_eq!(YearMonth::try_from(date), Err(ParseError::Invalid));
+	}
+
+	#[test]
+	fn test_year_month_hash_map_key() {
+		let mut map = HashMap::new();
+		map.insert(YearMonth::new(2004, 12), "december");
+		assert_eq!(map.get(&"2004-12".parse().unwrap()), Some(&"december"));
+	}
 }
@@ -1,7 +1,7 @@
 use crate::parse_format;
 use crate::tokens::{TOKEN_COLON, TOKEN_DOT};
 use crate::utils::{collect_ascii_digits, is_valid_hour, is_valid_min_or_sec};
-use chrono::NaiveTime;
+use chrono::{NaiveTime, Timelike};
 use whatwg_infra::collect_codepoints;
 
 /// Parse a specific time containing an hour, minute, and optionally a second,
@@ -23,6 +23,9 @@ use whatwg_infra::collect_codepoints;
 ///
 /// // parse a local datetime with hours, minutes, seconds, and milliseconds
 /// assert_eq!(parse_time("14:59:39.929"), NaiveTime::from_hms_milli_opt(14, 59, 39, 929));
+///
+/// // the fraction is a decimal fraction of a second, of any number of digits
+/// assert_eq!(parse_time("14:59:39.5"), NaiveTime::from_hms_milli_opt(14, 59, 39, 500));
 /// ```
 ///
 /// [whatwg-html-time]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#times
@@ -81,7 +84,7 @@ pub fn parse_time_component(s: &str, position: &mut usize) -> Option<NaiveTime>
 	}
 
 	let mut seconds = 0u32;
-	let mut milliseconds = 0u32;
+	let mut nanoseconds = 0u32;
 	if *position < s.len() && s.chars().nth(*position) == Some(TOKEN_COLON) {
 		*position += 1;
 
@@ -95,21 +98,58 @@ pub fn parse_time_component(s: &str, position: &mut usize) -> Option<NaiveTime>
 		if parsed_second_len == 3
 			|| (parsed_second_len > 3
 				&& parsed_second.chars().nth(2) != Some(TOKEN_DOT))
-			|| has_at_least_n_instances(s, TOKEN_DOT, 2)
+			|| has_at_least_n_instances(&parsed_second, TOKEN_DOT, 2)
 		{
 			return None;
 		}
 
-		let (parsed_seconds, parsed_milliseconds) =
-			parse_seconds_milliseconds(&parsed_second);
+		let (parsed_seconds, parsed_nanoseconds) =
+			parse_seconds_nanoseconds(&parsed_second)?;
 		seconds = parsed_seconds;
-		milliseconds = parsed_milliseconds;
+		nanoseconds = parsed_nanoseconds;
 		if !is_valid_min_or_sec(&seconds) {
 			return None;
 		}
 	}
 
-	NaiveTime::from_hms_milli_opt(hour, minute, seconds, milliseconds)
+	NaiveTime::from_hms_nano_opt(hour, minute, seconds, nanoseconds)
+}
+
+/// Serialize a time as a [valid normalized time string][whatwg-html-valid]
+///
+/// The shortest possible string is produced, meaning that the seconds are
+/// omitted when they are zero, and the fractional part of a second is omitted
+/// when it is zero.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveTime;
+/// use whatwg_datetime::serialize_time;
+///
+/// let time = NaiveTime::from_hms_opt(14, 54, 0).unwrap();
+/// assert_eq!(serialize_time(time), "14:54");
+///
+/// let time = NaiveTime::from_hms_milli_opt(14, 54, 39, 929).unwrap();
+/// assert_eq!(serialize_time(time), "14:54:39.929");
+/// ```
+///
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-normalized-time-string
+pub fn serialize_time(time: NaiveTime) -> String {
+	let mut serialized = format!("{:02}:{:02}", time.hour(), time.minute());
+	let second = time.second();
+	let nanosecond = time.nanosecond();
+	if second == 0 && nanosecond == 0 {
+		return serialized;
+	}
+
+	serialized.push_str(&format!(":{:02}", second));
+	if nanosecond != 0 {
+		let fraction = format!("{:09}", nanosecond);
+		serialized.push(TOKEN_DOT);
+		serialized.push_str(fraction.trim_end_matches('0'));
+	}
+
+	serialized
 }
 
 fn has_at_least_n_instances(s: &str, c: char, n: usize) -> bool {
@@ -125,17 +165,73 @@ fn has_at_least_n_instances(s: &str, c: char, n: usize) -> bool {
 	false
 }
 
-fn parse_seconds_milliseconds(s: &str) -> (u32, u32) {
-	let parts: Vec<&str> = s.split(TOKEN_DOT).collect();
-	let seconds = parts.first().unwrap_or(&"0").parse().unwrap_or(0);
-	let milliseconds = parts.get(1).unwrap_or(&"0").parse().unwrap_or(0);
+/// Parses seconds with an optional fraction, such as `39.929`, into
+/// seconds and nanoseconds. Digits past nanosecond precision are truncated.
+fn parse_seconds_nanoseconds(s: &str) -> Option<(u32, u32)> {
+	let (parsed_seconds, parsed_fraction) = match s.split_once(TOKEN_DOT) {
+		Some((seconds, fraction)) => (seconds, fraction),
+		None => (s, "0"),
+	};
+	if parsed_fraction.is_empty() {
+		return None;
+	}
+
+	let seconds = parsed_seconds.parse().ok()?;
+	let nanoseconds = format!("{:0<9.9}", parsed_fraction).parse().ok()?;
 
-	(seconds, milliseconds)
+	Some((seconds, nanoseconds))
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{parse_time, parse_time_component, NaiveTime};
+	use super::{parse_time, parse_time_component, serialize_time, NaiveTime};
+
+	#[test]
+	fn test_parse_time_succeeds_short_and_long_fractions() {
+		assert_eq!(
+			parse_time("14:54:39.5"),
+			NaiveTime::from_hms_milli_opt(14, 54, 39, 500)
+		);
+		assert_eq!(
+			parse_time("14:54:39.123456789123"),
+			NaiveTime::from_hms_nano_opt(14, 54, 39, 123_456_789)
+		);
+	}
+
+	#[test]
+	fn test_parse_time_fraction_is_decimal() {
+		// The fraction used to be read as a whole number of milliseconds, so
+		// that `.5` and `.05` were 5 ms, and `.1234` failed to parse at all.
+		for (s, nanoseconds) in [
+			("14:54:39.5", 500_000_000),
+			("14:54:39.05", 50_000_000),
+			("14:54:39.005", 5_000_000),
+			("14:54:39.1234", 123_400_000),
+			("14:54:39.000000001", 1),
+			("14:54:39.0000000009", 0),
+		] {
+			assert_eq!(
+				parse_time(s),
+				NaiveTime::from_hms_nano_opt(14, 54, 39, nanoseconds),
+				"{}",
+				s
+			);
+		}
+	}
+
+	#[test]
+	fn test_parse_time_component_fraction_is_decimal() {
+		let mut position = 0usize;
+		let parsed = parse_time_component("14:54:39.5Z", &mut position);
+		assert_eq!(parsed, NaiveTime::from_hms_milli_opt(14, 54, 39, 500));
+		assert_eq!(position, 10);
+	}
+
+	#[test]
+	fn test_parse_time_fails_empty_fraction() {
+		assert_eq!(parse_time("14:54:39."), None);
+		assert_eq!(parse_time("14:54:9."), None);
+	}
 
 	#[test]
 	fn test_parse_time_succeeds_hm() {
@@ -208,4 +304,32 @@ mod tests {
 
 		assert_eq!(parsed, NaiveTime::from_hms_milli_opt(12, 31, 59, 0));
 	}
+
+	#[test]
+	fn test_parse_time_component_ignores_decimals_after_the_time() {
+		let s = "12:31:59, then 1.5 and 2.5";
+		let mut position = 0usize;
+		let parsed = parse_time_component(s, &mut position);
+
+		assert_eq!(parsed, NaiveTime::from_hms_opt(12, 31, 59));
+		assert_eq!(position, 8);
+	}
+
+	#[test]
+	fn test_serialize_time_hm() {
+		let time = NaiveTime::from_hms_opt(12, 31, 0).unwrap();
+		assert_eq!(serialize_time(time), "12:31");
+	}
+
+	#[test]
+	fn test_serialize_time_hms() {
+		let time = NaiveTime::from_hms_opt(12, 31, 59).unwrap();
+		assert_eq!(serialize_time(time), "12:31:59");
+	}
+
+	#[test]
+	fn test_serialize_time_hms_fractional_seconds() {
+		let time = NaiveTime::from_hms_milli_opt(12, 31, 0, 500).unwrap();
+		assert_eq!(serialize_time(time), "12:31:00.5");
+	}
 }
@@ -1,29 +1,64 @@
-use crate::parse_format;
 use crate::tokens::{TOKEN_COLON, TOKEN_MINUS, TOKEN_PLUS, TOKEN_Z};
 use crate::utils::collect_ascii_digits;
+use crate::{parse_format, ParseError};
+use chrono::FixedOffset;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Neg;
+use std::str::FromStr;
 
 /// A time-zone offset, with a signed number of hours and minutes.
 ///
+/// The offset also remembers how it was written, as a [`TimeZoneDesignator`].
+/// This allows distinguishing `Z`, `+00:00` and `-00:00`, which all
+/// describe the same offset. Equality, ordering and hashing only consider
+/// the offset itself.
+///
 /// # Examples
 /// ```
 /// use whatwg_datetime::{parse_timezone_offset, TimeZoneOffset};
 ///
 /// assert_eq!(parse_timezone_offset("-07:00"), TimeZoneOffset::new_opt(-7, 0));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy)]
 pub struct TimeZoneOffset {
-	pub(crate) hour: i32,
-	pub(crate) minute: i32,
+	pub(crate) minutes: i32,
+	pub(crate) designator: TimeZoneDesignator,
 }
 
 impl TimeZoneOffset {
+	/// The largest offset allowed in either direction, `23:59`, in minutes.
+	const MAX_MINUTES: i32 = 23 * 60 + 59;
+
 	#[inline]
 	pub(crate) fn new(hour: i32, minute: i32) -> Self {
-		Self { hour, minute }
+		Self::from_minutes(hour * 60 + minute)
+	}
+
+	#[inline]
+	pub(crate) const fn with_designator(minutes: i32, designator: TimeZoneDesignator) -> Self {
+		Self {
+			minutes,
+			designator,
+		}
+	}
+
+	#[inline]
+	fn from_minutes(minutes: i32) -> Self {
+		let designator = match minutes.cmp(&0) {
+			Ordering::Less => TimeZoneDesignator::Minus,
+			Ordering::Equal => TimeZoneDesignator::Z,
+			Ordering::Greater => TimeZoneDesignator::Plus,
+		};
+		Self::with_designator(minutes, designator)
 	}
 
 	/// Creates a new `TimeZoneOffset` from a signed number of hours and minutes.
 	///
+	/// The sign of the offset is taken from the hours, so `(-5, 30)`
+	/// describes an offset of `-05:30`.
+	///
 	/// This asserts that:
 	///  - hours are in between -23 and 23, inclusive,
 	///  - minutes are in between 0 and 59, inclusive
@@ -46,7 +81,92 @@ impl TimeZoneOffset {
 			return None;
 		}
 
-		Some(Self::new(hours, minutes))
+		if hours < 0 {
+			Some(Self::new(hours, -minutes))
+		} else {
+			Some(Self::new(hours, minutes))
+		}
+	}
+
+	/// Creates a new `TimeZoneOffset` from a signed total number of minutes.
+	///
+	/// This asserts that the offset is in between `-23:59` and `+23:59`, inclusive.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::TimeZoneOffset;
+	///
+	/// assert_eq!(TimeZoneOffset::from_total_minutes_opt(-330), TimeZoneOffset::new_opt(-5, 30));
+	/// assert!(TimeZoneOffset::from_total_minutes_opt(24 * 60).is_none());
+	/// ```
+	pub fn from_total_minutes_opt(minutes: i32) -> Option<Self> {
+		if !(-Self::MAX_MINUTES..=Self::MAX_MINUTES).contains(&minutes) {
+			return None;
+		}
+
+		Some(Self::from_minutes(minutes))
+	}
+
+	/// The UTC offset, written as `Z`.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::{parse_timezone_offset, TimeZoneOffset};
+	///
+	/// assert_eq!(parse_timezone_offset("Z"), Some(TimeZoneOffset::utc()));
+	/// ```
+	#[inline]
+	pub const fn utc() -> Self {
+		Self::with_designator(0, TimeZoneDesignator::Z)
+	}
+
+	/// Whether this offset is zero, regardless of how it was written.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::parse_timezone_offset;
+	///
+	/// assert!(parse_timezone_offset("Z").unwrap().is_utc());
+	/// assert!(parse_timezone_offset("-00:00").unwrap().is_utc());
+	/// assert!(!parse_timezone_offset("+01:00").unwrap().is_utc());
+	/// ```
+	#[inline]
+	pub const fn is_utc(&self) -> bool {
+		self.minutes == 0
+	}
+
+	/// Whether this offset was written as `-00:00`.
+	///
+	/// [RFC 3339 § 4.3][rfc3339-unknown] uses this to describe a time in UTC
+	/// whose local offset is unknown.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::parse_timezone_offset;
+	///
+	/// assert!(parse_timezone_offset("-00:00").unwrap().is_unknown_local_offset());
+	/// assert!(!parse_timezone_offset("+00:00").unwrap().is_unknown_local_offset());
+	/// assert!(!parse_timezone_offset("Z").unwrap().is_unknown_local_offset());
+	/// ```
+	///
+	/// [rfc3339-unknown]: https://datatracker.ietf.org/doc/html/rfc3339#section-4.3
+	#[inline]
+	pub fn is_unknown_local_offset(&self) -> bool {
+		self.minutes == 0 && self.designator == TimeZoneDesignator::Minus
+	}
+
+	/// How the offset was written.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::{parse_timezone_offset, TimeZoneDesignator};
+	///
+	/// let tz_offset = parse_timezone_offset("+00:00").unwrap();
+	/// assert_eq!(tz_offset.designator(), TimeZoneDesignator::Plus);
+	/// ```
+	#[inline]
+	pub const fn designator(&self) -> TimeZoneDesignator {
+		self.designator
 	}
 
 	/// A minute component. This is a number from 0 to 59, inclusive.
@@ -60,7 +180,7 @@ impl TimeZoneOffset {
 	/// ```
 	#[inline]
 	pub const fn minute(&self) -> i32 {
-		self.minute
+		(self.minutes % 60).abs()
 	}
 
 	/// A hour component. This is a number from -23 to 23, inclusive.
@@ -74,10 +194,150 @@ impl TimeZoneOffset {
 	/// ```
 	#[inline]
 	pub const fn hour(&self) -> i32 {
-		self.hour
+		self.minutes / 60
+	}
+
+	/// The signed total number of minutes of this offset.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::TimeZoneOffset;
+	///
+	/// let tz_offset = TimeZoneOffset::new_opt(-5, 30).unwrap();
+	/// assert_eq!(tz_offset.total_minutes(), -330);
+	/// ```
+	#[inline]
+	pub const fn total_minutes(&self) -> i32 {
+		self.minutes
+	}
+
+	/// Converts this offset into a [`chrono::FixedOffset`].
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::FixedOffset;
+	/// use whatwg_datetime::TimeZoneOffset;
+	///
+	/// let tz_offset = TimeZoneOffset::new_opt(5, 30).unwrap();
+	/// assert_eq!(tz_offset.to_fixed_offset(), FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap());
+	/// ```
+	pub fn to_fixed_offset(&self) -> FixedOffset {
+		// We call unwrap() here since the offset is always
+		// within the bounds of a day, which `FixedOffset` accepts.
+		FixedOffset::east_opt(self.minutes * 60).unwrap()
+	}
+}
+
+impl PartialEq for TimeZoneOffset {
+	fn eq(&self, other: &Self) -> bool {
+		self.minutes == other.minutes
+	}
+}
+
+impl Eq for TimeZoneOffset {}
+
+impl PartialOrd for TimeZoneOffset {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for TimeZoneOffset {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.minutes.cmp(&other.minutes)
+	}
+}
+
+impl Hash for TimeZoneOffset {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.minutes.hash(state);
 	}
 }
 
+impl Neg for TimeZoneOffset {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		// A zero offset keeps its designator, since `-00:00` means
+		// an unknown local offset rather than a negated `+00:00`
+		let designator = match self.designator {
+			_ if self.minutes == 0 => self.designator,
+			TimeZoneDesignator::Z => TimeZoneDesignator::Z,
+			TimeZoneDesignator::Plus => TimeZoneDesignator::Minus,
+			TimeZoneDesignator::Minus => TimeZoneDesignator::Plus,
+		};
+		Self::with_designator(-self.minutes, designator)
+	}
+}
+
+impl From<TimeZoneOffset> for FixedOffset {
+	#[inline]
+	fn from(tz_offset: TimeZoneOffset) -> Self {
+		tz_offset.to_fixed_offset()
+	}
+}
+
+impl TryFrom<FixedOffset> for TimeZoneOffset {
+	type Error = ParseError;
+
+	/// Converts a [`chrono::FixedOffset`] into a `TimeZoneOffset`.
+	///
+	/// This fails if the offset has a seconds component, since
+	/// time-zone offset strings can only express whole minutes.
+	fn try_from(value: FixedOffset) -> Result<Self, Self::Error> {
+		let seconds = value.local_minus_utc();
+		if seconds % 60 != 0 {
+			return Err(ParseError::Invalid);
+		}
+
+		Self::from_total_minutes_opt(seconds / 60).ok_or(ParseError::Invalid)
+	}
+}
+
+impl Default for TimeZoneOffset {
+	/// The UTC offset, `Z`.
+	#[inline]
+	fn default() -> Self {
+		Self::utc()
+	}
+}
+
+impl fmt::Display for TimeZoneOffset {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&serialize_timezone_offset(*self))
+	}
+}
+
+impl FromStr for TimeZoneOffset {
+	type Err = ParseError;
+
+	/// Parses a string with [`parse_timezone_offset`].
+	#[inline]
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		parse_timezone_offset(s).ok_or(ParseError::Invalid)
+	}
+}
+
+impl TryFrom<&str> for TimeZoneOffset {
+	type Error = ParseError;
+
+	#[inline]
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		value.parse()
+	}
+}
+
+/// How a [`TimeZoneOffset`] was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeZoneDesignator {
+	/// The offset was written as `Z`.
+	Z,
+	/// The offset was written with a leading `+`.
+	Plus,
+	/// The offset was written with a leading `-`.
+	Minus,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TimeZoneSign {
 	Positive,
@@ -144,12 +404,13 @@ pub fn parse_timezone_offset(s: &str) -> Option<TimeZoneOffset> {
 pub fn parse_timezone_offset_component(s: &str, position: &mut usize) -> Option<TimeZoneOffset> {
 	let char_at = s.chars().nth(*position);
 
-	let mut minutes = 0i32;
-	let mut hours = 0i32;
+	let minutes;
+	let hours;
 
 	match char_at {
 		Some(TOKEN_Z) => {
 			*position += 1;
+			Some(TimeZoneOffset::utc())
 		}
 		Some(TOKEN_PLUS) | Some(TOKEN_MINUS) => {
 			let sign = TimeZoneSign::try_from(char_at.unwrap()).ok().unwrap();
@@ -189,15 +450,56 @@ pub fn parse_timezone_offset_component(s: &str, position: &mut usize) -> Option<
 				return None;
 			}
 
-			if sign == TimeZoneSign::Negative {
-				hours *= -1;
-				minutes *= -1;
+			let total_minutes = hours * 60 + minutes;
+			match sign {
+				TimeZoneSign::Positive => Some(TimeZoneOffset::with_designator(
+					total_minutes,
+					TimeZoneDesignator::Plus,
+				)),
+				TimeZoneSign::Negative => Some(TimeZoneOffset::with_designator(
+					-total_minutes,
+					TimeZoneDesignator::Minus,
+				)),
 			}
 		}
-		_ => (),
+		_ => Some(TimeZoneOffset::utc()),
 	}
+}
 
-	Some(TimeZoneOffset::new(hours, minutes))
+/// Serialize a time-zone offset as a [valid time-zone offset string][whatwg-html-valid],
+/// in the format of `±HH:MM`, or `Z`
+///
+/// The [`TimeZoneDesignator`] of the offset is preserved, so an offset
+/// parsed from `-00:00` serializes back to `-00:00`.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_timezone_offset, serialize_timezone_offset, TimeZoneOffset};
+///
+/// let tz_offset = TimeZoneOffset::new_opt(5, 30).unwrap();
+/// assert_eq!(serialize_timezone_offset(tz_offset), "+05:30");
+///
+/// let tz_offset = TimeZoneOffset::utc();
+/// assert_eq!(serialize_timezone_offset(tz_offset), "Z");
+///
+/// let tz_offset = parse_timezone_offset("-0000").unwrap();
+/// assert_eq!(serialize_timezone_offset(tz_offset), "-00:00");
+/// ```
+///
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-time-zone-offset-string
+pub fn serialize_timezone_offset(tz_offset: TimeZoneOffset) -> String {
+	let sign = match tz_offset.designator {
+		TimeZoneDesignator::Z => return TOKEN_Z.to_string(),
+		TimeZoneDesignator::Plus => TOKEN_PLUS,
+		TimeZoneDesignator::Minus => TOKEN_MINUS,
+	};
+	format!(
+		"{}{:02}{}{:02}",
+		sign,
+		tz_offset.hour().abs(),
+		TOKEN_COLON,
+		tz_offset.minute()
+	)
 }
 
 #[cfg(test)]
@@ -206,9 +508,14 @@ mod tests {
 	use super::{
 		parse_timezone_offset,
 		parse_timezone_offset_component,
+		serialize_timezone_offset,
+		TimeZoneDesignator,
 		TimeZoneOffset,
 		TimeZoneSign,
 	};
+	use crate::ParseError;
+	use chrono::FixedOffset;
+	use std::collections::HashSet;
 
 	#[test]
 	pub fn test_parse_timezone_sign_tryfrom_char_positive() {
@@ -327,4 +634,150 @@ mod tests {
 
 		assert_eq!(parsed, None);
 	}
+
+	#[test]
+	fn test_serialize_timezone_offset() {
+		assert_eq!(
+			serialize_timezone_offset(TimeZoneOffset::new(5, 30)),
+			"+05:30"
+		);
+		assert_eq!(
+			serialize_timezone_offset(TimeZoneOffset::new(-1, -30)),
+			"-01:30"
+		);
+		assert_eq!(serialize_timezone_offset(TimeZoneOffset::new(0, 0)), "Z");
+	}
+
+	#[test]
+	fn test_serialize_timezone_offset_preserves_designator() {
+		for s in ["Z", "+00:00", "-00:00", "-00:30"] {
+			let parsed = parse_timezone_offset(s).unwrap();
+			assert_eq!(serialize_timezone_offset(parsed), s);
+		}
+	}
+
+	#[test]
+	fn test_parse_timezone_offset_designator() {
+		let parsed = parse_timezone_offset("Z").unwrap();
+		assert_eq!(parsed.designator(), TimeZoneDesignator::Z);
+		assert!(!parsed.is_unknown_local_offset());
+
+		let parsed = parse_timezone_offset("+00:00").unwrap();
+		assert_eq!(parsed.designator(), TimeZoneDesignator::Plus);
+		assert!(!parsed.is_unknown_local_offset());
+
+		let parsed = parse_timezone_offset("-00:00").unwrap();
+		assert_eq!(parsed.designator(), TimeZoneDesignator::Minus);
+		assert!(parsed.is_unknown_local_offset());
+		assert!(parsed.is_utc());
+		assert_eq!(parsed, TimeZoneOffset::utc());
+	}
+
+	#[test]
+	fn test_timezone_offset_components() {
+		let parsed = parse_timezone_offset("-05:30").unwrap();
+		assert_eq!(parsed.hour(), -5);
+		assert_eq!(parsed.minute(), 30);
+		assert_eq!(parsed.total_minutes(), -330);
+		assert_eq!(TimeZoneOffset::new_opt(-5, 30), Some(parsed));
+	}
+
+	#[test]
+	fn test_timezone_offset_from_total_minutes_opt() {
+		assert_eq!(
+			TimeZoneOffset::from_total_minutes_opt(-1439),
+			Some(TimeZoneOffset::new(-23, -59))
+		);
+		assert_eq!(TimeZoneOffset::from_total_minutes_opt(1440), None);
+	}
+
+	#[test]
+	fn test_timezone_offset_to_fixed_offset() {
+		let tz_offset = TimeZoneOffset::new(-5, -30);
+		assert_eq!(
+			tz_offset.to_fixed_offset(),
+			FixedOffset::west_opt(5 * 3600 + 30 * 60).unwrap()
+		);
+		assert_eq!(FixedOffset::from(tz_offset), tz_offset.to_fixed_offset());
+	}
+
+	#[test]
+	fn test_timezone_offset_try_from_fixed_offset() {
+		let fixed_offset = FixedOffset::east_opt(9 * 3600 + 45 * 60).unwrap();
+		assert_eq!(
+			TimeZoneOffset::try_from(fixed_offset),
+			Ok(TimeZoneOffset::new(9, 45))
+		);
+	}
+
+	#[test]
+	fn test_timezone_offset_try_from_fixed_offset_fails_seconds() {
+		let fixed_offset = FixedOffset::east_opt(3600 + 1).unwrap();
+		assert_eq!(
+			TimeZoneOffset::try_from(fixed_offset),
+			Err(ParseError::Invalid)
+		);
+	}
+
+	#[test]
+	fn test_timezone_offset_ord() {
+		let mut offsets = vec![
+			TimeZoneOffset::new(5, 30),
+			TimeZoneOffset::new(-8, 0),
+			TimeZoneOffset::utc(),
+		];
+		offsets.sort();
+		assert_eq!(
+			offsets,
+			vec![
+				TimeZoneOffset::new(-8, 0),
+				TimeZoneOffset::utc(),
+				TimeZoneOffset::new(5, 30),
+			]
+		);
+	}
+
+	#[test]
+	fn test_timezone_offset_neg() {
+		let negated = -parse_timezone_offset("+05:30").unwrap();
+		assert_eq!(negated, TimeZoneOffset::new(-5, -30));
+		assert_eq!(negated.designator(), TimeZoneDesignator::Minus);
+		assert_eq!((-TimeZoneOffset::utc()).designator(), TimeZoneDesignator::Z);
+	}
+
+	#[test]
+	fn test_timezone_offset_neg_zero_keeps_designator() {
+		let negated = -parse_timezone_offset("+00:00").unwrap();
+		assert_eq!(negated.designator(), TimeZoneDesignator::Plus);
+		assert!(!negated.is_unknown_local_offset());
+		assert_eq!(serialize_timezone_offset(negated), "+00:00");
+
+		let negated = -parse_timezone_offset("-00:00").unwrap();
+		assert_eq!(negated.designator(), TimeZoneDesignator::Minus);
+		assert!(negated.is_unknown_local_offset());
+	}
+
+	#[test]
+	fn test_timezone_offset_from_str() {
+		assert_eq!(
+			"+0530".parse::<TimeZoneOffset>(),
+			Ok(TimeZoneOffset::new(5, 30))
+		);
+		assert_eq!(TimeZoneOffset::try_from("+24:00"), Err(ParseError::Invalid));
+	}
+
+	#[test]
+	fn test_timezone_offset_display() {
+		assert_eq!(TimeZoneOffset::new(-8, 0).to_string(), "-08:00");
+		assert_eq!(TimeZoneOffset::default().to_string(), "Z");
+	}
+
+	#[test]
+	fn test_timezone_offset_hash_ignores_designator() {
+		let mut set = HashSet::new();
+		set.insert("Z".parse::<TimeZoneOffset>().unwrap());
+		set.insert("+00:00".parse::<TimeZoneOffset>().unwrap());
+		set.insert("-00:00".parse::<TimeZoneOffset>().unwrap());
+		assert_eq!(set.len(), 1);
+	}
 }
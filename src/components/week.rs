@@ -1,5 +1,9 @@
 use crate::tokens::{TOKEN_ABBR_WEEK, TOKEN_HYPHEN};
-use crate::utils::{collect_ascii_digits, week_number_of_year};
+use crate::utils::{collect_ascii_digits, is_valid_year, week_number_of_year};
+use crate::{parse_format, ParseError};
+use chrono::{Datelike, Duration, IsoWeek, NaiveDate, Weekday};
+use std::fmt;
+use std::str::FromStr;
 
 /// A week date consisting of a year and a week number.
 ///
@@ -9,7 +13,7 @@ use crate::utils::{collect_ascii_digits, week_number_of_year};
 ///
 /// assert_eq!(parse_week("2011-W47"), YearWeek::new_opt(2011, 47));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct YearWeek {
 	pub(crate) year: i32,
 	pub(crate) week: u32,
@@ -23,9 +27,10 @@ impl YearWeek {
 
 	/// Creates a new `YearWeek` from a year and a week number.
 	///
-	/// This asserts that the year is greater than 0 and that the week number
-	/// is in the valid range for the year. Specifically, the week number
-	/// must be between 1 and the number of weeks in the year, inclusive.
+	/// This asserts that the year is greater than 0, no later than the year
+	/// of [`NaiveDate::MAX`], and that the week number is in the valid range
+	/// for the year. Specifically, the week number must be between 1 and the
+	/// number of weeks in the year, inclusive.
 	///
 	/// The number of weeks in a year is described by the algorithm
 	/// in [WHATWG HTML Standard § 2.3.5.8 Weeks][whatwg-html-weeks].
@@ -39,9 +44,12 @@ impl YearWeek {
 	/// assert!(YearWeek::new_opt(2011, 53).is_none()); // 2011 only has 52 weeks
 	/// assert!(YearWeek::new_opt(1952, 0).is_none()); // Week number must be at least 1
 	/// assert!(YearWeek::new_opt(0, 1).is_none()); // Year number must be greater than 0
+	/// assert!(YearWeek::new_opt(300_000, 1).is_none()); // Year must have dates in chrono
 	/// ```
+	///
+	/// [whatwg-html-weeks]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#weeks
 	pub fn new_opt(year: i32, week: u32) -> Option<Self> {
-		if year <= 0 {
+		if !is_valid_year(year) {
 			return None;
 		}
 
@@ -80,6 +88,234 @@ impl YearWeek {
 	pub const fn week(&self) -> u32 {
 		self.week
 	}
+
+	/// Creates a new `YearWeek` containing the given date.
+	///
+	/// Weeks start on a Monday, and week 1 of a week-year is the week
+	/// containing the first Thursday of that year, as described by
+	/// [WHATWG HTML Standard § 2.3.5.8 Weeks][whatwg-html-weeks].
+	/// This means that the week-year may differ from the calendar year
+	/// for dates near the start or end of a year.
+	///
+	/// This returns `None` if the week-year would not be greater than 0.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::YearWeek;
+	///
+	/// let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+	/// assert_eq!(YearWeek::from_date(date), YearWeek::new_opt(2011, 46));
+	///
+	/// // January 1st, 2010 falls in the last week of 2009
+	/// let date = NaiveDate::from_ymd_opt(2010, 1, 1).unwrap();
+	/// assert_eq!(YearWeek::from_date(date), YearWeek::new_opt(2009, 53));
+	/// ```
+	///
+	/// [whatwg-html-weeks]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#weeks
+	pub fn from_date(date: NaiveDate) -> Option<Self> {
+		let iso_week = date.iso_week();
+		Self::new_opt(iso_week.year(), iso_week.week())
+	}
+
+	/// The Monday that starts this week.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::YearWeek;
+	///
+	/// let year_week = YearWeek::new_opt(2011, 47).unwrap();
+	/// assert_eq!(year_week.monday(), NaiveDate::from_ymd_opt(2011, 11, 21).unwrap());
+	/// ```
+	#[inline]
+	pub fn monday(&self) -> NaiveDate {
+		self.day(Weekday::Mon)
+	}
+
+	/// The date of the given weekday within this week.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::{NaiveDate, Weekday};
+	/// use whatwg_datetime::YearWeek;
+	///
+	/// let year_week = YearWeek::new_opt(2009, 53).unwrap();
+	/// assert_eq!(year_week.day(Weekday::Sun), NaiveDate::from_ymd_opt(2010, 1, 3).unwrap());
+	/// ```
+	pub fn day(&self, weekday: Weekday) -> NaiveDate {
+		// We call unwrap() here since a `YearWeek` can only be constructed with
+		// a year that `NaiveDate` can represent, and a week number that is in
+		// the valid range for that year.
+		NaiveDate::from_isoywd_opt(self.year, self.week, weekday).unwrap()
+	}
+
+	/// An iterator over the seven dates of this week, from Monday through Sunday.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::YearWeek;
+	///
+	/// let year_week = YearWeek::new_opt(2011, 47).unwrap();
+	/// let days: Vec<NaiveDate> = year_week.days().collect();
+	///
+	/// assert_eq!(days.len(), 7);
+	/// assert_eq!(days[6], NaiveDate::from_ymd_opt(2011, 11, 27).unwrap());
+	/// ```
+	pub fn days(&self) -> impl Iterator<Item = NaiveDate> {
+		// Each date is looked up by its weekday; see `utils::is_valid_year`.
+		let year_week = *self;
+		[
+			Weekday::Mon,
+			Weekday::Tue,
+			Weekday::Wed,
+			Weekday::Thu,
+			Weekday::Fri,
+			Weekday::Sat,
+			Weekday::Sun,
+		]
+		.into_iter()
+		.map(move |weekday| year_week.day(weekday))
+	}
+
+	/// Adds a number of weeks, crossing into following week-years as needed.
+	///
+	/// This returns `None` if the result would be out of range.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearWeek;
+	///
+	/// let year_week = YearWeek::new_opt(2009, 52).unwrap();
+	/// assert_eq!(year_week.checked_add_weeks(2), YearWeek::new_opt(2010, 1));
+	/// ```
+	pub fn checked_add_weeks(&self, weeks: u32) -> Option<Self> {
+		let duration = Duration::weeks(i64::from(weeks));
+		Self::from_date(self.monday().checked_add_signed(duration)?)
+	}
+
+	/// Subtracts a number of weeks, crossing into preceding week-years as needed.
+	///
+	/// This returns `None` if the result would be out of range, such as
+	/// going before the first week of year 1.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearWeek;
+	///
+	/// let year_week = YearWeek::new_opt(2010, 1).unwrap();
+	/// assert_eq!(year_week.checked_sub_weeks(2), YearWeek::new_opt(2009, 52));
+	/// assert_eq!(YearWeek::new_opt(1, 1).unwrap().checked_sub_weeks(1), None);
+	/// ```
+	pub fn checked_sub_weeks(&self, weeks: u32) -> Option<Self> {
+		let duration = Duration::weeks(i64::from(weeks));
+		Self::from_date(self.monday().checked_sub_signed(duration)?)
+	}
+
+	/// The signed number of weeks from this week to another week.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearWeek;
+	///
+	/// let start = YearWeek::new_opt(2009, 50).unwrap();
+	/// let end = YearWeek::new_opt(2010, 2).unwrap();
+	///
+	/// assert_eq!(start.weeks_between(&end), 5);
+	/// assert_eq!(end.weeks_between(&start), -5);
+	/// ```
+	pub fn weeks_between(&self, other: &Self) -> i64 {
+		other.monday()
+			.signed_duration_since(self.monday())
+			.num_weeks()
+	}
+
+	/// The week following this week.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearWeek;
+	///
+	/// let year_week = YearWeek::new_opt(2004, 53).unwrap();
+	/// assert_eq!(year_week.next(), YearWeek::new_opt(2005, 1));
+	/// ```
+	#[inline]
+	pub fn next(&self) -> Option<Self> {
+		self.checked_add_weeks(1)
+	}
+
+	/// The week preceding this week.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearWeek;
+	///
+	/// let year_week = YearWeek::new_opt(2005, 1).unwrap();
+	/// assert_eq!(year_week.prev(), YearWeek::new_opt(2004, 53));
+	/// ```
+	#[inline]
+	pub fn prev(&self) -> Option<Self> {
+		self.checked_sub_weeks(1)
+	}
+}
+
+impl Default for YearWeek {
+	/// The first week of year 1, `0001-W01`.
+	#[inline]
+	fn default() -> Self {
+		Self::new(1, 1)
+	}
+}
+
+impl fmt::Display for YearWeek {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&serialize_week(*self))
+	}
+}
+
+impl FromStr for YearWeek {
+	type Err = ParseError;
+
+	/// Parses a string with [`parse_week`].
+	#[inline]
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		parse_week(s).ok_or(ParseError::Invalid)
+	}
+}
+
+impl TryFrom<&str> for YearWeek {
+	type Error = ParseError;
+
+	#[inline]
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		value.parse()
+	}
+}
+
+impl TryFrom<NaiveDate> for YearWeek {
+	type Error = ParseError;
+
+	#[inline]
+	fn try_from(value: NaiveDate) -> Result<Self, Self::Error> {
+		Self::from_date(value).ok_or(ParseError::Invalid)
+	}
+}
+
+impl TryFrom<IsoWeek> for YearWeek {
+	type Error = ParseError;
+
+	#[inline]
+	fn try_from(value: IsoWeek) -> Result<Self, Self::Error> {
+		Self::new_opt(value.year(), value.week()).ok_or(ParseError::Invalid)
+	}
+}
+
+impl From<YearWeek> for IsoWeek {
+	#[inline]
+	fn from(year_week: YearWeek) -> Self {
+		year_week.monday().iso_week()
+	}
 }
 
 /// Parse a week-year number and a week-number
@@ -98,33 +334,61 @@ impl YearWeek {
 ///
 /// [whatwg-html-weeks]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#weeks
 /// [whatwg-html-parse]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#parse-a-week-string
-pub fn parse_week(input: &str) -> Option<YearWeek> {
-	// Step 1, 2
-	let mut position = 0usize;
+#[inline]
+pub fn parse_week(s: &str) -> Option<YearWeek> {
+	// Step 8
+	parse_format(s, parse_week_component)
+}
 
+/// Low-level function for parsing an individual week component at a given position
+///
+/// This follows steps 3 to 7 of the rules for [parsing a week string][whatwg-html-parse],
+/// per [WHATWG HTML Standard § 2.3.5.8 Weeks][whatwg-html-weeks].
+///
+/// > **Note**:
+/// > This function exposes a lower-level API than [`parse_week`]. More than likely,
+/// > you will want to use [`parse_week`] instead.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_week_component, YearWeek};
+///
+/// let mut position = 0usize;
+/// let year_week = parse_week_component("2011-W47", &mut position);
+///
+/// assert_eq!(year_week, YearWeek::new_opt(2011, 47));
+/// ```
+///
+/// [whatwg-html-weeks]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#weeks
+/// [whatwg-html-parse]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#parse-a-week-string
+pub fn parse_week_component(s: &str, position: &mut usize) -> Option<YearWeek> {
 	// Step 3, 4
-	let year_string = collect_ascii_digits(input, &mut position);
-	let year = year_string.parse::<i32>().unwrap();
-	if year <= 0 {
+	let year_string = collect_ascii_digits(s, position);
+	if year_string.len() < 4 {
+		return None;
+	}
+
+	let year = year_string.parse::<i32>().ok()?;
+	if !is_valid_year(year) {
 		return None;
 	}
 
 	// Step 5
-	if position > input.len() || input.chars().nth(position) != Some(TOKEN_HYPHEN) {
+	if *position > s.len() || s.chars().nth(*position) != Some(TOKEN_HYPHEN) {
 		return None;
 	} else {
-		position += 1;
+		*position += 1;
 	}
 
 	// Step 6
-	if position > input.len() || input.chars().nth(position) != Some(TOKEN_ABBR_WEEK) {
+	if *position > s.len() || s.chars().nth(*position) != Some(TOKEN_ABBR_WEEK) {
 		return None;
 	} else {
-		position += 1;
+		*position += 1;
 	}
 
 	// Step 7
-	let parsed_week = collect_ascii_digits(input, &mut position);
+	let parsed_week = collect_ascii_digits(s, position);
 	if parsed_week.len() != 2 {
 		return None;
 	}
@@ -138,9 +402,27 @@ pub fn parse_week(input: &str) -> Option<YearWeek> {
 	Some(YearWeek::new(year, week))
 }
 
+/// Serialize a [`YearWeek`] as a [valid week string][whatwg-html-valid],
+/// in the format of `YYYY-Www`
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{serialize_week, YearWeek};
+///
+/// let year_week = YearWeek::new_opt(2011, 47).unwrap();
+/// assert_eq!(serialize_week(year_week), "2011-W47");
+/// ```
+///
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-week-string
+pub fn serialize_week(year_week: YearWeek) -> String {
+	format!("{:04}-W{:02}", year_week.year, year_week.week)
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{parse_week, YearWeek};
+	use super::{parse_week, serialize_week, YearWeek};
+	use crate::ParseError;
+	use chrono::{Datelike, IsoWeek, NaiveDate, Weekday};
 
 	#[test]
 	fn test_parse_week() {
@@ -152,6 +434,42 @@ mod tests {
 		assert_eq!(parse_week("0000-W01"), None);
 	}
 
+	#[test]
+	fn test_parse_week_fails_invalid_year() {
+		assert_eq!(parse_week(""), None);
+		assert_eq!(parse_week("W01"), None);
+		assert_eq!(parse_week("204-W01"), None);
+	}
+
+	#[test]
+	fn test_parse_week_fails_year_out_of_range() {
+		assert_eq!(parse_week("999999-W01"), None);
+		assert_eq!(parse_week("2147483647-W01"), None);
+	}
+
+	#[test]
+	fn test_year_week_new_opt_fails_year_out_of_chrono_range() {
+		let max_year = NaiveDate::MAX.year();
+		assert!(YearWeek::new_opt(max_year, 1).is_some());
+		assert_eq!(YearWeek::new_opt(max_year + 1, 1), None);
+		assert_eq!(YearWeek::new_opt(i32::MAX, 1), None);
+		assert_eq!(YearWeek::from_date(NaiveDate::MAX), None);
+	}
+
+	#[test]
+	fn test_year_week_last_week_of_chrono_range() {
+		let max_year = NaiveDate::MAX.year();
+		let weeks = YearWeek::new_opt(max_year, 53)
+			.or_else(|| YearWeek::new_opt(max_year, 52))
+			.unwrap();
+		let days: Vec<NaiveDate> = weeks.days().collect();
+		assert_eq!(days.len(), 7);
+		assert_eq!(days[0], weeks.monday());
+		assert_eq!(days[6], weeks.day(Weekday::Sun));
+		assert!(days[6] <= NaiveDate::MAX);
+		assert_eq!(weeks.checked_add_weeks(1), None);
+	}
+
 	#[test]
 	fn test_parse_week_fails_invalid_separator() {
 		assert_eq!(parse_week("2004_W01"), None);
@@ -168,6 +486,12 @@ mod tests {
 		assert_eq!(parse_week("2008-W001"), None);
 	}
 
+	#[test]
+	fn test_parse_week_fails_trailing_characters() {
+		assert_eq!(parse_week("2011-W47."), None);
+		assert_eq!(parse_week("2011-W47T"), None);
+	}
+
 	#[test]
 	fn test_parse_week_fails_invalid_week_num_lower_bound() {
 		assert_eq!(parse_week("2022-W00"), None);
@@ -179,4 +503,139 @@ mod tests {
 		assert_eq!(parse_week("2004-W54"), None);
 		assert_eq!(parse_week("1996-W53"), None);
 	}
+
+	#[test]
+	fn test_serialize_week() {
+		assert_eq!(serialize_week(YearWeek::new(2004, 53)), "2004-W53");
+		assert_eq!(serialize_week(YearWeek::new(2011, 1)), "2011-W01");
+	}
+
+	#[test]
+	fn test_year_week_from_date() {
+		let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+		assert_eq!(YearWeek::from_date(date), Some(YearWeek::new(2011, 46)));
+	}
+
+	#[test]
+	fn test_year_week_from_date_previous_week_year() {
+		let date = NaiveDate::from_ymd_opt(2010, 1, 3).unwrap();
+		assert_eq!(YearWeek::from_date(date), Some(YearWeek::new(2009, 53)));
+	}
+
+	#[test]
+	fn test_year_week_from_date_next_week_year() {
+		let date = NaiveDate::from_ymd_opt(2019, 12, 30).unwrap();
+		assert_eq!(YearWeek::from_date(date), Some(YearWeek::new(2020, 1)));
+	}
+
+	#[test]
+	fn test_year_week_day() {
+		let year_week = YearWeek::new(2020, 1);
+		assert_eq!(
+			year_week.monday(),
+			NaiveDate::from_ymd_opt(2019, 12, 30).unwrap()
+		);
+		assert_eq!(
+			year_week.day(Weekday::Thu),
+			NaiveDate::from_ymd_opt(2020, 1, 2).unwrap()
+		);
+	}
+
+	#[test]
+	fn test_year_week_days() {
+		let days: Vec<NaiveDate> = YearWeek::new(2004, 53).days().collect();
+		assert_eq!(days.first(), NaiveDate::from_ymd_opt(2004, 12, 27).as_ref());
+		assert_eq!(days.last(), NaiveDate::from_ymd_opt(2005, 1, 2).as_ref());
+		assert_eq!(days.len(), 7);
+	}
+
+	#[test]
+	fn test_year_week_checked_add_weeks_crosses_53_week_year() {
+		let year_week = YearWeek::new(2004, 52);
+		assert_eq!(
+			year_week.checked_add_weeks(1),
+			Some(YearWeek::new(2004, 53))
+		);
+		assert_eq!(year_week.checked_add_weeks(2), Some(YearWeek::new(2005, 1)));
+		assert_eq!(
+			year_week.checked_add_weeks(54),
+			Some(YearWeek::new(2006, 1))
+		);
+	}
+
+	#[test]
+	fn test_year_week_checked_sub_weeks() {
+		let year_week = YearWeek::new(2005, 1);
+		assert_eq!(
+			year_week.checked_sub_weeks(1),
+			Some(YearWeek::new(2004, 53))
+		);
+		assert_eq!(year_week.checked_sub_weeks(0), Some(year_week));
+	}
+
+	#[test]
+	fn test_year_week_checked_sub_weeks_fails_before_year_1() {
+		assert_eq!(YearWeek::new(1, 1).checked_sub_weeks(1), None);
+	}
+
+	#[test]
+	fn test_year_week_weeks_between() {
+		let start = YearWeek::new(2004, 1);
+		let end = YearWeek::new(2005, 1);
+		assert_eq!(start.weeks_between(&end), 53);
+		assert_eq!(start.weeks_between(&start), 0);
+	}
+
+	#[test]
+	fn test_year_week_ord() {
+		assert!(YearWeek::new(2004, 53) < YearWeek::new(2005, 1));
+		assert!(YearWeek::new(2005, 2) > YearWeek::new(2005, 1));
+	}
+
+	#[test]
+	fn test_year_week_next_prev() {
+		assert_eq!(YearWeek::new(2011, 52).next(), Some(YearWeek::new(2012, 1)));
+		assert_eq!(YearWeek::new(2012, 1).prev(), Some(YearWeek::new(2011, 52)));
+	}
+
+	#[test]
+	fn test_year_week_from_str() {
+		assert_eq!("2011-W47".parse::<YearWeek>(), Ok(YearWeek::new(2011, 47)));
+		assert_eq!(YearWeek::try_from("2011-W53"), Err(ParseError::Invalid));
+		assert_eq!("999999-W01".parse::<YearWeek>(), Err(ParseError::Invalid));
+	}
+
+	#[test]
+	fn test_year_week_from_str_fails_short_year() {
+		assert!("".parse::<YearWeek>().is_err());
+		assert!("W01".parse::<YearWeek>().is_err());
+		assert!("-W01".parse::<YearWeek>().is_err());
+		assert!("204-W01".parse::<YearWeek>().is_err());
+	}
+
+	#[test]
+	fn test_year_week_display_round_trips() {
+		let year_week = YearWeek::new(2004, 3);
+		assert_eq!(year_week.to_string(), "2004-W03");
+		assert_eq!(year_week.to_string().parse::<YearWeek>(), Ok(year_week));
+	}
+
+	#[test]
+	fn test_year_week_default() {
+		assert_eq!(YearWeek::default(), YearWeek::new(1, 1));
+	}
+
+	#[test]
+	fn test_year_week_iso_week_conversions() {
+		let iso_week = NaiveDate::from_ymd_opt(2010, 1, 1).unwrap().iso_week();
+		let year_week = YearWeek::try_from(iso_week).unwrap();
+		assert_eq!(year_week, YearWeek::new(2009, 53));
+		assert_eq!(IsoWeek::from(year_week), iso_week);
+	}
+
+	#[test]
+	fn test_year_week_try_from_naive_date() {
+		let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+		assert_eq!(YearWeek::try_from(date), Ok(YearWeek::new(2011, 46)));
+	}
 }
@@ -1,6 +1,9 @@
 use crate::tokens::TOKEN_HYPHEN;
 use crate::utils::is_valid_month;
-use crate::{collect_day_and_validate, collect_month_and_validate, parse_format};
+use crate::{collect_day_and_validate, collect_month_and_validate, parse_format, ParseError};
+use chrono::{Datelike, NaiveDate};
+use std::fmt;
+use std::str::FromStr;
 use whatwg_infra::collect_codepoints;
 
 /// A yearless date, consisting of a gregorian month and a day
@@ -13,7 +16,7 @@ use whatwg_infra::collect_codepoints;
 ///
 /// assert_eq!(parse_yearless_date("11-18"), YearlessDate::new_opt(11, 18));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct YearlessDate {
 	pub(crate) month: u32,
 	pub(crate) day: u32,
@@ -88,6 +91,157 @@ impl YearlessDate {
 	pub const fn day(&self) -> u32 {
 		self.day
 	}
+
+	/// Whether this yearless date exists in the given year.
+	///
+	/// This is only ever `false` for February 29th in a non-leap year.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::YearlessDate;
+	///
+	/// let leap_day = YearlessDate::new_opt(2, 29).unwrap();
+	/// assert!(leap_day.is_valid_in_year(2012));
+	/// assert!(!leap_day.is_valid_in_year(2011));
+	/// ```
+	#[inline]
+	pub fn is_valid_in_year(&self, year: i32) -> bool {
+		self.to_date(year).is_some()
+	}
+
+	/// Resolves this yearless date to a date in the given year.
+	///
+	/// This returns `None` if the date does not exist in that year,
+	/// such as February 29th in a non-leap year.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::YearlessDate;
+	///
+	/// let yearless_date = YearlessDate::new_opt(11, 18).unwrap();
+	/// assert_eq!(yearless_date.to_date(2011), NaiveDate::from_ymd_opt(2011, 11, 18));
+	///
+	/// let leap_day = YearlessDate::new_opt(2, 29).unwrap();
+	/// assert_eq!(leap_day.to_date(2011), None);
+	/// ```
+	#[inline]
+	pub fn to_date(&self, year: i32) -> Option<NaiveDate> {
+		NaiveDate::from_ymd_opt(year, self.month, self.day)
+	}
+
+	/// Resolves this yearless date to a date in the given year, using
+	/// a [`LeapDayPolicy`] to decide what February 29th becomes
+	/// in a non-leap year.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::{LeapDayPolicy, YearlessDate};
+	///
+	/// let leap_day = YearlessDate::new_opt(2, 29).unwrap();
+	/// assert_eq!(leap_day.to_date_with(2011, LeapDayPolicy::Skip), None);
+	/// assert_eq!(
+	///     leap_day.to_date_with(2011, LeapDayPolicy::Feb28),
+	///     NaiveDate::from_ymd_opt(2011, 2, 28)
+	/// );
+	/// assert_eq!(
+	///     leap_day.to_date_with(2011, LeapDayPolicy::Mar1),
+	///     NaiveDate::from_ymd_opt(2011, 3, 1)
+	/// );
+	/// ```
+	pub fn to_date_with(&self, year: i32, policy: LeapDayPolicy) -> Option<NaiveDate> {
+		if let Some(date) = self.to_date(year) {
+			return Some(date);
+		}
+
+		if self.month != 2 || self.day != 29 {
+			return None;
+		}
+
+		match policy {
+			LeapDayPolicy::Skip => None,
+			LeapDayPolicy::Feb28 => NaiveDate::from_ymd_opt(year, 2, 28),
+			LeapDayPolicy::Mar1 => NaiveDate::from_ymd_opt(year, 3, 1),
+		}
+	}
+
+	/// The first date strictly after the given date that falls on this yearless date.
+	///
+	/// February 29th only occurs in leap years, so the next occurrence
+	/// of a leap day may be up to 8 years away.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::YearlessDate;
+	///
+	/// let birthday = YearlessDate::new_opt(11, 18).unwrap();
+	/// let today = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+	/// assert_eq!(birthday.next_occurrence_after(today), NaiveDate::from_ymd_opt(2012, 11, 18));
+	///
+	/// let leap_day = YearlessDate::new_opt(2, 29).unwrap();
+	/// let today = NaiveDate::from_ymd_opt(2012, 3, 1).unwrap();
+	/// assert_eq!(leap_day.next_occurrence_after(today), NaiveDate::from_ymd_opt(2016, 2, 29));
+	/// ```
+	pub fn next_occurrence_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+		let year = date.year();
+		(year..=year.checked_add(8)?)
+			.filter_map(|year| self.to_date(year))
+			.find(|occurrence| *occurrence > date)
+	}
+}
+
+impl From<NaiveDate> for YearlessDate {
+	#[inline]
+	fn from(date: NaiveDate) -> Self {
+		Self::new(date.month(), date.day())
+	}
+}
+
+impl Default for YearlessDate {
+	/// January 1st, `01-01`.
+	#[inline]
+	fn default() -> Self {
+		Self::new(1, 1)
+	}
+}
+
+impl fmt::Display for YearlessDate {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&serialize_yearless_date(*self))
+	}
+}
+
+impl FromStr for YearlessDate {
+	type Err = ParseError;
+
+	/// Parses a string with [`parse_yearless_date`].
+	#[inline]
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		parse_yearless_date(s).ok_or(ParseError::Invalid)
+	}
+}
+
+impl TryFrom<&str> for YearlessDate {
+	type Error = ParseError;
+
+	#[inline]
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		value.parse()
+	}
+}
+
+/// Describes how February 29th of a [`YearlessDate`] is resolved
+/// in a year that is not a leap year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeapDayPolicy {
+	/// The date does not occur in that year.
+	Skip,
+	/// The date is observed on February 28th instead.
+	Feb28,
+	/// The date is observed on March 1st instead.
+	Mar1,
 }
 
 /// Parses a string consisting of a gregorian month and a day
@@ -153,14 +307,34 @@ pub fn parse_yearless_date_component(s: &str, position: &mut usize) -> Option<Ye
 	Some(YearlessDate::new(month, day))
 }
 
+/// Serialize a [`YearlessDate`] as a [valid yearless date string][whatwg-html-valid],
+/// in the format of `MM-DD`
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{serialize_yearless_date, YearlessDate};
+///
+/// let yearless_date = YearlessDate::new_opt(11, 18).unwrap();
+/// assert_eq!(serialize_yearless_date(yearless_date), "11-18");
+/// ```
+///
+/// [whatwg-html-valid]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-yearless-date-string
+pub fn serialize_yearless_date(yearless_date: YearlessDate) -> String {
+	format!("{:02}-{:02}", yearless_date.month, yearless_date.day)
+}
+
 #[cfg(test)]
 mod tests {
 	#[rustfmt::skip]
 	use super::{
 		parse_yearless_date,
 		parse_yearless_date_component,
+		serialize_yearless_date,
+		LeapDayPolicy,
 		YearlessDate,
 	};
+	use crate::ParseError;
+	use chrono::NaiveDate;
 
 	#[test]
 	fn test_parse_yearless_date() {
@@ -228,4 +402,101 @@ mod tests {
 
 		assert_eq!(parsed, None);
 	}
+
+	#[test]
+	fn test_serialize_yearless_date() {
+		assert_eq!(serialize_yearless_date(YearlessDate::new(2, 9)), "02-09");
+	}
+
+	#[test]
+	fn test_yearless_date_to_date() {
+		let yearless_date = YearlessDate::new(12, 31);
+		assert_eq!(
+			yearless_date.to_date(2004),
+			NaiveDate::from_ymd_opt(2004, 12, 31)
+		);
+	}
+
+	#[test]
+	fn test_yearless_date_to_date_leap_day() {
+		let leap_day = YearlessDate::new(2, 29);
+		assert_eq!(leap_day.to_date(2004), NaiveDate::from_ymd_opt(2004, 2, 29));
+		assert_eq!(leap_day.to_date(1900), None);
+		assert!(leap_day.is_valid_in_year(2000));
+		assert!(!leap_day.is_valid_in_year(1900));
+	}
+
+	#[test]
+	fn test_yearless_date_to_date_with_policy() {
+		let leap_day = YearlessDate::new(2, 29);
+		assert_eq!(leap_day.to_date_with(2005, LeapDayPolicy::Skip), None);
+		assert_eq!(
+			leap_day.to_date_with(2005, LeapDayPolicy::Feb28),
+			NaiveDate::from_ymd_opt(2005, 2, 28)
+		);
+		assert_eq!(
+			leap_day.to_date_with(2005, LeapDayPolicy::Mar1),
+			NaiveDate::from_ymd_opt(2005, 3, 1)
+		);
+		assert_eq!(
+			leap_day.to_date_with(2004, LeapDayPolicy::Mar1),
+			NaiveDate::from_ymd_opt(2004, 2, 29)
+		);
+	}
+
+	#[test]
+	fn test_yearless_date_next_occurrence_after() {
+		let yearless_date = YearlessDate::new(11, 18);
+		let date = NaiveDate::from_ymd_opt(2011, 11, 17).unwrap();
+		assert_eq!(
+			yearless_date.next_occurrence_after(date),
+			NaiveDate::from_ymd_opt(2011, 11, 18)
+		);
+	}
+
+	#[test]
+	fn test_yearless_date_next_occurrence_after_leap_day_skips_century() {
+		let leap_day = YearlessDate::new(2, 29);
+		let date = NaiveDate::from_ymd_opt(1896, 2, 29).unwrap();
+		assert_eq!(
+			leap_day.next_occurrence_after(date),
+			NaiveDate::from_ymd_opt(1904, 2, 29)
+		);
+	}
+
+	#[test]
+	fn test_yearless_date_from_naive_date() {
+		let date = NaiveDate::from_ymd_opt(2004, 2, 29).unwrap();
+		assert_eq!(YearlessDate::from(date), YearlessDate::new(2, 29));
+	}
+
+	#[test]
+	fn test_yearless_date_ord() {
+		assert!(YearlessDate::new(1, 31) < YearlessDate::new(2, 1));
+		assert!(YearlessDate::new(12, 2) > YearlessDate::new(12, 1));
+	}
+
+	#[test]
+	fn test_yearless_date_from_str() {
+		assert_eq!(
+			"--11-18".parse::<YearlessDate>(),
+			Ok(YearlessDate::new(11, 18))
+		);
+		assert_eq!(YearlessDate::try_from("02-30"), Err(ParseError::Invalid));
+	}
+
+	#[test]
+	fn test_yearless_date_display_round_trips() {
+		let yearless_date = YearlessDate::new(2, 29);
+		assert_eq!(yearless_date.to_string(), "02-29");
+		assert_eq!(
+			yearless_date.to_string().parse::<YearlessDate>(),
+			Ok(yearless_date)
+		);
+	}
+
+	#[test]
+	fn test_yearless_date_default() {
+		assert_eq!(YearlessDate::default(), YearlessDate::new(1, 1));
+	}
 }
@@ -0,0 +1,581 @@
+use crate::syntax::{parse_spanned, ComponentKind, TokenKind};
+use crate::tokens::{TOKEN_ABBR_WEEK, TOKEN_COLON, TOKEN_HYPHEN, TOKEN_P, TOKEN_PLUS, TOKEN_T};
+use crate::utils::collect_ascii_digits;
+use crate::{
+	parse_date, parse_datetime_value, parse_duration, parse_time, parse_timezone_offset,
+	serialize_date, to_utc_datetime, ParseMode, WhatwgDateTime,
+};
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike, Weekday};
+
+const WEEKDAYS: [Weekday; 7] = [
+	Weekday::Mon,
+	Weekday::Tue,
+	Weekday::Wed,
+	Weekday::Thu,
+	Weekday::Fri,
+	Weekday::Sat,
+	Weekday::Sun,
+];
+
+/// The forms in which ISO 8601 can write a date.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Iso8601DateForm {
+	/// A calendar date, such as `2011-11-18`.
+	#[default]
+	Calendar,
+	/// An ordinal date, made up of the year and the day of the year, such as `2011-322`.
+	Ordinal,
+	/// A week date, made up of the week-year, the week, and the day of the week
+	/// starting from Monday, such as `2011-W46-5`.
+	Week,
+}
+
+/// Which formats a string is valid in, as reported by [`compatibility`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Compatibility {
+	pub(crate) whatwg: bool,
+	pub(crate) rfc3339: bool,
+	pub(crate) iso8601: bool,
+}
+
+impl Compatibility {
+	/// Whether the string is a valid [datetime value][whatwg-html-datetime-value].
+	///
+	/// [whatwg-html-datetime-value]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#concept-time-datetime
+	#[inline]
+	pub const fn is_whatwg(&self) -> bool {
+		self.whatwg
+	}
+
+	/// Whether the string is valid RFC 3339, as read by [`WhatwgDateTime::from_rfc3339`].
+	#[inline]
+	pub const fn is_rfc3339(&self) -> bool {
+		self.rfc3339
+	}
+
+	/// Whether the string is valid ISO 8601, as read by [`WhatwgDateTime::from_iso8601`].
+	#[inline]
+	pub const fn is_iso8601(&self) -> bool {
+		self.iso8601
+	}
+}
+
+impl WhatwgDateTime {
+	/// Serializes this value as [RFC 3339][rfc3339].
+	///
+	/// RFC 3339 can only write full dates, times, and global datetimes with
+	/// four-digit years, so this returns `None` for any other value. Unlike the
+	/// WHATWG formats, times always include seconds.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::{parse_datetime_value, ParseMode};
+	///
+	/// let value = parse_datetime_value("2011-11-18 14:54+0530", ParseMode::Attribute).unwrap();
+	/// assert_eq!(value.to_rfc3339(), Some(String::from("2011-11-18T09:24:00Z")));
+	///
+	/// let value = parse_datetime_value("2011-W47", ParseMode::Attribute).unwrap();
+	/// assert_eq!(value.to_rfc3339(), None);
+	/// ```
+	///
+	/// [rfc3339]: https://datatracker.ietf.org/doc/html/rfc3339#section-5.6
+	pub fn to_rfc3339(&self) -> Option<String> {
+		if self.year().map_or(false, |year| year > 9999) {
+			return None;
+		}
+
+		match *self {
+			WhatwgDateTime::Date(date) => Some(serialize_date(date)),
+			WhatwgDateTime::Time(time) => Some(serialize_rfc3339_time(time)),
+			WhatwgDateTime::GlobalDateTime(datetime) => Some(format!(
+				"{}{}{}Z",
+				serialize_date(datetime.date_naive()),
+				TOKEN_T,
+				serialize_rfc3339_time(datetime.time())
+			)),
+			_ => None,
+		}
+	}
+
+	/// Parses an [RFC 3339][rfc3339] `date-time`, `full-date`, or `partial-time`.
+	///
+	/// Unlike the WHATWG formats, the `T` and `Z` may be lowercase, seconds
+	/// are required, and time-zone offsets must have a colon. Leap seconds
+	/// are not supported.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::{parse_global_datetime, WhatwgDateTime};
+	///
+	/// assert_eq!(
+	///     WhatwgDateTime::from_rfc3339("2011-11-18t14:54:00z"),
+	///     parse_global_datetime("2011-11-18T14:54Z").map(WhatwgDateTime::GlobalDateTime)
+	/// );
+	/// assert_eq!(
+	///     WhatwgDateTime::from_rfc3339("2011-11-18"),
+	///     Some(WhatwgDateTime::Date(NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()))
+	/// );
+	///
+	/// // seconds are required
+	/// assert_eq!(WhatwgDateTime::from_rfc3339("2011-11-18T14:54Z"), None);
+	/// ```
+	///
+	/// [rfc3339]: https://datatracker.ietf.org/doc/html/rfc3339#section-5.6
+	pub fn from_rfc3339(s: &str) -> Option<Self> {
+		if s.as_bytes().get(2) == Some(&(TOKEN_COLON as u8)) {
+			return parse_rfc3339_time(s).map(WhatwgDateTime::Time);
+		}
+		if s.len() <= 10 {
+			return parse_rfc3339_date(s).map(WhatwgDateTime::Date);
+		}
+
+		let date = parse_rfc3339_date(s.get(..10)?)?;
+		if !s[10..].starts_with(['T', 't']) {
+			return None;
+		}
+
+		let time_and_offset = &s[11..];
+		let offset_start = time_and_offset.rfind(['Z', 'z', '+', '-'])?;
+		let time = parse_rfc3339_time(&time_and_offset[..offset_start])?;
+		let offset = match &time_and_offset[offset_start..] {
+			"Z" | "z" => parse_timezone_offset("Z")?,
+			offset if offset.len() == 6 => parse_timezone_offset(offset)?,
+			_ => return None,
+		};
+
+		to_utc_datetime(date, time, offset).map(WhatwgDateTime::GlobalDateTime)
+	}
+
+	/// Serializes this value in the extended format of [ISO 8601][iso8601].
+	///
+	/// Years after 9999 are written in the expanded form, with a leading `+`.
+	/// Yearless dates and lone time-zone offsets have no representation in
+	/// ISO 8601, so this returns `None` for them. Durations use the
+	/// ISO 8601 based form of [`serialize_duration`](crate::serialize_duration).
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::{parse_datetime_value, ParseMode};
+	///
+	/// let value = parse_datetime_value("2011-11-18 14:54", ParseMode::Attribute).unwrap();
+	/// assert_eq!(value.to_iso8601(), Some(String::from("2011-11-18T14:54")));
+	///
+	/// let value = parse_datetime_value("12011-W47", ParseMode::Attribute).unwrap();
+	/// assert_eq!(value.to_iso8601(), Some(String::from("+12011-W47")));
+	/// ```
+	///
+	/// [iso8601]: https://www.iso.org/iso-8601-date-and-time-format.html
+	pub fn to_iso8601(&self) -> Option<String> {
+		if matches!(
+			self,
+			WhatwgDateTime::YearlessDate(_) | WhatwgDateTime::TimeZoneOffset(_)
+		) {
+			return None;
+		}
+
+		let serialized = self.serialize();
+		match self.year() {
+			Some(year) if year > 9999 => Some(format!("{}{}", TOKEN_PLUS, serialized)),
+			_ => Some(serialized),
+		}
+	}
+
+	/// Parses a string in the extended format of [ISO 8601][iso8601].
+	///
+	/// This reads the same kinds of values as the WHATWG formats, except for yearless
+	/// dates and lone time-zone offsets, along with ordinal dates and week dates.
+	/// Unlike the WHATWG formats, dates and times must be separated by a `T`,
+	/// offsets must have a colon, years after 9999 must have a leading `+`, and
+	/// durations must use the form starting with `P`.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::WhatwgDateTime;
+	///
+	/// let date = Some(WhatwgDateTime::Date(NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()));
+	/// assert_eq!(WhatwgDateTime::from_iso8601("2011-11-18"), date);
+	/// assert_eq!(WhatwgDateTime::from_iso8601("2011-322"), date);
+	/// assert_eq!(WhatwgDateTime::from_iso8601("2011-W46-5"), date);
+	///
+	/// assert_eq!(WhatwgDateTime::from_iso8601("2011-11-18 14:54"), None);
+	/// ```
+	///
+	/// [iso8601]: https://www.iso.org/iso-8601-date-and-time-format.html
+	pub fn from_iso8601(s: &str) -> Option<Self> {
+		// Years after 9999 must be expanded, and only they may be
+		let (s, is_expanded) = match s.strip_prefix(TOKEN_PLUS) {
+			Some(unsigned) => (unsigned, true),
+			None => (s, false),
+		};
+		let value = parse_iso8601_value(s)?;
+		if value.year().map_or(false, |year| year > 9999) != is_expanded {
+			return None;
+		}
+
+		Some(value)
+	}
+
+	fn year(&self) -> Option<i32> {
+		match *self {
+			WhatwgDateTime::Month(year_month) => Some(year_month.year()),
+			WhatwgDateTime::Date(date) => Some(date.year()),
+			WhatwgDateTime::LocalDateTime(datetime) => Some(datetime.year()),
+			WhatwgDateTime::GlobalDateTime(datetime) => Some(datetime.year()),
+			WhatwgDateTime::Week(year_week) => Some(year_week.year()),
+			WhatwgDateTime::Year(year) => Some(year),
+			_ => None,
+		}
+	}
+}
+
+/// Reports whether a string is valid in each of the WHATWG, RFC 3339 and ISO 8601 formats
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::compatibility;
+///
+/// let report = compatibility("2011-11-18T14:54:39Z");
+/// assert!(report.is_whatwg() && report.is_rfc3339() && report.is_iso8601());
+///
+/// // WHATWG allows a space separator, omitted seconds and offsets without a colon
+/// let report = compatibility("2011-11-18 14:54+0530");
+/// assert!(report.is_whatwg() && !report.is_rfc3339() && !report.is_iso8601());
+///
+/// // RFC 3339 allows a lowercase `t` and `z`
+/// let report = compatibility("2011-11-18t14:54:39z");
+/// assert!(!report.is_whatwg() && report.is_rfc3339() && !report.is_iso8601());
+/// ```
+pub fn compatibility(s: &str) -> Compatibility {
+	Compatibility {
+		whatwg: parse_datetime_value(s, ParseMode::Attribute).is_some(),
+		rfc3339: WhatwgDateTime::from_rfc3339(s).is_some(),
+		iso8601: WhatwgDateTime::from_iso8601(s).is_some(),
+	}
+}
+
+/// Serialize a date in one of the forms of ISO 8601
+///
+/// Years after 9999 are written in the expanded form, with a leading `+`.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::{serialize_iso8601_date, Iso8601DateForm};
+///
+/// let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+/// assert_eq!(serialize_iso8601_date(date, Iso8601DateForm::Calendar), "2011-11-18");
+/// assert_eq!(serialize_iso8601_date(date, Iso8601DateForm::Ordinal), "2011-322");
+/// assert_eq!(serialize_iso8601_date(date, Iso8601DateForm::Week), "2011-W46-5");
+/// ```
+pub fn serialize_iso8601_date(date: NaiveDate, form: Iso8601DateForm) -> String {
+	match form {
+		Iso8601DateForm::Calendar => format!(
+			"{}{}{:02}{}{:02}",
+			serialize_iso8601_year(date.year()),
+			TOKEN_HYPHEN,
+			date.month(),
+			TOKEN_HYPHEN,
+			date.day()
+		),
+		Iso8601DateForm::Ordinal => format!(
+			"{}{}{:03}",
+			serialize_iso8601_year(date.year()),
+			TOKEN_HYPHEN,
+			date.ordinal()
+		),
+		Iso8601DateForm::Week => {
+			let week = date.iso_week();
+			format!(
+				"{}{}{}{:02}{}{}",
+				serialize_iso8601_year(week.year()),
+				TOKEN_HYPHEN,
+				TOKEN_ABBR_WEEK,
+				week.week(),
+				TOKEN_HYPHEN,
+				date.weekday().number_from_monday()
+			)
+		}
+	}
+}
+
+/// Parse a date written in any of the forms of ISO 8601
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::parse_iso8601_date;
+///
+/// let date = NaiveDate::from_ymd_opt(2004, 12, 31);
+/// assert_eq!(parse_iso8601_date("2004-12-31"), date);
+/// assert_eq!(parse_iso8601_date("2004-366"), date);
+/// assert_eq!(parse_iso8601_date("2004-W53-5"), date);
+/// assert_eq!(parse_iso8601_date("2011-366"), None);
+/// ```
+pub fn parse_iso8601_date(s: &str) -> Option<NaiveDate> {
+	match WhatwgDateTime::from_iso8601(s)? {
+		WhatwgDateTime::Date(date) => Some(date),
+		_ => None,
+	}
+}
+
+fn parse_iso8601_value(s: &str) -> Option<WhatwgDateTime> {
+	if s.starts_with(TOKEN_P) {
+		return parse_duration(s).map(WhatwgDateTime::Duration);
+	}
+	if let Some(date) = parse_ordinal_or_week_date(s) {
+		return Some(WhatwgDateTime::Date(date));
+	}
+
+	let tree = parse_spanned(s)?;
+	if matches!(
+		tree.value(),
+		WhatwgDateTime::YearlessDate(_)
+			| WhatwgDateTime::TimeZoneOffset(_)
+			| WhatwgDateTime::Duration(_)
+	) {
+		return None;
+	}
+
+	let is_extended_format = tree.tokens().all(|token| match token.kind() {
+		TokenKind::Year => token.text().len() == 4 || !token.text().starts_with('0'),
+		TokenKind::Hour | TokenKind::Minute | TokenKind::Second => token.text().len() == 2,
+		TokenKind::DateTimeSeparator => token.text() == TOKEN_T.to_string(),
+		_ => true,
+	});
+	let offsets_have_colons = tree
+		.components()
+		.iter()
+		.filter(|component| component.kind() == ComponentKind::TimeZoneOffset)
+		.all(|component| {
+			let tokens = component.tokens();
+			tokens.len() == 1
+				|| tokens.iter().any(|token| token.kind() == TokenKind::Colon)
+		});
+	if !is_extended_format || !offsets_have_colons {
+		return None;
+	}
+
+	Some(tree.value())
+}
+
+/// Parses an ordinal date (`YYYY-DDD`) or a week date (`YYYY-Www-D`).
+fn parse_ordinal_or_week_date(s: &str) -> Option<NaiveDate> {
+	let mut position = 0usize;
+	let parsed_year = collect_ascii_digits(s, &mut position);
+	if parsed_year.len() < 4 || !s[position..].starts_with(TOKEN_HYPHEN) {
+		return None;
+	}
+	let year = parsed_year.parse::<i32>().ok()?;
+	if year <= 0 {
+		return None;
+	}
+	let rest = &s[position + 1..];
+
+	if let Some(week_date) = rest.strip_prefix(TOKEN_ABBR_WEEK) {
+		let (week, weekday) = week_date.split_once(TOKEN_HYPHEN)?;
+		if week.len() != 2
+			|| weekday.len() != 1 || !week.bytes().all(|c| c.is_ascii_digit())
+		{
+			return None;
+		}
+
+		let weekday = match weekday.as_bytes()[0] {
+			c @ b'1'..=b'7' => WEEKDAYS[(c - b'1') as usize],
+			_ => return None,
+		};
+		return NaiveDate::from_isoywd_opt(year, week.parse().ok()?, weekday);
+	}
+
+	if rest.len() != 3 || !rest.bytes().all(|c| c.is_ascii_digit()) {
+		return None;
+	}
+	NaiveDate::from_yo_opt(year, rest.parse().ok()?)
+}
+
+/// Parses an RFC 3339 `full-date`, with a four-digit year.
+fn parse_rfc3339_date(s: &str) -> Option<NaiveDate> {
+	if s.len() != 10 {
+		return None;
+	}
+
+	parse_date(s)
+}
+
+/// Parses an RFC 3339 `partial-time`, which always has seconds.
+fn parse_rfc3339_time(s: &str) -> Option<NaiveTime> {
+	let bytes = s.as_bytes();
+	if bytes.len() < 8
+		|| bytes[5] != TOKEN_COLON as u8
+		|| !bytes[6].is_ascii_digit()
+		|| !bytes[7].is_ascii_digit()
+	{
+		return None;
+	}
+
+	parse_time(s)
+}
+
+fn serialize_rfc3339_time(time: NaiveTime) -> String {
+	let mut serialized = format!(
+		"{:02}:{:02}:{:02}",
+		time.hour(),
+		time.minute(),
+		time.second()
+	);
+	if time.nanosecond() != 0 {
+		let fraction = format!("{:09}", time.nanosecond());
+		serialized.push('.');
+		serialized.push_str(fraction.trim_end_matches('0'));
+	}
+
+	serialized
+}
+
+fn serialize_iso8601_year(year: i32) -> String {
+	if year > 9999 {
+		return format!("{}{}", TOKEN_PLUS, year);
+	}
+
+	format!("{:04}", year)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{compatibility, parse_iso8601_date, serialize_iso8601_date, Iso8601DateForm};
+	use crate::{parse_datetime_value, ParseMode, WhatwgDateTime};
+	use chrono::NaiveDate;
+
+	fn parse(s: &str) -> WhatwgDateTime {
+		parse_datetime_value(s, ParseMode::Attribute).unwrap()
+	}
+
+	#[test]
+	fn test_to_rfc3339() {
+		assert_eq!(
+			parse("2011-11-18T14:54:39.929-08:00").to_rfc3339().unwrap(),
+			"2011-11-18T22:54:39.929Z"
+		);
+		assert_eq!(parse("14:54").to_rfc3339().unwrap(), "14:54:00");
+		assert_eq!(parse("2011-11-18").to_rfc3339().unwrap(), "2011-11-18");
+		assert_eq!(parse("12011-11-18").to_rfc3339(), None);
+		assert_eq!(parse("2011-11-18T14:54").to_rfc3339(), None);
+		assert_eq!(parse("2011-11").to_rfc3339(), None);
+	}
+
+	#[test]
+	fn test_from_rfc3339() {
+		assert_eq!(
+			WhatwgDateTime::from_rfc3339("2011-11-18T14:54:39.929-08:00"),
+			Some(parse("2011-11-18T22:54:39.929Z"))
+		);
+		assert_eq!(
+			WhatwgDateTime::from_rfc3339("14:54:39"),
+			Some(parse("14:54:39"))
+		);
+		assert_eq!(WhatwgDateTime::from_rfc3339("2011-11-18 14:54:39Z"), None);
+		assert_eq!(
+			WhatwgDateTime::from_rfc3339("2011-11-18T14:54:39+0800"),
+			None
+		);
+		assert_eq!(WhatwgDateTime::from_rfc3339("2011-11-18T14:54:39"), None);
+		assert_eq!(WhatwgDateTime::from_rfc3339("12011-11-18"), None);
+		assert_eq!(WhatwgDateTime::from_rfc3339("14:54"), None);
+		assert_eq!(WhatwgDateTime::from_rfc3339("14:54:3"), None);
+		assert_eq!(WhatwgDateTime::from_rfc3339(""), None);
+	}
+
+	#[test]
+	fn test_to_iso8601_round_trips() {
+		for s in [
+			"2011-11",
+			"2011-11-18",
+			"14:54:39.929",
+			"2011-11-18T14:54",
+			"2011-11-18T14:54Z",
+			"2011-W47",
+			"2011",
+			"PT4H18M3S",
+		] {
+			let value = parse(s);
+			assert_eq!(value.to_iso8601().unwrap(), s);
+			assert_eq!(WhatwgDateTime::from_iso8601(s), Some(value));
+		}
+
+		assert_eq!(parse("11-18").to_iso8601(), None);
+		assert_eq!(parse("+05:30").to_iso8601(), None);
+		assert_eq!(parse("12011-11").to_iso8601().unwrap(), "+12011-11");
+	}
+
+	#[test]
+	fn test_from_iso8601_fails() {
+		for s in [
+			"2011-11-18 14:54",
+			"2011-11-18T14:54+0530",
+			"12011-11-18",
+			"+2011-11-18",
+			"02011-11-18",
+			"11-18",
+			"+05:30",
+			"4h 18m",
+			"2011-W47-8",
+			"2011-000",
+			"0000-001",
+			"+999999-W01",
+		] {
+			assert_eq!(WhatwgDateTime::from_iso8601(s), None, "{}", s);
+		}
+	}
+
+	#[test]
+	fn test_iso8601_date_forms_round_trip() {
+		for date in [
+			NaiveDate::from_ymd_opt(2011, 11, 18).unwrap(),
+			NaiveDate::from_ymd_opt(2008, 12, 29).unwrap(),
+			NaiveDate::from_ymd_opt(2010, 1, 3).unwrap(),
+			NaiveDate::from_ymd_opt(12011, 1, 1).unwrap(),
+		] {
+			for form in [
+				Iso8601DateForm::Calendar,
+				Iso8601DateForm::Ordinal,
+				Iso8601DateForm::Week,
+			] {
+				let serialized = serialize_iso8601_date(date, form);
+				assert_eq!(
+					parse_iso8601_date(&serialized),
+					Some(date),
+					"{}",
+					serialized
+				);
+			}
+		}
+
+		let date = NaiveDate::from_ymd_opt(2008, 12, 29).unwrap();
+		assert_eq!(
+			serialize_iso8601_date(date, Iso8601DateForm::Week),
+			"2009-W01-1"
+		);
+	}
+
+	#[test]
+	fn test_compatibility() {
+		let report = compatibility("2011-11-18");
+		assert!(report.is_whatwg() && report.is_rfc3339() && report.is_iso8601());
+
+		let report = compatibility("2011-W47");
+		assert!(report.is_whatwg() && !report.is_rfc3339() && report.is_iso8601());
+
+		let report = compatibility("2011-322");
+		assert!(!report.is_whatwg() && !report.is_rfc3339() && report.is_iso8601());
+
+		let report = compatibility("11-18");
+		assert!(report.is_whatwg() && !report.is_rfc3339() && !report.is_iso8601());
+
+		let report = compatibility("999999-W01");
+		assert!(!report.is_whatwg() && !report.is_rfc3339() && !report.is_iso8601());
+
+		let report = compatibility("next tuesday");
+		assert!(!report.is_whatwg() && !report.is_rfc3339() && !report.is_iso8601());
+	}
+}
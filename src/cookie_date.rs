@@ -0,0 +1,214 @@
+//! Parsing the dates of cookie attributes, such as `Expires`, following the
+//! [cookie-date algorithm][rfc6265-cookie-date] of RFC 6265 § 5.1.1.
+//!
+//! Unlike the WHATWG microsyntaxes, this algorithm is very forgiving. It splits
+//! the string into tokens, and takes the first token that looks like each of
+//! a time, a day of the month, a month, and a year, ignoring everything else.
+//!
+//! [rfc6265-cookie-date]: https://datatracker.ietf.org/doc/html/rfc6265#section-5.1.1
+
+use crate::tokens::TOKEN_COLON;
+use crate::utils::collect_ascii_digits;
+use chrono::{DateTime, NaiveDate, Utc};
+
+const MONTHS: [&str; 12] = [
+	"jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Parse a cookie date into a [`DateTime<Utc>`]
+///
+/// Cookie dates are always in UTC, and any time zone in the string is ignored.
+/// Two-digit years from 70 to 99 are in the 1900s, and from 00 to 69 are in the
+/// 2000s. This returns `None` if the string does not have all of a time,
+/// a day of the month, a month, and a year, if the year is before 1601,
+/// or if the date and time do not exist.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{cookie_date, parse_global_datetime};
+///
+/// let expected = parse_global_datetime("1994-11-06T08:49:37Z");
+///
+/// assert_eq!(cookie_date::parse("Sun, 06 Nov 1994 08:49:37 GMT"), expected);
+/// assert_eq!(cookie_date::parse("Sunday, 06-Nov-94 08:49:37 GMT"), expected);
+/// assert_eq!(cookie_date::parse("Sun Nov  6 08:49:37 1994"), expected);
+/// assert_eq!(cookie_date::parse("6 november 94, 8:49:37"), expected);
+///
+/// assert_eq!(cookie_date::parse("Sun, 06 Nov 1994"), None);
+/// ```
+pub fn parse(s: &str) -> Option<DateTime<Utc>> {
+	let mut time = None;
+	let mut day_of_month = None;
+	let mut month = None;
+	let mut year = None;
+
+	// Step 1, 2
+	for token in s.split(is_delimiter).filter(|token| !token.is_empty()) {
+		if time.is_none() {
+			if let Some(hms) = parse_time(token) {
+				time = Some(hms);
+				continue;
+			}
+		}
+		if day_of_month.is_none() {
+			if let Some(day) = parse_digits(token, 1, 2) {
+				day_of_month = Some(day);
+				continue;
+			}
+		}
+		if month.is_none() {
+			if let Some(parsed_month) = parse_month(token) {
+				month = Some(parsed_month);
+				continue;
+			}
+		}
+		if year.is_none() {
+			if let Some(parsed_year) = parse_digits(token, 2, 4) {
+				year = Some(parsed_year);
+			}
+		}
+	}
+
+	// Step 3, 4
+	let year = match year? {
+		year @ 70..=99 => year + 1900,
+		year @ 0..=69 => year + 2000,
+		year => year,
+	};
+
+	// Step 5
+	let (hour, minute, second) = time?;
+	let (day_of_month, month) = (day_of_month?, month?);
+	if !(1..=31).contains(&day_of_month)
+		|| year < 1601 || hour > 23
+		|| minute > 59 || second > 59
+	{
+		return None;
+	}
+
+	// Step 6
+	let naive_datetime = NaiveDate::from_ymd_opt(year as i32, month, day_of_month)?
+		.and_hms_opt(hour, minute, second)?;
+	Some(DateTime::<Utc>::from_naive_utc_and_offset(
+		naive_datetime,
+		Utc,
+	))
+}
+
+/// Whether a character is a `delimiter`, which separates the `date-token`s.
+fn is_delimiter(c: char) -> bool {
+	matches!(c, '\t' | ' '..='/' | ';'..='@' | '['..='`' | '{'..='~')
+}
+
+/// Matches the `time` production, which is three `time-field`s of one or
+/// two digits separated by `:`, followed by a non-digit and anything else.
+fn parse_time(token: &str) -> Option<(u32, u32, u32)> {
+	let mut position = 0usize;
+	let mut fields = [0u32; 3];
+	for (index, field) in fields.iter_mut().enumerate() {
+		if index > 0 {
+			if token.chars().nth(position) != Some(TOKEN_COLON) {
+				return None;
+			}
+			position += 1;
+		}
+
+		let parsed_field = collect_ascii_digits(token, &mut position);
+		if !(1..=2).contains(&parsed_field.len()) {
+			return None;
+		}
+		*field = parsed_field.parse().ok()?;
+	}
+
+	Some((fields[0], fields[1], fields[2]))
+}
+
+/// Matches `min` to `max` digits at the start of a token,
+/// followed by a non-digit and anything else.
+fn parse_digits(token: &str, min: usize, max: usize) -> Option<u32> {
+	let mut position = 0usize;
+	let parsed_digits = collect_ascii_digits(token, &mut position);
+	if !(min..=max).contains(&parsed_digits.len()) {
+		return None;
+	}
+
+	parsed_digits.parse().ok()
+}
+
+/// Matches a token starting with the first three letters
+/// of the name of a month, in any case.
+fn parse_month(token: &str) -> Option<u32> {
+	let prefix = token.as_bytes().get(..3)?;
+	MONTHS.iter()
+		.position(|month| prefix.eq_ignore_ascii_case(month.as_bytes()))
+		.map(|index| index as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{is_delimiter, parse};
+	use crate::parse_global_datetime;
+
+	#[test]
+	fn test_parse_first_match_of_each_production_wins() {
+		// The first two-digit token is the day of the month,
+		// the second is the year, and the third is ignored
+		assert_eq!(
+			parse("18 11 12 Nov 14:54:39"),
+			parse_global_datetime("2011-11-18T14:54:39Z")
+		);
+		// The first time is used, and the second is ignored
+		assert_eq!(
+			parse("14:54:39 Nov 18 2011 15:00:00"),
+			parse_global_datetime("2011-11-18T14:54:39Z")
+		);
+	}
+
+	#[test]
+	fn test_parse_two_digit_years() {
+		assert_eq!(
+			parse("01-Jan-70 00:00:00"),
+			parse_global_datetime("1970-01-01T00:00Z")
+		);
+		assert_eq!(
+			parse("01-Jan-69 00:00:00"),
+			parse_global_datetime("2069-01-01T00:00Z")
+		);
+		assert_eq!(
+			parse("01-Jan-0 00:00:00"),
+			None,
+			"a year needs at least two digits"
+		);
+	}
+
+	#[test]
+	fn test_parse_trailing_characters_in_tokens() {
+		assert_eq!(
+			parse("18th Novembre 2011AD 14:54:39pm"),
+			parse_global_datetime("2011-11-18T14:54:39Z")
+		);
+	}
+
+	#[test]
+	fn test_parse_fails() {
+		assert_eq!(parse(""), None);
+		assert_eq!(parse("Nov 2011 14:54:39"), None);
+		assert_eq!(parse("18 Nov 1600 14:54:39"), None);
+		assert_eq!(parse("31 Feb 2011 14:54:39"), None);
+		assert_eq!(parse("32 Jan 2011 14:54:39"), None);
+		assert_eq!(parse("18 Nov 2011 24:00:00"), None);
+		assert_eq!(parse("18 Nov 2011 14:54:60"), None);
+		assert_eq!(parse("18 Nov 2011 14:54:390"), None);
+		assert_eq!(parse("18 Nov 20111 14:54:39"), None);
+	}
+
+	#[test]
+	fn test_is_delimiter() {
+		for c in ['\t', ' ', '/', ';', '@', '[', '`', '{', '~', ',', '-'] {
+			assert!(is_delimiter(c), "{:?}", c);
+		}
+		for c in ['0', '9', ':', 'a', 'Z', '\n', '\u{7f}', 'é'] {
+			assert!(!is_delimiter(c), "{:?}", c);
+		}
+	}
+}
@@ -0,0 +1,418 @@
+//! Parsing the [Date Time String Format][ecma-262-date-time-string-format] of ECMA-262,
+//! which is the only format `Date.parse` is required to support.
+//!
+//! The format is a simplification of ISO 8601 extended format, which differs from
+//! the WHATWG formats. It allows expanded years with a sign, such as `+275760`
+//! and `-000001`, a year of `0000`, and a time of `24:00`, but requires
+//! a `T` separator and an offset with a colon. Date-only forms are in UTC, while
+//! date-time forms without an offset are in local time.
+//!
+//! The format itself has exactly three digits for milliseconds. Like V8, this
+//! accepts one or more digits instead, truncating them to milliseconds, so
+//! that `14:54:39.9` is 900 milliseconds past the second.
+//!
+//! Strings that are not in this format are left to implementation-specific
+//! fallback parsers in JavaScript engines, and are rejected here.
+//!
+//! [ecma-262-date-time-string-format]: https://tc39.es/ecma262/#sec-date-time-string-format
+
+use crate::tokens::{
+	TOKEN_COLON, TOKEN_DOT, TOKEN_HYPHEN, TOKEN_MINUS, TOKEN_PLUS, TOKEN_T, TOKEN_Z,
+};
+use crate::utils::{collect_ascii_digits, max_days_in_month_year};
+use crate::{
+	collect_day_and_validate, collect_month_and_validate, parse_format, parse_time_component,
+	parse_timezone_offset, TimeZoneOffset,
+};
+use chrono::{
+	DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone, Timelike, Utc,
+};
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// The largest distance from the epoch, in milliseconds,
+/// of a [time value][ecma-262-time-values].
+///
+/// [ecma-262-time-values]: https://tc39.es/ecma262/#sec-time-values-and-time-range
+const MAX_TIME_VALUE: i64 = 100_000_000 * MS_PER_DAY;
+
+/// A date and time parsed from the ECMA-262 Date Time String Format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EcmaScriptDateTime {
+	/// The milliseconds since the epoch of the date and time as written,
+	/// before applying the offset.
+	pub(crate) local_time_value: i64,
+	/// The offset, which is `None` for local time.
+	pub(crate) offset: Option<TimeZoneOffset>,
+}
+
+impl EcmaScriptDateTime {
+	/// The offset the date and time were written in.
+	///
+	/// Date-only forms are in UTC, and this is `None`
+	/// for date-time forms without an offset, which are in local time.
+	#[inline]
+	pub const fn offset(&self) -> Option<TimeZoneOffset> {
+		self.offset
+	}
+
+	/// Whether this is a date-time without an offset, which is in local time.
+	#[inline]
+	pub const fn is_local(&self) -> bool {
+		self.offset.is_none()
+	}
+
+	/// The [time value][ecma-262-time-values], in milliseconds since the epoch,
+	/// as returned by `Date.parse`.
+	///
+	/// This is `None` for local time, as it depends on the local time zone.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::ecmascript;
+	///
+	/// let parsed = ecmascript::parse("+275760-09-13T00:00:00.000Z").unwrap();
+	/// assert_eq!(parsed.time_value(), Some(8_640_000_000_000_000));
+	///
+	/// let parsed = ecmascript::parse("-000001-01-01").unwrap();
+	/// assert_eq!(parsed.time_value(), Some(-62_198_755_200_000));
+	/// ```
+	///
+	/// [ecma-262-time-values]: https://tc39.es/ecma262/#sec-time-values-and-time-range
+	pub fn time_value(&self) -> Option<i64> {
+		let offset = self.offset?;
+		Some(self.local_time_value - offset.total_minutes() as i64 * 60_000)
+	}
+
+	/// The date and time as written, without applying the offset.
+	///
+	/// This returns `None` for years outside of the range of [`NaiveDate`].
+	pub fn to_naive_datetime(&self) -> Option<NaiveDateTime> {
+		NaiveDate::from_ymd_opt(1970, 1, 1)?
+			.and_hms_opt(0, 0, 0)?
+			.checked_add_signed(Duration::milliseconds(self.local_time_value))
+	}
+
+	/// Converts this date and time to UTC, using `local` as the local time zone.
+	///
+	/// As in ECMA-262, a local time that is repeated by a time zone transition is
+	/// resolved to the earlier instant, and a local time that is skipped by one
+	/// is resolved with the offset from before the transition.
+	///
+	/// This returns `None` if the result is out of the range of a time value,
+	/// or of [`DateTime`].
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::{FixedOffset, Utc};
+	/// use whatwg_datetime::{ecmascript, parse_global_datetime};
+	///
+	/// let pacific = FixedOffset::west_opt(8 * 3600).unwrap();
+	///
+	/// // date-time forms without an offset are in local time
+	/// let parsed = ecmascript::parse("2011-11-18T14:54:39.929").unwrap();
+	/// assert_eq!(
+	///     parsed.to_utc(&pacific),
+	///     parse_global_datetime("2011-11-18T22:54:39.929Z")
+	/// );
+	///
+	/// // but date-only forms are in UTC
+	/// let parsed = ecmascript::parse("2011-11-18").unwrap();
+	/// assert_eq!(parsed.to_utc(&pacific), parse_global_datetime("2011-11-18T00:00Z"));
+	/// ```
+	pub fn to_utc<Tz: TimeZone>(&self, local: &Tz) -> Option<DateTime<Utc>> {
+		let time_value = match self.time_value() {
+			Some(time_value) => time_value,
+			None => {
+				let naive = self.to_naive_datetime()?;
+				let offset = match local.from_local_datetime(&naive) {
+					LocalResult::Single(datetime) => datetime.offset().fix(),
+					LocalResult::Ambiguous(earliest, _) => {
+						earliest.offset().fix()
+					}
+					LocalResult::None => local
+						.offset_from_utc_datetime(
+							&(naive - Duration::days(1)),
+						)
+						.fix(),
+				};
+				self.local_time_value - offset.local_minus_utc() as i64 * 1000
+			}
+		};
+		if time_value.abs() > MAX_TIME_VALUE {
+			return None;
+		}
+
+		let naive = NaiveDate::from_ymd_opt(1970, 1, 1)?
+			.and_hms_opt(0, 0, 0)?
+			.checked_add_signed(Duration::milliseconds(time_value))?;
+		Some(DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc))
+	}
+}
+
+/// Parse a string in the ECMA-262 Date Time String Format
+///
+/// This accepts the date-only forms `YYYY`, `YYYY-MM` and `YYYY-MM-DD`, each
+/// optionally followed by `THH:mm`, `THH:mm:ss` or `THH:mm:ss.s`, with one or
+/// more digits for the fraction of a second, and then an
+/// optional `Z` or `±HH:mm`. The year may also be written as a sign followed
+/// by six digits, though `-000000` is not allowed.
+///
+/// Out of range fields, such as `2011-02-30`, are rejected, as are
+/// dates and times more than 100,000,000 days from the epoch.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::ecmascript;
+///
+/// assert!(ecmascript::parse("2011-11-18T14:54:39.929Z").is_some());
+/// assert!(ecmascript::parse("2011-11-18T14:54:39.9Z").is_some());
+/// assert!(ecmascript::parse("2011-11-18T24:00").is_some());
+/// assert!(ecmascript::parse("0000").is_some());
+///
+/// assert!(ecmascript::parse("2011-11-18 14:54").is_none());
+/// assert!(ecmascript::parse("2011-11-18T14:54:39.Z").is_none());
+/// assert!(ecmascript::parse("-000000-01-01").is_none());
+/// ```
+pub fn parse(s: &str) -> Option<EcmaScriptDateTime> {
+	if !s.is_ascii() {
+		return None;
+	}
+
+	let mut position = 0usize;
+	let year = parse_year(s, &mut position)?;
+	let (mut month, mut day) = (1, 1);
+	if s[position..].starts_with(TOKEN_HYPHEN) {
+		position += 1;
+		month = collect_month_and_validate(s, &mut position)?;
+		if s[position..].starts_with(TOKEN_HYPHEN) {
+			position += 1;
+			day = collect_day_and_validate(s, &mut position, month)?;
+			if day > max_days_in_month_year(month, year.rem_euclid(400) as u32)? {
+				return None;
+			}
+		}
+	}
+
+	let days = days_from_civil(year as i64, month, day);
+	if position == s.len() {
+		// Date-only forms are in UTC
+		return checked_time_value(days * MS_PER_DAY, Some(TimeZoneOffset::utc()));
+	}
+
+	if !s[position..].starts_with(TOKEN_T) {
+		return None;
+	}
+	position += 1;
+
+	let time_end = s[position..]
+		.find([TOKEN_Z, TOKEN_PLUS, TOKEN_MINUS])
+		.map_or(s.len(), |offset| position + offset);
+	let milliseconds = parse_time_milliseconds(&s[position..time_end])?;
+
+	let offset = match &s[time_end..] {
+		"" => None,
+		"Z" => Some(TimeZoneOffset::utc()),
+		offset if offset.len() == 6 && offset[3..].starts_with(TOKEN_COLON) => {
+			Some(parse_timezone_offset(offset)?)
+		}
+		_ => return None,
+	};
+
+	checked_time_value(days * MS_PER_DAY + milliseconds, offset)
+}
+
+/// Parses `YYYY`, or `±YYYYYY` except for `-000000`.
+fn parse_year(s: &str, position: &mut usize) -> Option<i32> {
+	let sign = match s.as_bytes().first() {
+		Some(b'+') => 1,
+		Some(b'-') => -1,
+		_ => {
+			let parsed_year = collect_ascii_digits(s, position);
+			if parsed_year.len() != 4 {
+				return None;
+			}
+			return parsed_year.parse().ok();
+		}
+	};
+
+	*position += 1;
+	let parsed_year = collect_ascii_digits(s, position);
+	if parsed_year.len() != 6 || (sign == -1 && parsed_year == "000000") {
+		return None;
+	}
+
+	Some(sign * parsed_year.parse::<i32>().ok()?)
+}
+
+/// Parses `HH:mm`, `HH:mm:ss` or `HH:mm:ss.s` into milliseconds since midnight,
+/// where `24:00`, `24:00:00` and `24:00:00.0` are the end of the day.
+/// Digits of the fraction past milliseconds are truncated.
+fn parse_time_milliseconds(s: &str) -> Option<i64> {
+	let has_fraction = s.len() >= 10 && s[8..].starts_with(TOKEN_DOT);
+	if !matches!(s.len(), 5 | 8) && !has_fraction {
+		return None;
+	}
+
+	if let Some(rest) = s.strip_prefix("24") {
+		let is_midnight = rest
+			.bytes()
+			.all(|c| c == b'0' || c == TOKEN_COLON as u8 || c == TOKEN_DOT as u8);
+		if !is_midnight {
+			return None;
+		}
+
+		parse_format(&format!("00{}", rest), parse_time_component)?;
+		return Some(MS_PER_DAY);
+	}
+
+	let time = parse_format(s, parse_time_component)?;
+	Some(time.num_seconds_from_midnight() as i64 * 1000
+		+ (time.nanosecond() / 1_000_000) as i64)
+}
+
+fn checked_time_value(
+	local_time_value: i64,
+	offset: Option<TimeZoneOffset>,
+) -> Option<EcmaScriptDateTime> {
+	let parsed = EcmaScriptDateTime {
+		local_time_value,
+		offset,
+	};
+
+	// The offset of local time is not known yet, so it is checked by `to_utc`
+	let time_value = parsed.time_value().unwrap_or(local_time_value);
+	if time_value.abs() > MAX_TIME_VALUE {
+		return None;
+	}
+
+	Some(parsed)
+}
+
+/// The number of days from 1970-01-01 to a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let year_of_era = year.rem_euclid(400);
+	let month = month as i64;
+	let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+	era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{days_from_civil, parse};
+	use crate::{parse_global_datetime, TimeZoneOffset};
+	use chrono::{Datelike, FixedOffset, NaiveDate, Utc};
+
+	#[test]
+	fn test_days_from_civil_matches_chrono() {
+		let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+		for date in [
+			NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+			NaiveDate::from_ymd_opt(2000, 2, 29).unwrap(),
+			NaiveDate::from_ymd_opt(0, 3, 1).unwrap(),
+			NaiveDate::from_ymd_opt(-1, 12, 31).unwrap(),
+			NaiveDate::from_ymd_opt(-4713, 11, 24).unwrap(),
+		] {
+			assert_eq!(
+				days_from_civil(date.year() as i64, date.month(), date.day()),
+				date.signed_duration_since(epoch).num_days(),
+			);
+		}
+	}
+
+	#[test]
+	fn test_parse_date_only_forms_are_utc() {
+		for s in ["2011", "2011-11", "2011-11-18", "+002011-11-18"] {
+			let parsed = parse(s).unwrap();
+			assert_eq!(parsed.offset(), Some(TimeZoneOffset::utc()), "{}", s);
+		}
+		assert_eq!(
+			parse("2011-11").unwrap().to_utc(&Utc),
+			parse_global_datetime("2011-11-01T00:00Z")
+		);
+	}
+
+	#[test]
+	fn test_parse_date_time_forms() {
+		let parsed = parse("2011-11-18T14:54:39.929+05:30").unwrap();
+		assert_eq!(
+			parsed.to_utc(&Utc),
+			parse_global_datetime("2011-11-18T09:24:39.929Z")
+		);
+
+		let parsed = parse("2011-11-18T14:54").unwrap();
+		assert!(parsed.is_local());
+		assert_eq!(parsed.time_value(), None);
+		assert_eq!(
+			parsed.to_utc(&FixedOffset::east_opt(3600).unwrap()),
+			parse_global_datetime("2011-11-18T13:54Z")
+		);
+
+		assert_eq!(
+			parse("2011-11-18T24:00:00.000Z").unwrap().to_utc(&Utc),
+			parse_global_datetime("2011-11-19T00:00Z")
+		);
+		assert_eq!(
+			parse("2011T14:54Z").unwrap().to_utc(&Utc),
+			parse_global_datetime("2011-01-01T14:54Z")
+		);
+	}
+
+	#[test]
+	fn test_parse_fraction_digits_like_v8() {
+		for (s, expected) in [
+			("2011-11-18T14:54:39.9Z", "2011-11-18T14:54:39.900Z"),
+			("2011-11-18T14:54:39.92Z", "2011-11-18T14:54:39.920Z"),
+			("2011-11-18T14:54:39.9299Z", "2011-11-18T14:54:39.929Z"),
+			("2011-11-18T24:00:00.0Z", "2011-11-19T00:00Z"),
+		] {
+			assert_eq!(
+				parse(s).unwrap().to_utc(&Utc),
+				parse_global_datetime(expected),
+				"{}",
+				s
+			);
+		}
+	}
+
+	#[test]
+	fn test_parse_time_value_range() {
+		assert_eq!(
+			parse("-271821-04-20T00:00:00.000Z").unwrap().time_value(),
+			Some(-8_640_000_000_000_000)
+		);
+		assert_eq!(parse("+275760-09-13T00:00:00.001Z"), None);
+		assert_eq!(parse("-271821-04-19T23:59:59.999Z"), None);
+		// out of the range of chrono
+		assert_eq!(parse("+275760-09-13").unwrap().to_utc(&Utc), None);
+	}
+
+	#[test]
+	fn test_parse_fails() {
+		for s in [
+			"",
+			"11-18",
+			"02011-11-18",
+			"+2011-11-18",
+			"-000000-01-01",
+			"2011-02-29",
+			"2011-11-18T",
+			"2011-11-18t14:54",
+			"2011-11-18T14:54z",
+			"2011-11-18T14:54+0530",
+			"2011-11-18T14:54:39.",
+			"2011-11-18T14:54:39.Z",
+			"2011-11-18T14:54:39.9.2",
+			"2011-11-18T24:00:00.0001",
+			"2011-11-18T24:01",
+			"2011-11-18T14:60",
+			"2011-11-18T14:54:39.929+05:30 ",
+		] {
+			assert_eq!(parse(s), None, "{}", s);
+		}
+	}
+}
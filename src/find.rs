@@ -0,0 +1,249 @@
+use crate::tokens::{TOKEN_P, TOKEN_SPACE, TOKEN_T};
+use crate::{
+	parse_date_component, parse_duration_component, parse_month_component,
+	parse_time_component, parse_timezone_offset_component, parse_week_component,
+	to_utc_datetime, WhatwgDateTime,
+};
+use std::ops::Range;
+
+/// A datetime string found in text by [`find_iter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match<'a> {
+	pub(crate) text: &'a str,
+	pub(crate) span: Range<usize>,
+	pub(crate) value: WhatwgDateTime,
+}
+
+impl<'a> Match<'a> {
+	/// The byte range of the match in the searched text.
+	#[inline]
+	pub fn span(&self) -> Range<usize> {
+		self.span.clone()
+	}
+
+	/// The byte offset of the start of the match.
+	#[inline]
+	pub fn start(&self) -> usize {
+		self.span.start
+	}
+
+	/// The byte offset of the end of the match.
+	#[inline]
+	pub fn end(&self) -> usize {
+		self.span.end
+	}
+
+	/// The matched substring.
+	#[inline]
+	pub fn as_str(&self) -> &'a str {
+		self.text
+	}
+
+	/// The parsed value of the match.
+	#[inline]
+	pub const fn value(&self) -> WhatwgDateTime {
+		self.value
+	}
+}
+
+/// An iterator over the datetime strings in a text, created by [`find_iter`].
+#[derive(Debug, Clone)]
+pub struct FindIter<'a> {
+	text: &'a str,
+	position: usize,
+}
+
+impl<'a> Iterator for FindIter<'a> {
+	type Item = Match<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let bytes = self.text.as_bytes();
+		while self.position < bytes.len() {
+			let start = self.position;
+			self.position += 1;
+
+			let is_candidate =
+				bytes[start].is_ascii_digit() || bytes[start] == TOKEN_P as u8;
+			if !is_candidate || (start > 0 && bytes[start - 1].is_ascii_alphanumeric())
+			{
+				continue;
+			}
+
+			if let Some((end, value)) = longest_match_at(self.text, start) {
+				self.position = end;
+				return Some(Match {
+					text: &self.text[start..end],
+					span: start..end,
+					value,
+				});
+			}
+		}
+
+		None
+	}
+}
+
+/// Finds every valid datetime string in a text, such as a log, markdown, or an email
+///
+/// This finds dates, times, months, weeks, local and global datetimes, and durations.
+/// Each match is the longest valid string starting at its position, and matches
+/// do not overlap. A match must not be directly preceded or followed by an ASCII
+/// letter or digit, so that datetime strings are not found in the middle of words.
+///
+/// Candidates are read by the same component parsers as
+/// [`parse_date`], [`parse_global_datetime`], etc.
+///
+/// [`parse_date`]: crate::parse_date
+/// [`parse_global_datetime`]: crate::parse_global_datetime
+///
+/// # Examples
+/// ```
+/// use chrono::Duration;
+/// use whatwg_datetime::{find_iter, WhatwgDateTime};
+///
+/// let text = "Deployed 2011-11-18T14:54:39Z, rolled back after 4h 18m. Next try: 2011-W47.";
+/// let matches: Vec<_> = find_iter(text).collect();
+///
+/// assert_eq!(matches.len(), 3);
+/// assert_eq!(matches[0].as_str(), "2011-11-18T14:54:39Z");
+/// assert_eq!(
+///     matches[1].value(),
+///     WhatwgDateTime::Duration(Duration::minutes(258))
+/// );
+/// assert_eq!(&text[matches[2].span()], "2011-W47");
+/// ```
+#[inline]
+pub fn find_iter(text: &str) -> FindIter<'_> {
+	FindIter { text, position: 0 }
+}
+
+/// Finds the longest valid datetime string starting at `start`,
+/// returning its end along with its value.
+///
+/// Each component parser is run once from `start`, and the furthest end
+/// that is followed by a word boundary wins. On a tie, the kind that is
+/// tried first wins.
+fn longest_match_at(text: &str, start: usize) -> Option<(usize, WhatwgDateTime)> {
+	// Datetime strings are ASCII, and the component parsers index by
+	// character, so the candidate is limited to the run of ASCII characters.
+	let end = text[start..]
+		.find(|c: char| !c.is_ascii())
+		.map_or(text.len(), |offset| start + offset);
+	let s = &text[start..end];
+
+	let mut longest: Option<(usize, WhatwgDateTime)> = None;
+	let mut keep = |position: usize, value: WhatwgDateTime| {
+		let is_boundary = s
+			.as_bytes()
+			.get(position)
+			.map_or(true, |b| !b.is_ascii_alphanumeric());
+		if is_boundary && longest.as_ref().map_or(true, |(end, _)| position > *end) {
+			longest = Some((position, value));
+		}
+	};
+
+	// A date, which may be extended into a local or global datetime
+	let mut position = 0usize;
+	if let Some(date) = parse_date_component(s, &mut position) {
+		keep(position, WhatwgDateTime::Date(date));
+
+		if matches!(s.chars().nth(position), Some(TOKEN_T) | Some(TOKEN_SPACE)) {
+			position += 1;
+			if let Some(time) = parse_time_component(s, &mut position) {
+				keep(position, WhatwgDateTime::LocalDateTime(date.and_time(time)));
+
+				let global = parse_timezone_offset_component(s, &mut position)
+					.and_then(|offset| to_utc_datetime(date, time, offset));
+				if let Some(global) = global {
+					keep(position, WhatwgDateTime::GlobalDateTime(global));
+				}
+			}
+		}
+	}
+
+	let mut position = 0usize;
+	if let Some(year_month) = parse_month_component(s, &mut position) {
+		keep(position, WhatwgDateTime::Month(year_month));
+	}
+
+	let mut position = 0usize;
+	if let Some(year_week) = parse_week_component(s, &mut position) {
+		keep(position, WhatwgDateTime::Week(year_week));
+	}
+
+	let mut position = 0usize;
+	if let Some(time) = parse_time_component(s, &mut position) {
+		keep(position, WhatwgDateTime::Time(time));
+	}
+
+	let mut position = 0usize;
+	if let Some(duration) = parse_duration_component(s, &mut position) {
+		keep(position, WhatwgDateTime::Duration(duration));
+	}
+
+	longest.map(|(position, value)| (start + position, value))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::find_iter;
+	use crate::{WhatwgDateTime, YearMonth};
+	use chrono::{Duration, NaiveDate, NaiveTime};
+
+	fn find_all(text: &str) -> Vec<&str> {
+		find_iter(text).map(|m| m.as_str()).collect()
+	}
+
+	#[test]
+	fn test_find_iter_longest_match_wins() {
+		assert_eq!(
+			find_all("from 2011-11-18 14:54:39.929+05:30 until 2011-11-18"),
+			["2011-11-18 14:54:39.929+05:30", "2011-11-18"]
+		);
+		assert_eq!(find_all("PT2H30M, P1DT and 3h 4h"), ["PT2H30M", "3h", "4h"]);
+	}
+
+	#[test]
+	fn test_find_iter_each_kind() {
+		let values: Vec<WhatwgDateTime> = find_iter("2011-11, 2011-11-18, 14:54. Took 3s.")
+			.map(|m| m.value())
+			.collect();
+		assert_eq!(
+			values,
+			[
+				WhatwgDateTime::Month(YearMonth::new(2011, 11)),
+				WhatwgDateTime::Date(
+					NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+				),
+				WhatwgDateTime::Time(NaiveTime::from_hms_opt(14, 54, 0).unwrap()),
+				WhatwgDateTime::Duration(Duration::seconds(3)),
+			]
+		);
+	}
+
+	#[test]
+	fn test_find_iter_spans() {
+		let text = "– at 14:54 –";
+		let found: Vec<_> = find_iter(text).collect();
+		assert_eq!(found.len(), 1);
+		assert_eq!(found[0].span(), 7..12);
+		assert_eq!(&text[found[0].span()], "14:54");
+	}
+
+	#[test]
+	fn test_find_iter_word_boundaries() {
+		assert!(find_all("v2011-11-18 abc3s 2011-13-01 3 days 12:00am PT1H2").is_empty());
+		assert_eq!(find_all("(2011-11-18)"), ["2011-11-18"]);
+		assert!(find_all("").is_empty());
+	}
+
+	#[test]
+	fn test_find_iter_week_year_out_of_range() {
+		assert!(find_all("see 999999-W01 x").is_empty());
+	}
+
+	#[test]
+	fn test_find_iter_time_in_text_with_decimals() {
+		assert_eq!(find_all("Lap 1.5 at 14:54:39, lap 2.5 later"), ["14:54:39"]);
+	}
+}
@@ -0,0 +1,736 @@
+//! Extracting datetime values from HTML documents.
+//!
+//! This module is only available with the `html` feature enabled.
+
+use crate::{
+	parse_date_or_time, parse_datetime_value, DateOrTime, InputType, ParseMode, WhatwgDateTime,
+};
+use html5ever::tendril::StrTendril;
+use html5ever::tokenizer::{
+	BufferQueue, Tag, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer,
+};
+use html5ever::tree_builder::{
+	Attribute, ElementFlags, NodeOrText, QuirksMode, TreeBuilder, TreeSink,
+};
+use html5ever::{ns, QualName, TokenizerResult};
+use std::borrow::Cow;
+use std::cell::{Cell, Ref, RefCell};
+use std::ops::Range;
+
+/// The kind of element a datetime value was found on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+	/// A [`<time>`][whatwg-html-time] element.
+	///
+	/// [whatwg-html-time]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-time-element
+	Time,
+	/// An [`<ins>`][whatwg-html-ins] element.
+	///
+	/// [whatwg-html-ins]: https://html.spec.whatwg.org/multipage/edits.html#the-ins-element
+	Ins,
+	/// A [`<del>`][whatwg-html-del] element.
+	///
+	/// [whatwg-html-del]: https://html.spec.whatwg.org/multipage/edits.html#the-del-element
+	Del,
+	/// An `<input>` element with a date and time related `type` attribute.
+	Input(InputType),
+}
+
+/// Where the raw string of a datetime value was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueSource {
+	/// The `datetime` attribute of a `<time>`, `<ins>` or `<del>` element,
+	/// or the `value` attribute of an `<input>` element.
+	Attribute,
+	/// The text content of a `<time>` element without a `datetime` attribute.
+	TextContent,
+}
+
+/// A datetime value found on an element of an HTML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateTimeElement {
+	pub(crate) kind: ElementKind,
+	pub(crate) source: ValueSource,
+	pub(crate) raw: String,
+	pub(crate) value: Option<WhatwgDateTime>,
+	pub(crate) location: Range<usize>,
+}
+
+impl DateTimeElement {
+	/// The kind of element the value was found on.
+	#[inline]
+	pub const fn kind(&self) -> ElementKind {
+		self.kind
+	}
+
+	/// Where the raw string of the value was taken from.
+	#[inline]
+	pub const fn source(&self) -> ValueSource {
+		self.source
+	}
+
+	/// The raw string of the value, as written in the document.
+	#[inline]
+	pub fn raw(&self) -> &str {
+		&self.raw
+	}
+
+	/// The parsed value, or `None` if the raw string is not valid for the element.
+	#[inline]
+	pub const fn value(&self) -> Option<WhatwgDateTime> {
+		self.value
+	}
+
+	/// The byte range of the start tag of the element in the input.
+	#[inline]
+	pub fn location(&self) -> Range<usize> {
+		self.location.clone()
+	}
+}
+
+/// Extracts the datetime value of every `<time>`, `<ins>`, `<del>`,
+/// and date and time related `<input>` element of an HTML document,
+/// in document order
+///
+/// The document is parsed with the html5ever tree builder, so elements
+/// are opened and closed as a browser would, including implied end tags
+/// and elements left open at the end of the document.
+///
+/// - The value of a `<time>` element is its `datetime` attribute, or
+///   its [child text content][dom-child-text-content] if the attribute is
+///   missing, parsed with [`parse_datetime_value`].
+/// - The value of an `<ins>` or `<del>` element is its `datetime` attribute,
+///   which must be a date or a global datetime. Elements without the
+///   attribute are skipped.
+/// - The value of an `<input>` element is its `value` attribute, parsed
+///   according to its `type` attribute. Elements without the attribute,
+///   or that are not date and time related, are skipped.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::html::{extract_datetime_values, ElementKind, ValueSource};
+/// use whatwg_datetime::WhatwgDateTime;
+///
+/// let elements = extract_datetime_values("<p>Posted on\n<time> 2011-11-18 </time></p>");
+///
+/// assert_eq!(elements.len(), 1);
+/// assert_eq!(elements[0].kind(), ElementKind::Time);
+/// assert_eq!(elements[0].source(), ValueSource::TextContent);
+/// assert_eq!(elements[0].location(), 13..19);
+/// assert_eq!(
+///     elements[0].value(),
+///     Some(WhatwgDateTime::Date(NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()))
+/// );
+/// ```
+///
+/// [dom-child-text-content]: https://dom.spec.whatwg.org/#concept-child-text-content
+pub fn extract_datetime_values(html: &str) -> Vec<DateTimeElement> {
+	let sink = LocatingSink {
+		tree_builder: TreeBuilder::new(Dom::default(), Default::default()),
+		html,
+		chunk: Cell::new((0, 0)),
+		last_token_chunk_start: Cell::new(0),
+	};
+	let tokenizer = Tokenizer::new(sink, Default::default());
+	let input = BufferQueue::default();
+
+	// Tokens are only emitted once the tokenizer has seen the end of them, so
+	// the input is fed in chunks that end at every `<` and `>` to tell where a
+	// start tag ends. A start tag begins at the first `<` since the chunk that
+	// emitted the previous token.
+	let mut chunk_start = 0;
+	for (index, _) in html.match_indices(['<', '>']) {
+		for chunk in [chunk_start..index, index..index + 1] {
+			if !chunk.is_empty() {
+				feed(&tokenizer, &input, chunk);
+			}
+		}
+		chunk_start = index + 1;
+	}
+	if chunk_start < html.len() {
+		feed(&tokenizer, &input, chunk_start..html.len());
+	}
+	tokenizer.end();
+
+	let dom = tokenizer.sink.tree_builder.sink;
+	let mut elements = Vec::new();
+	dom.collect_datetime_elements(Dom::DOCUMENT, &mut elements);
+	elements
+}
+
+fn feed(tokenizer: &Tokenizer<LocatingSink<'_>>, input: &BufferQueue, chunk: Range<usize>) {
+	tokenizer.sink.chunk.set((chunk.start, chunk.end));
+	input.push_back(StrTendril::from_slice(&tokenizer.sink.html[chunk]));
+
+	// Feeding stops after every `</script>`, which can be resumed as is
+	while !matches!(tokenizer.feed(input), TokenizerResult::Done) {}
+}
+
+/// Passes tokens on to the tree builder, recording the
+/// byte range of every start tag along the way.
+struct LocatingSink<'h> {
+	tree_builder: TreeBuilder<usize, Dom>,
+	html: &'h str,
+	/// The byte range of the chunk being fed to the tokenizer.
+	chunk: Cell<(usize, usize)>,
+	/// The start of the chunk that emitted the last token.
+	last_token_chunk_start: Cell<usize>,
+}
+
+impl TokenSink for LocatingSink<'_> {
+	type Handle = usize;
+
+	fn process_token(&self, token: Token, line_number: u64) -> TokenSinkResult<usize> {
+		let (chunk_start, chunk_end) = self.chunk.get();
+		match &token {
+			// Parse errors can be emitted from the middle of a tag
+			Token::ParseError(_) => (),
+			Token::TagToken(Tag {
+				kind: TagKind::StartTag,
+				name,
+				..
+			}) => {
+				let search_start = self.last_token_chunk_start.get();
+				let start = self.html[search_start..chunk_end]
+					.find('<')
+					.map_or(search_start, |offset| search_start + offset);
+				*self.tree_builder.sink.start_tag.borrow_mut() =
+					Some((name.clone(), start..chunk_end));
+				self.last_token_chunk_start.set(chunk_start);
+			}
+			_ => self.last_token_chunk_start.set(chunk_start),
+		}
+
+		self.tree_builder.process_token(token, line_number)
+	}
+
+	fn end(&self) {
+		self.tree_builder.end();
+	}
+
+	fn adjusted_current_node_present_but_not_in_html_namespace(&self) -> bool {
+		self.tree_builder
+			.adjusted_current_node_present_but_not_in_html_namespace()
+	}
+}
+
+struct Node {
+	parent: Option<usize>,
+	children: Vec<usize>,
+	data: NodeData,
+}
+
+enum NodeData {
+	Document,
+	/// The contents of a `<template>` element, which are not
+	/// part of the document tree.
+	TemplateContents,
+	Element {
+		name: QualName,
+		attrs: Vec<Attribute>,
+		template_contents: Option<usize>,
+		/// The byte range of the start tag, which is empty for elements
+		/// the tree builder inserted without a start tag.
+		location: Range<usize>,
+	},
+	Text(StrTendril),
+	/// Comments and processing instructions, whose contents are not needed.
+	Other,
+}
+
+/// A minimal document tree, built by the html5ever tree builder.
+struct Dom {
+	nodes: RefCell<Vec<Node>>,
+	/// The name and byte range of the start tag being processed.
+	start_tag: RefCell<Option<(html5ever::LocalName, Range<usize>)>>,
+}
+
+impl Default for Dom {
+	fn default() -> Self {
+		Self {
+			nodes: RefCell::new(vec![Node {
+				parent: None,
+				children: Vec::new(),
+				data: NodeData::Document,
+			}]),
+			start_tag: RefCell::new(None),
+		}
+	}
+}
+
+impl Dom {
+	const DOCUMENT: usize = 0;
+
+	fn new_node(&self, data: NodeData) -> usize {
+		let mut nodes = self.nodes.borrow_mut();
+		nodes.push(Node {
+			parent: None,
+			children: Vec::new(),
+			data,
+		});
+		nodes.len() - 1
+	}
+
+	fn detach(&self, node: usize) {
+		let mut nodes = self.nodes.borrow_mut();
+		if let Some(parent) = nodes[node].parent.take() {
+			nodes[parent].children.retain(|&child| child != node);
+		}
+	}
+
+	/// Inserts a child into `parent` at `index`, merging text into an
+	/// adjacent text node instead of creating a new one.
+	fn insert(&self, parent: usize, index: usize, child: NodeOrText<usize>) {
+		let child = match child {
+			NodeOrText::AppendNode(child) => {
+				self.detach(child);
+				child
+			}
+			NodeOrText::AppendText(text) => {
+				let previous = index.checked_sub(1).map(|previous| {
+					self.nodes.borrow()[parent].children[previous]
+				});
+				if let Some(previous) = previous {
+					if let NodeData::Text(previous_text) =
+						&mut self.nodes.borrow_mut()[previous].data
+					{
+						previous_text.push_tendril(&text);
+						return;
+					}
+				}
+				self.new_node(NodeData::Text(text))
+			}
+		};
+
+		let mut nodes = self.nodes.borrow_mut();
+		nodes[child].parent = Some(parent);
+		nodes[parent].children.insert(index, child);
+	}
+
+	fn collect_datetime_elements(&self, node: usize, elements: &mut Vec<DateTimeElement>) {
+		let nodes = self.nodes.borrow();
+		if let NodeData::Element {
+			name,
+			attrs,
+			location,
+			..
+		} = &nodes[node].data
+		{
+			if name.ns == ns!(html) {
+				let element = datetime_element(
+					&name.local,
+					attrs,
+					location.clone(),
+					|| self.child_text_content(node),
+				);
+				elements.extend(element);
+			}
+		}
+
+		for &child in &nodes[node].children {
+			self.collect_datetime_elements(child, elements);
+		}
+	}
+
+	/// The concatenated text of the text node children of an element.
+	fn child_text_content(&self, node: usize) -> String {
+		let nodes = self.nodes.borrow();
+		nodes[node]
+			.children
+			.iter()
+			.filter_map(|&child| match &nodes[child].data {
+				NodeData::Text(text) => Some(&**text),
+				_ => None,
+			})
+			.collect()
+	}
+}
+
+impl TreeSink for Dom {
+	type Handle = usize;
+	type Output = Self;
+	type ElemName<'a> = Ref<'a, QualName>;
+
+	fn finish(self) -> Self {
+		self
+	}
+
+	fn parse_error(&self, _msg: Cow<'static, str>) {}
+
+	fn get_document(&self) -> usize {
+		Self::DOCUMENT
+	}
+
+	fn elem_name<'a>(&'a self, target: &'a usize) -> Ref<'a, QualName> {
+		Ref::map(self.nodes.borrow(), |nodes| match &nodes[*target].data {
+			NodeData::Element { name, .. } => name,
+			_ => panic!("not an element"),
+		})
+	}
+
+	fn create_element(
+		&self,
+		name: QualName,
+		attrs: Vec<Attribute>,
+		flags: ElementFlags,
+	) -> usize {
+		let template_contents = if flags.template {
+			Some(self.new_node(NodeData::TemplateContents))
+		} else {
+			None
+		};
+		// Elements implied by the start tag are created before its own element
+		let mut start_tag = self.start_tag.borrow_mut();
+		let location = match start_tag.as_ref() {
+			Some((tag_name, _)) if *tag_name == name.local => {
+				start_tag.take().unwrap().1
+			}
+			_ => 0..0,
+		};
+		drop(start_tag);
+
+		self.new_node(NodeData::Element {
+			name,
+			attrs,
+			template_contents,
+			location,
+		})
+	}
+
+	fn create_comment(&self, _text: StrTendril) -> usize {
+		self.new_node(NodeData::Other)
+	}
+
+	fn create_pi(&self, _target: StrTendril, _data: StrTendril) -> usize {
+		self.new_node(NodeData::Other)
+	}
+
+	fn append(&self, parent: &usize, child: NodeOrText<usize>) {
+		let index = self.nodes.borrow()[*parent].children.len();
+		self.insert(*parent, index, child);
+	}
+
+	fn append_based_on_parent_node(
+		&self,
+		element: &usize,
+		prev_element: &usize,
+		child: NodeOrText<usize>,
+	) {
+		if self.nodes.borrow()[*element].parent.is_some() {
+			self.append_before_sibling(element, child);
+		} else {
+			self.append(prev_element, child);
+		}
+	}
+
+	fn append_doctype_to_document(
+		&self,
+		_name: StrTendril,
+		_public_id: StrTendril,
+		_system_id: StrTendril,
+	) {
+	}
+
+	fn get_template_contents(&self, target: &usize) -> usize {
+		match &self.nodes.borrow()[*target].data {
+			NodeData::Element {
+				template_contents: Some(template_contents),
+				..
+			} => *template_contents,
+			_ => panic!("not a template element"),
+		}
+	}
+
+	fn same_node(&self, x: &usize, y: &usize) -> bool {
+		x == y
+	}
+
+	fn set_quirks_mode(&self, _mode: QuirksMode) {}
+
+	fn append_before_sibling(&self, sibling: &usize, new_node: NodeOrText<usize>) {
+		if let NodeOrText::AppendNode(new_node) = new_node {
+			self.detach(new_node);
+		}
+		let (parent, index) = {
+			let nodes = self.nodes.borrow();
+			// The tree builder only inserts before nodes that have a parent
+			let parent = nodes[*sibling].parent.unwrap();
+			let index = nodes[parent]
+				.children
+				.iter()
+				.position(|child| child == sibling)
+				.unwrap();
+			(parent, index)
+		};
+		self.insert(parent, index, new_node);
+	}
+
+	fn add_attrs_if_missing(&self, target: &usize, new_attrs: Vec<Attribute>) {
+		if let NodeData::Element { attrs, .. } = &mut self.nodes.borrow_mut()[*target].data
+		{
+			for new_attr in new_attrs {
+				if !attrs.iter().any(|attr| attr.name == new_attr.name) {
+					attrs.push(new_attr);
+				}
+			}
+		}
+	}
+
+	fn remove_from_parent(&self, target: &usize) {
+		self.detach(*target);
+	}
+
+	fn reparent_children(&self, node: &usize, new_parent: &usize) {
+		let mut nodes = self.nodes.borrow_mut();
+		let children = std::mem::take(&mut nodes[*node].children);
+		for &child in &children {
+			nodes[child].parent = Some(*new_parent);
+		}
+		nodes[*new_parent].children.extend(children);
+	}
+}
+
+fn datetime_element(
+	tag_name: &str,
+	attrs: &[Attribute],
+	location: Range<usize>,
+	child_text_content: impl FnOnce() -> String,
+) -> Option<DateTimeElement> {
+	let (kind, source, raw) = match tag_name {
+		"time" => match attribute(attrs, "datetime") {
+			Some(raw) => (ElementKind::Time, ValueSource::Attribute, raw.to_owned()),
+			None => (
+				ElementKind::Time,
+				ValueSource::TextContent,
+				child_text_content(),
+			),
+		},
+		"ins" => (
+			ElementKind::Ins,
+			ValueSource::Attribute,
+			attribute(attrs, "datetime")?.to_owned(),
+		),
+		"del" => (
+			ElementKind::Del,
+			ValueSource::Attribute,
+			attribute(attrs, "datetime")?.to_owned(),
+		),
+		"input" => {
+			let input_type = attribute(attrs, "type")?.parse::<InputType>().ok()?;
+			(
+				ElementKind::Input(input_type),
+				ValueSource::Attribute,
+				attribute(attrs, "value")?.to_owned(),
+			)
+		}
+		_ => return None,
+	};
+	let mode = match source {
+		ValueSource::Attribute => ParseMode::Attribute,
+		ValueSource::TextContent => ParseMode::Content,
+	};
+
+	Some(DateTimeElement {
+		kind,
+		source,
+		value: parse_element_value(kind, &raw, mode),
+		raw,
+		location,
+	})
+}
+
+fn attribute<'a>(attrs: &'a [Attribute], name: &str) -> Option<&'a str> {
+	attrs.iter()
+		.find(|attr| &*attr.name.local == name)
+		.map(|attr| &*attr.value)
+}
+
+fn parse_element_value(kind: ElementKind, raw: &str, mode: ParseMode) -> Option<WhatwgDateTime> {
+	match kind {
+		ElementKind::Time => parse_datetime_value(raw, mode),
+		// https://html.spec.whatwg.org/multipage/edits.html#attr-mod-datetime
+		ElementKind::Ins | ElementKind::Del => match parse_date_or_time(raw, mode)? {
+			DateOrTime::Time(_) => None,
+			value => Some(value.into()),
+		},
+		ElementKind::Input(input_type) => input_type.parse(raw).map(WhatwgDateTime::from),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{extract_datetime_values, ElementKind, ValueSource};
+	use crate::{InputType, WhatwgDateTime, YearMonth, YearWeek};
+	use chrono::{NaiveDate, NaiveTime};
+
+	#[test]
+	fn test_extract_time_attribute() {
+		let elements =
+			extract_datetime_values(r#"<time datetime="2011-11">November</time>"#);
+
+		assert_eq!(elements.len(), 1);
+		assert_eq!(elements[0].kind(), ElementKind::Time);
+		assert_eq!(elements[0].source(), ValueSource::Attribute);
+		assert_eq!(elements[0].raw(), "2011-11");
+		assert_eq!(
+			elements[0].value(),
+			Some(WhatwgDateTime::Month(YearMonth::new(2011, 11)))
+		);
+	}
+
+	#[test]
+	fn test_extract_time_child_text_content() {
+		let elements = extract_datetime_values("<time>14:<!-- comment -->54</time>");
+
+		assert_eq!(elements[0].source(), ValueSource::TextContent);
+		assert_eq!(elements[0].raw(), "14:54");
+		assert_eq!(
+			elements[0].value(),
+			Some(WhatwgDateTime::Time(
+				NaiveTime::from_hms_opt(14, 54, 0).unwrap()
+			))
+		);
+
+		// Only the text of the children counts, not the text of other descendants
+		let elements = extract_datetime_values("<time><b>14</b>:54</time>");
+
+		assert_eq!(elements[0].raw(), ":54");
+		assert_eq!(elements[0].value(), None);
+	}
+
+	#[test]
+	fn test_extract_invalid_value() {
+		let elements = extract_datetime_values("<time>next tuesday</time>");
+
+		assert_eq!(elements.len(), 1);
+		assert_eq!(elements[0].raw(), "next tuesday");
+		assert_eq!(elements[0].value(), None);
+	}
+
+	#[test]
+	fn test_extract_ins_del() {
+		let html = r#"<ins datetime="2011-11-18">a</ins><del datetime="14:54">b</del><ins>c</ins>"#;
+		let elements = extract_datetime_values(html);
+
+		assert_eq!(elements.len(), 2);
+		assert_eq!(elements[0].kind(), ElementKind::Ins);
+		assert_eq!(
+			elements[0].value(),
+			Some(WhatwgDateTime::Date(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+			))
+		);
+		assert_eq!(elements[1].kind(), ElementKind::Del);
+		assert_eq!(elements[1].value(), None);
+	}
+
+	#[test]
+	fn test_extract_inputs() {
+		let html = r#"
+			<input type="WEEK" value="2011-W47">
+			<input type="text" value="2011-W47">
+			<input type="date">
+		"#;
+		let elements = extract_datetime_values(html);
+
+		assert_eq!(elements.len(), 1);
+		assert_eq!(elements[0].kind(), ElementKind::Input(InputType::Week));
+		assert_eq!(
+			&html[elements[0].location()],
+			r#"<input type="WEEK" value="2011-W47">"#
+		);
+		assert_eq!(
+			elements[0].value(),
+			Some(WhatwgDateTime::Week(YearWeek::new(2011, 47)))
+		);
+	}
+
+	#[test]
+	fn test_extract_document_order_with_nested_time() {
+		let html = r#"<time>2011-11-18<time datetime="14:54"></time></time>"#;
+		let elements = extract_datetime_values(html);
+
+		assert_eq!(elements.len(), 2);
+		assert_eq!(elements[0].source(), ValueSource::TextContent);
+		assert_eq!(elements[0].raw(), "2011-11-18");
+		assert_eq!(elements[1].source(), ValueSource::Attribute);
+
+		let html = r#"<time>2011-11-<time datetime="2011">x</time>18</time>"#;
+		let elements = extract_datetime_values(html);
+
+		assert_eq!(elements.len(), 2);
+		assert_eq!(elements[0].raw(), "2011-11-18");
+		assert_eq!(elements[1].raw(), "2011");
+		assert_eq!(elements[1].value(), Some(WhatwgDateTime::Year(2011)));
+
+		let html = r#"<time> 2011-11-18 <time datetime="2011"></time></time>"#;
+		let elements = extract_datetime_values(html);
+
+		assert_eq!(
+			elements[0].value(),
+			Some(WhatwgDateTime::Date(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+			))
+		);
+	}
+
+	#[test]
+	fn test_extract_skips_raw_text() {
+		let html =
+			r#"<script>let s = "<time>2011-11-18</time>";</script><time>2011</time>"#;
+		let elements = extract_datetime_values(html);
+
+		assert_eq!(elements.len(), 1);
+		assert_eq!(elements[0].value(), Some(WhatwgDateTime::Year(2011)));
+	}
+
+	#[test]
+	fn test_extract_unclosed_time() {
+		let elements = extract_datetime_values("<time>2011-11-18");
+		assert_eq!(elements.len(), 1);
+		assert_eq!(elements[0].raw(), "2011-11-18");
+
+		let elements = extract_datetime_values("<div><time>2011-11-18<br>  </div>");
+		assert_eq!(elements[0].raw(), "2011-11-18  ");
+		assert_eq!(
+			elements[0].value(),
+			Some(WhatwgDateTime::Date(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+			))
+		);
+	}
+
+	#[test]
+	fn test_extract_time_closed_by_implied_end_tags() {
+		let elements = extract_datetime_values("<p><time>2011-11-18</p><p>foo</p>");
+
+		assert_eq!(elements.len(), 1);
+		assert_eq!(elements[0].raw(), "2011-11-18");
+		assert_eq!(
+			elements[0].value(),
+			Some(WhatwgDateTime::Date(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+			))
+		);
+
+		let elements = extract_datetime_values("<ul><li><time>14:54<li>foo</ul>");
+		assert_eq!(elements[0].raw(), "14:54");
+	}
+
+	#[test]
+	fn test_extract_locations() {
+		let html = "<p title=\"a > b\">x < y\r\n<time\ndatetime='2011'>&amp;<time>2011</time></time>";
+		let elements = extract_datetime_values(html);
+
+		assert_eq!(elements.len(), 2);
+		assert_eq!(&html[elements[0].location()], "<time\ndatetime='2011'>");
+		assert_eq!(&html[elements[1].location()], "<time>");
+
+		let html = "<table><td><ins datetime=2011-11-18>x</ins></table><del datetime=\"<2011>\">";
+		let elements = extract_datetime_values(html);
+
+		assert_eq!(&html[elements[0].location()], "<ins datetime=2011-11-18>");
+		assert_eq!(&html[elements[1].location()], "<del datetime=\"<2011>\">");
+	}
+}
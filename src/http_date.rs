@@ -0,0 +1,309 @@
+//! Parsing and serializing [HTTP-dates][rfc9110-http-date], as used by the
+//! `Date`, `Last-Modified` and `Expires` header fields.
+//!
+//! HTTP-dates are always in UTC. They are sent as IMF-fixdate, but recipients
+//! must also accept the obsolete RFC 850 and asctime formats:
+//!
+//! - IMF-fixdate: `Sun, 06 Nov 1994 08:49:37 GMT`
+//! - RFC 850: `Sunday, 06-Nov-94 08:49:37 GMT`
+//! - asctime: `Sun Nov  6 08:49:37 1994`
+//!
+//! [rfc9110-http-date]: https://www.rfc-editor.org/rfc/rfc9110#section-5.6.7
+
+use crate::tokens::{TOKEN_COLON, TOKEN_HYPHEN, TOKEN_SPACE};
+use crate::utils::collect_ascii_digits;
+use crate::{parse_format, parse_time_component};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Timelike, Utc};
+
+const DAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const LONG_DAY_NAMES: [&str; 7] = [
+	"Monday",
+	"Tuesday",
+	"Wednesday",
+	"Thursday",
+	"Friday",
+	"Saturday",
+	"Sunday",
+];
+const MONTH_NAMES: [&str; 12] = [
+	"Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Parse an HTTP-date in any of the IMF-fixdate, RFC 850 and asctime formats
+///
+/// The two-digit years of RFC 850 dates are resolved against the current time.
+/// See [`parse_at`] for details.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{http_date, parse_global_datetime};
+///
+/// assert_eq!(
+///     http_date::parse("Sun, 06 Nov 1994 08:49:37 GMT"),
+///     parse_global_datetime("1994-11-06T08:49:37Z")
+/// );
+/// assert_eq!(
+///     http_date::parse("Sun Nov  6 08:49:37 1994"),
+///     parse_global_datetime("1994-11-06T08:49:37Z")
+/// );
+/// ```
+#[inline]
+pub fn parse(s: &str) -> Option<DateTime<Utc>> {
+	parse_at(s, Utc::now())
+}
+
+/// Parse an HTTP-date in any of the IMF-fixdate, RFC 850 and asctime formats,
+/// resolving the two-digit years of RFC 850 dates against `now`
+///
+/// A two-digit year is resolved to the year with the same last two digits that
+/// is closest to the year of `now`, so that a date never appears to be more
+/// than 50 years in the future.
+///
+/// The names of days and months are case-sensitive. The name of the day
+/// is not checked against the date. Leap seconds are not supported.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{http_date, parse_global_datetime};
+///
+/// let now = parse_global_datetime("2011-11-18T14:54Z").unwrap();
+/// assert_eq!(
+///     http_date::parse_at("Sunday, 06-Nov-94 08:49:37 GMT", now),
+///     parse_global_datetime("1994-11-06T08:49:37Z")
+/// );
+/// assert_eq!(
+///     http_date::parse_at("Tuesday, 06-Nov-40 08:49:37 GMT", now),
+///     parse_global_datetime("2040-11-06T08:49:37Z")
+/// );
+///
+/// assert_eq!(http_date::parse_at("sun, 06 nov 1994 08:49:37 gmt", now), None);
+/// ```
+pub fn parse_at(s: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+	if !s.is_ascii() {
+		return None;
+	}
+
+	let (date, time) = match s.as_bytes().get(3)? {
+		b',' => parse_imf_fixdate(s)?,
+		b' ' => parse_asctime(s)?,
+		_ => parse_rfc850(s, now.year())?,
+	};
+
+	Some(DateTime::<Utc>::from_naive_utc_and_offset(
+		date.and_time(time),
+		Utc,
+	))
+}
+
+/// Serialize a [`DateTime<Utc>`] as an IMF-fixdate
+///
+/// Fractions of a second are truncated. This returns `None` for years
+/// outside of 0000 to 9999, which can not be written as an IMF-fixdate.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{http_date, parse_global_datetime};
+///
+/// let modified = parse_global_datetime("2011-11-18T14:54:39.929-08:00").unwrap();
+/// assert_eq!(
+///     http_date::serialize(modified),
+///     Some(String::from("Fri, 18 Nov 2011 22:54:39 GMT"))
+/// );
+/// ```
+pub fn serialize(datetime: DateTime<Utc>) -> Option<String> {
+	if !(0..=9999).contains(&datetime.year()) {
+		return None;
+	}
+
+	Some(format!(
+		"{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+		DAY_NAMES[datetime.weekday().num_days_from_monday() as usize],
+		datetime.day(),
+		MONTH_NAMES[datetime.month0() as usize],
+		datetime.year(),
+		datetime.hour(),
+		datetime.minute(),
+		datetime.second(),
+	))
+}
+
+/// Parses `day-name "," SP day SP month SP year SP time-of-day SP "GMT"`.
+fn parse_imf_fixdate(s: &str) -> Option<(NaiveDate, NaiveTime)> {
+	if s.len() != 29
+		|| !DAY_NAMES.contains(&&s[..3])
+		|| &s[3..5] != ", "
+		|| s.as_bytes()[16] != TOKEN_SPACE as u8
+		|| &s[25..] != " GMT"
+	{
+		return None;
+	}
+
+	let mut fields = s[5..16].split(TOKEN_SPACE);
+	let day = parse_digits(fields.next()?, 2)?;
+	let month = parse_month(fields.next()?)?;
+	let year = parse_digits(fields.next()?, 4)?;
+
+	Some((
+		NaiveDate::from_ymd_opt(year as i32, month, day)?,
+		parse_time_of_day(&s[17..25])?,
+	))
+}
+
+/// Parses `day-name-l "," SP day "-" month "-" 2DIGIT SP time-of-day SP "GMT"`.
+fn parse_rfc850(s: &str, current_year: i32) -> Option<(NaiveDate, NaiveTime)> {
+	let (day_name, rest) = s.split_once(", ")?;
+	if !LONG_DAY_NAMES.contains(&day_name) || rest.len() != 22 || !rest.ends_with(" GMT") {
+		return None;
+	}
+
+	let mut fields = rest[..9].split(TOKEN_HYPHEN);
+	let day = parse_digits(fields.next()?, 2)?;
+	let month = parse_month(fields.next()?)?;
+	let year = resolve_two_digit_year(parse_digits(fields.next()?, 2)? as i32, current_year);
+	if rest.as_bytes()[9] != TOKEN_SPACE as u8 {
+		return None;
+	}
+
+	Some((
+		NaiveDate::from_ymd_opt(year, month, day)?,
+		parse_time_of_day(&rest[10..18])?,
+	))
+}
+
+/// Parses `day-name SP month SP ( 2DIGIT / ( SP DIGIT ) ) SP time-of-day SP year`.
+fn parse_asctime(s: &str) -> Option<(NaiveDate, NaiveTime)> {
+	let bytes = s.as_bytes();
+	if s.len() != 24 || !DAY_NAMES.contains(&&s[..3]) {
+		return None;
+	}
+	if [3, 7, 10, 19]
+		.iter()
+		.any(|&index| bytes[index] != TOKEN_SPACE as u8)
+	{
+		return None;
+	}
+
+	let month = parse_month(&s[4..7])?;
+	let day = match s[8..10].strip_prefix(TOKEN_SPACE) {
+		Some(day) => parse_digits(day, 1)?,
+		None => parse_digits(&s[8..10], 2)?,
+	};
+	let year = parse_digits(&s[20..], 4)?;
+
+	Some((
+		NaiveDate::from_ymd_opt(year as i32, month, day)?,
+		parse_time_of_day(&s[11..19])?,
+	))
+}
+
+/// Parses `hour ":" minute ":" second`, each of which are two digits.
+fn parse_time_of_day(s: &str) -> Option<NaiveTime> {
+	if s.len() != 8 || s.as_bytes()[5] != TOKEN_COLON as u8 {
+		return None;
+	}
+
+	parse_format(s, parse_time_component)
+}
+
+/// Parses exactly `len` ASCII digits.
+fn parse_digits(s: &str, len: usize) -> Option<u32> {
+	let mut position = 0usize;
+	let parsed_digits = collect_ascii_digits(s, &mut position);
+	if parsed_digits.len() != len || position < s.len() {
+		return None;
+	}
+
+	parsed_digits.parse().ok()
+}
+
+fn parse_month(s: &str) -> Option<u32> {
+	MONTH_NAMES
+		.iter()
+		.position(|&month| month == s)
+		.map(|index| index as u32 + 1)
+}
+
+/// Resolves a two-digit year to the closest year with the same last
+/// two digits, which is never more than 50 years in the future.
+fn resolve_two_digit_year(year: i32, current_year: i32) -> i32 {
+	let candidate = current_year - current_year.rem_euclid(100) + year;
+	if candidate > current_year + 50 {
+		candidate - 100
+	} else if candidate <= current_year - 50 {
+		candidate + 100
+	} else {
+		candidate
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_at, resolve_two_digit_year, serialize};
+	use crate::parse_global_datetime;
+	use chrono::{DateTime, Utc};
+
+	fn now() -> DateTime<Utc> {
+		parse_global_datetime("2011-11-18T14:54Z").unwrap()
+	}
+
+	#[test]
+	fn test_parse_each_format() {
+		let expected = parse_global_datetime("1994-11-06T08:49:37Z");
+		assert_eq!(parse_at("Sun, 06 Nov 1994 08:49:37 GMT", now()), expected);
+		assert_eq!(parse_at("Sunday, 06-Nov-94 08:49:37 GMT", now()), expected);
+		assert_eq!(parse_at("Sun Nov  6 08:49:37 1994", now()), expected);
+		assert_eq!(
+			parse_at("Fri Nov 18 14:54:39 2011", now()),
+			parse_global_datetime("2011-11-18T14:54:39Z")
+		);
+	}
+
+	#[test]
+	fn test_parse_fails() {
+		for s in [
+			"",
+			"Sun, 06 Nov 1994 08:49:37 UTC",
+			"Sun, 6 Nov 1994 08:49:37 GMT",
+			"Sun, 06 Nov 94 08:49:37 GMT",
+			"Sun, 06 Nov 1994 08:49 GMT",
+			"Sun, 06 Nov 1994 24:49:37 GMT",
+			"Sun, 31 Feb 1994 08:49:37 GMT",
+			"Sun,  06 Nov 1994 08:49:37 GMT",
+			"Sunday, 06 Nov 1994 08:49:37 GMT",
+			"Sun, 06-Nov-94 08:49:37 GMT",
+			"Sunday, 06-Nov-1994 08:49:37 GMT",
+			"Sun Nov 6 08:49:37 1994",
+			"Sun Nov  6 08:49:37 94",
+			"Sun, 06 Nov 1994 08:49:37 GMT ",
+			"Sun, 06 Nov 1994T08:49:37 GMT",
+			"1994-11-06T08:49:37Z",
+		] {
+			assert_eq!(parse_at(s, now()), None, "{}", s);
+		}
+	}
+
+	#[test]
+	fn test_resolve_two_digit_year() {
+		assert_eq!(resolve_two_digit_year(61, 2011), 2061);
+		assert_eq!(resolve_two_digit_year(62, 2011), 1962);
+		assert_eq!(resolve_two_digit_year(0, 2099), 2100);
+		assert_eq!(resolve_two_digit_year(49, 2099), 2149);
+		assert_eq!(resolve_two_digit_year(50, 2099), 2050);
+	}
+
+	#[test]
+	fn test_serialize_round_trips() {
+		for s in [
+			"Sun, 06 Nov 1994 08:49:37 GMT",
+			"Sat, 01 Jan 0000 00:00:00 GMT",
+			"Fri, 31 Dec 9999 23:59:59 GMT",
+		] {
+			let parsed = parse_at(s, now()).unwrap();
+			assert_eq!(serialize(parsed).unwrap(), s);
+		}
+		assert_eq!(
+			serialize(parse_global_datetime("12011-11-18T14:54Z").unwrap()),
+			None
+		);
+	}
+}
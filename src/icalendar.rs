@@ -0,0 +1,578 @@
+//! Converting to and from the [value data types][rfc5545-value-types] of
+//! iCalendar (RFC 5545), for exporting dates and times to `.ics` files.
+//!
+//! | iCalendar                  | Example            | Type               |
+//! |----------------------------|--------------------|--------------------|
+//! | `DATE`                     | `20111118`         | [`NaiveDate`]      |
+//! | `DATE-TIME`, floating      | `20111118T145439`  | [`NaiveDateTime`]  |
+//! | `DATE-TIME`, UTC           | `20111118T145439Z` | [`DateTime<Utc>`]  |
+//! | `DURATION`                 | `P1DT4H18M`        | [`Duration`]       |
+//! | `UTC-OFFSET`               | `+0530`            | [`TimeZoneOffset`] |
+//!
+//! iCalendar values are written in the ISO 8601 basic format, so they have
+//! no separators between the components of a date or a time. Years have
+//! exactly four digits, and fractions of a second are not supported.
+//!
+//! [rfc5545-value-types]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3
+
+use crate::tokens::{
+	TOKEN_ABBR_DAY, TOKEN_ABBR_HOUR, TOKEN_ABBR_MIN, TOKEN_ABBR_SEC, TOKEN_ABBR_WEEK,
+	TOKEN_MINUS, TOKEN_P, TOKEN_PLUS, TOKEN_T, TOKEN_Z,
+};
+use crate::utils::collect_ascii_digits;
+use crate::{TimeZoneDesignator, TimeZoneOffset};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+
+/// The designators of the `dur-time` components, in the order they are written.
+const TIME_DESIGNATORS: [char; 3] = [TOKEN_ABBR_HOUR, TOKEN_ABBR_MIN, TOKEN_ABBR_SEC];
+
+/// Parse an iCalendar [`DATE`][rfc5545-date] value, in the format of `YYYYMMDD`
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::icalendar;
+///
+/// assert_eq!(icalendar::parse_date("20111118"), NaiveDate::from_ymd_opt(2011, 11, 18));
+/// assert_eq!(icalendar::parse_date("20110229"), None); // 2011 is not a leap year
+/// assert_eq!(icalendar::parse_date("2011-11-18"), None);
+/// ```
+///
+/// [rfc5545-date]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.4
+pub fn parse_date(s: &str) -> Option<NaiveDate> {
+	if s.len() != 8 {
+		return None;
+	}
+
+	NaiveDate::from_ymd_opt(
+		parse_digits(s.get(..4)?, 4)? as i32,
+		parse_digits(s.get(4..6)?, 2)?,
+		parse_digits(s.get(6..)?, 2)?,
+	)
+}
+
+/// Serialize a [`NaiveDate`] as an iCalendar [`DATE`][rfc5545-date] value,
+/// in the format of `YYYYMMDD`
+///
+/// This returns `None` for years outside of 0000 to 9999, which can not be written
+/// as an iCalendar date.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::icalendar;
+///
+/// let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+/// assert_eq!(icalendar::serialize_date(date), Some(String::from("20111118")));
+/// ```
+///
+/// [rfc5545-date]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.4
+pub fn serialize_date(date: NaiveDate) -> Option<String> {
+	if !(0..=9999).contains(&date.year()) {
+		return None;
+	}
+
+	Some(format!(
+		"{:04}{:02}{:02}",
+		date.year(),
+		date.month(),
+		date.day()
+	))
+}
+
+/// Parse an iCalendar [`DATE-TIME`][rfc5545-date-time] value in floating time,
+/// in the format of `YYYYMMDDThhmmss`
+///
+/// A floating time is the same wall-clock time in every time zone, and is
+/// represented by a [`NaiveDateTime`]. Leap seconds are not supported.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::icalendar;
+///
+/// assert_eq!(
+///     icalendar::parse_local_datetime("20111118T145439"),
+///     NaiveDate::from_ymd_opt(2011, 11, 18).and_then(|date| date.and_hms_opt(14, 54, 39))
+/// );
+/// assert_eq!(icalendar::parse_local_datetime("20111118T145439Z"), None); // not floating
+/// assert_eq!(icalendar::parse_local_datetime("20111118T1454"), None);    // seconds are required
+/// ```
+///
+/// [rfc5545-date-time]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.5
+pub fn parse_local_datetime(s: &str) -> Option<NaiveDateTime> {
+	if s.len() != 15 || s.as_bytes()[8] != TOKEN_T as u8 {
+		return None;
+	}
+
+	Some(parse_date(&s[..8])?.and_time(parse_time(s.get(9..)?)?))
+}
+
+/// Serialize a [`NaiveDateTime`] as an iCalendar [`DATE-TIME`][rfc5545-date-time]
+/// value in floating time, in the format of `YYYYMMDDThhmmss`
+///
+/// Fractions of a second are truncated. This returns `None` for years outside
+/// of 0000 to 9999, which can not be written as an iCalendar date-time.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::icalendar;
+///
+/// let datetime = NaiveDate::from_ymd_opt(2011, 11, 18)
+///     .unwrap()
+///     .and_hms_milli_opt(14, 54, 39, 929)
+///     .unwrap();
+/// assert_eq!(
+///     icalendar::serialize_local_datetime(datetime),
+///     Some(String::from("20111118T145439"))
+/// );
+/// ```
+///
+/// [rfc5545-date-time]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.5
+pub fn serialize_local_datetime(datetime: NaiveDateTime) -> Option<String> {
+	Some(format!(
+		"{}{}{:02}{:02}{:02}",
+		serialize_date(datetime.date())?,
+		TOKEN_T,
+		datetime.hour(),
+		datetime.minute(),
+		datetime.second()
+	))
+}
+
+/// Parse an iCalendar [`DATE-TIME`][rfc5545-date-time] value in UTC,
+/// in the format of `YYYYMMDDThhmmssZ`
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{icalendar, parse_global_datetime};
+///
+/// assert_eq!(
+///     icalendar::parse_global_datetime("20111118T145439Z"),
+///     parse_global_datetime("2011-11-18T14:54:39Z")
+/// );
+/// assert_eq!(icalendar::parse_global_datetime("20111118T145439"), None); // floating
+/// ```
+///
+/// [rfc5545-date-time]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.5
+pub fn parse_global_datetime(s: &str) -> Option<DateTime<Utc>> {
+	let naive_datetime = parse_local_datetime(s.strip_suffix(TOKEN_Z)?)?;
+	Some(DateTime::<Utc>::from_naive_utc_and_offset(
+		naive_datetime,
+		Utc,
+	))
+}
+
+/// Serialize a [`DateTime<Utc>`] as an iCalendar [`DATE-TIME`][rfc5545-date-time]
+/// value in UTC, in the format of `YYYYMMDDThhmmssZ`
+///
+/// Fractions of a second are truncated. This returns `None` for years outside
+/// of 0000 to 9999, which can not be written as an iCalendar date-time.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{icalendar, parse_global_datetime};
+///
+/// let datetime = parse_global_datetime("2011-11-18T14:54:39-08:00").unwrap();
+/// assert_eq!(
+///     icalendar::serialize_global_datetime(datetime),
+///     Some(String::from("20111118T225439Z"))
+/// );
+/// ```
+///
+/// [rfc5545-date-time]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.5
+pub fn serialize_global_datetime(datetime: DateTime<Utc>) -> Option<String> {
+	let mut serialized = serialize_local_datetime(datetime.naive_utc())?;
+	serialized.push(TOKEN_Z);
+	Some(serialized)
+}
+
+/// Parse an iCalendar [`DURATION`][rfc5545-duration] value
+///
+/// Unlike a WHATWG duration string, an iCalendar duration may be negative, may be
+/// written in weeks, such as `P2W`, and may not have a fraction of a second.
+/// The hours, minutes and seconds that are given must be consecutive, so
+/// `PT1H0M1S` is valid, but `PT1H1S` is not.
+///
+/// # Examples
+/// ```
+/// use chrono::Duration;
+/// use whatwg_datetime::icalendar;
+///
+/// assert_eq!(icalendar::parse_duration("P15DT5H0M20S"), Some(Duration::seconds(1_314_020)));
+/// assert_eq!(icalendar::parse_duration("P7W"), Some(Duration::weeks(7)));
+/// assert_eq!(icalendar::parse_duration("-PT15M"), Some(Duration::minutes(-15)));
+///
+/// assert_eq!(icalendar::parse_duration("PT1H1S"), None);
+/// assert_eq!(icalendar::parse_duration("PT1.5S"), None);
+/// ```
+///
+/// [rfc5545-duration]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.6
+pub fn parse_duration(s: &str) -> Option<Duration> {
+	let (is_negative, unsigned) = match s.strip_prefix(TOKEN_MINUS) {
+		Some(unsigned) => (true, unsigned),
+		None => (false, s.strip_prefix(TOKEN_PLUS).unwrap_or(s)),
+	};
+	let designators = unsigned.strip_prefix(TOKEN_P)?;
+
+	let seconds = match designators.strip_suffix(TOKEN_ABBR_WEEK) {
+		Some(weeks) => parse_number(weeks)?.checked_mul(7 * 86_400)?,
+		None => parse_day_and_time(designators)?,
+	};
+
+	let milliseconds = seconds.checked_mul(1000)?;
+	let duration = Duration::milliseconds(milliseconds);
+	Some(if is_negative { -duration } else { duration })
+}
+
+/// Serialize a [`Duration`] as an iCalendar [`DURATION`][rfc5545-duration] value
+///
+/// Durations are written in days, hours, minutes and seconds, leaving out the
+/// zero components at either end of the time. Fractions of a second are truncated.
+///
+/// # Examples
+/// ```
+/// use chrono::Duration;
+/// use whatwg_datetime::icalendar;
+///
+/// assert_eq!(icalendar::serialize_duration(Duration::seconds(1_314_020)), "P15DT5H0M20S");
+/// assert_eq!(icalendar::serialize_duration(Duration::weeks(-1)), "-P7D");
+/// assert_eq!(icalendar::serialize_duration(Duration::zero()), "PT0S");
+/// ```
+///
+/// [rfc5545-duration]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.6
+pub fn serialize_duration(duration: Duration) -> String {
+	let mut serialized = String::new();
+	if duration.num_seconds() < 0 {
+		serialized.push(TOKEN_MINUS);
+	}
+	serialized.push(TOKEN_P);
+
+	let seconds = duration.num_seconds().unsigned_abs();
+	let days = seconds / 86_400;
+	let time = [seconds % 86_400 / 3600, seconds % 3600 / 60, seconds % 60];
+	if days != 0 {
+		serialized.push_str(&format!("{}{}", days, TOKEN_ABBR_DAY));
+	}
+
+	let first = time.iter().position(|&value| value != 0);
+	let last = time.iter().rposition(|&value| value != 0);
+	let range = match (first, last) {
+		(Some(first), Some(last)) => first..=last,
+		_ if days == 0 => 2..=2,
+		_ => return serialized,
+	};
+
+	serialized.push(TOKEN_T);
+	for index in range {
+		serialized.push_str(&format!("{}{}", time[index], TIME_DESIGNATORS[index]));
+	}
+
+	serialized
+}
+
+/// Parse an iCalendar [`UTC-OFFSET`][rfc5545-utc-offset] value,
+/// in the format of `±hhmm` or `±hhmmss`
+///
+/// As a [`TimeZoneOffset`] only has minutes, the seconds must be `00`.
+/// An offset of `-0000` is not allowed.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{icalendar, TimeZoneOffset};
+///
+/// assert_eq!(icalendar::parse_utc_offset("+0530"), TimeZoneOffset::new_opt(5, 30));
+/// assert_eq!(icalendar::parse_utc_offset("-080000"), TimeZoneOffset::new_opt(-8, 0));
+///
+/// assert_eq!(icalendar::parse_utc_offset("-0000"), None);
+/// assert_eq!(icalendar::parse_utc_offset("+05:30"), None);
+/// ```
+///
+/// [rfc5545-utc-offset]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.14
+pub fn parse_utc_offset(s: &str) -> Option<TimeZoneOffset> {
+	if !s.is_ascii() || !(s.len() == 5 || s.len() == 7) {
+		return None;
+	}
+
+	let designator = match s.as_bytes()[0] as char {
+		TOKEN_PLUS => TimeZoneDesignator::Plus,
+		TOKEN_MINUS => TimeZoneDesignator::Minus,
+		_ => return None,
+	};
+	let hours = parse_digits(&s[1..3], 2)?;
+	let minutes = parse_digits(&s[3..5], 2)?;
+	if hours > 23 || minutes > 59 || (s.len() == 7 && parse_digits(&s[5..], 2)? != 0) {
+		return None;
+	}
+
+	let total_minutes = (hours * 60 + minutes) as i32;
+	match designator {
+		TimeZoneDesignator::Minus if total_minutes == 0 => None,
+		TimeZoneDesignator::Minus => {
+			Some(TimeZoneOffset::with_designator(-total_minutes, designator))
+		}
+		_ => Some(TimeZoneOffset::with_designator(total_minutes, designator)),
+	}
+}
+
+/// Serialize a [`TimeZoneOffset`] as an iCalendar [`UTC-OFFSET`][rfc5545-utc-offset]
+/// value, in the format of `±hhmm`
+///
+/// A zero offset is always written as `+0000`, as `-0000` is not allowed.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{icalendar, parse_timezone_offset, TimeZoneOffset};
+///
+/// let tz_offset = TimeZoneOffset::new_opt(5, 30).unwrap();
+/// assert_eq!(icalendar::serialize_utc_offset(tz_offset), "+0530");
+///
+/// let tz_offset = parse_timezone_offset("Z").unwrap();
+/// assert_eq!(icalendar::serialize_utc_offset(tz_offset), "+0000");
+/// ```
+///
+/// [rfc5545-utc-offset]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.14
+pub fn serialize_utc_offset(tz_offset: TimeZoneOffset) -> String {
+	let sign = if tz_offset.total_minutes() < 0 {
+		TOKEN_MINUS
+	} else {
+		TOKEN_PLUS
+	};
+	format!(
+		"{}{:02}{:02}",
+		sign,
+		tz_offset.hour().abs(),
+		tz_offset.minute()
+	)
+}
+
+/// Parses `hhmmss`, without leap seconds.
+fn parse_time(s: &str) -> Option<NaiveTime> {
+	if s.len() != 6 {
+		return None;
+	}
+
+	NaiveTime::from_hms_opt(
+		parse_digits(s.get(..2)?, 2)?,
+		parse_digits(s.get(2..4)?, 2)?,
+		parse_digits(s.get(4..)?, 2)?,
+	)
+}
+
+/// Parses `dur-date` or `dur-time`, returning the number of seconds.
+fn parse_day_and_time(s: &str) -> Option<i64> {
+	let (day, time) = match s.split_once(TOKEN_T) {
+		Some((day, time)) => (day, Some(time)),
+		None => (s, None),
+	};
+
+	let mut seconds = match day.strip_suffix(TOKEN_ABBR_DAY) {
+		Some(days) => parse_number(days)?.checked_mul(86_400)?,
+		None if day.is_empty() && time.is_some() => 0,
+		None => return None,
+	};
+
+	if let Some(time) = time {
+		seconds = seconds.checked_add(parse_time_designators(time)?)?;
+	}
+
+	Some(seconds)
+}
+
+/// Parses the hours, minutes and seconds of a `dur-time`, which must be
+/// consecutive, returning the number of seconds.
+fn parse_time_designators(s: &str) -> Option<i64> {
+	let mut seconds = 0i64;
+	let mut previous = None;
+	let mut rest = s;
+	while !rest.is_empty() {
+		let end = rest.find(|c: char| !c.is_ascii_digit())?;
+		let designator = rest[end..].chars().next()?;
+		let index = TIME_DESIGNATORS.iter().position(|&d| d == designator)?;
+		if previous.map_or(false, |previous| index != previous + 1) {
+			return None;
+		}
+
+		let scale = [3600, 60, 1][index];
+		seconds = seconds.checked_add(parse_number(&rest[..end])?.checked_mul(scale)?)?;
+		previous = Some(index);
+		rest = &rest[end + 1..];
+	}
+
+	previous.map(|_| seconds)
+}
+
+/// Parses one or more ASCII digits.
+fn parse_number(s: &str) -> Option<i64> {
+	if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+		return None;
+	}
+
+	s.parse().ok()
+}
+
+/// Parses exactly `len` ASCII digits.
+fn parse_digits(s: &str, len: usize) -> Option<u32> {
+	let mut position = 0usize;
+	let parsed_digits = collect_ascii_digits(s, &mut position);
+	if parsed_digits.len() != len || position < s.len() {
+		return None;
+	}
+
+	parsed_digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		parse_date, parse_duration, parse_global_datetime, parse_local_datetime,
+		parse_utc_offset, serialize_date, serialize_duration, serialize_global_datetime,
+		serialize_local_datetime, serialize_utc_offset,
+	};
+	use crate::{TimeZoneDesignator, TimeZoneOffset};
+	use chrono::{Duration, NaiveDate};
+
+	#[test]
+	fn test_date() {
+		let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+		assert_eq!(parse_date("20111118"), Some(date));
+		assert_eq!(serialize_date(date).unwrap(), "20111118");
+		assert_eq!(
+			serialize_date(NaiveDate::from_ymd_opt(12, 1, 2).unwrap()).unwrap(),
+			"00120102"
+		);
+		assert_eq!(
+			serialize_date(NaiveDate::from_ymd_opt(12011, 1, 2).unwrap()),
+			None
+		);
+
+		for s in [
+			"",
+			"2011111",
+			"201111180",
+			"20111318",
+			"20111131",
+			"2011-11-18",
+			"+2011111",
+		] {
+			assert_eq!(parse_date(s), None, "{}", s);
+		}
+	}
+
+	#[test]
+	fn test_local_datetime() {
+		let datetime = NaiveDate::from_ymd_opt(2011, 11, 18)
+			.unwrap()
+			.and_hms_opt(14, 54, 39)
+			.unwrap();
+		assert_eq!(parse_local_datetime("20111118T145439"), Some(datetime));
+		assert_eq!(
+			serialize_local_datetime(datetime).unwrap(),
+			"20111118T145439"
+		);
+
+		for s in [
+			"20111118T1454",
+			"20111118t145439",
+			"20111118 145439",
+			"20111118T240000",
+			"20111118T145460",
+			"20111118T145439Z",
+			"2011-11-18T14:54:39",
+		] {
+			assert_eq!(parse_local_datetime(s), None, "{}", s);
+		}
+	}
+
+	#[test]
+	fn test_global_datetime() {
+		let datetime = crate::parse_global_datetime("2011-11-18T14:54:39Z").unwrap();
+		assert_eq!(parse_global_datetime("20111118T145439Z"), Some(datetime));
+		assert_eq!(
+			serialize_global_datetime(datetime).unwrap(),
+			"20111118T145439Z"
+		);
+
+		for s in [
+			"20111118T145439",
+			"20111118T145439z",
+			"20111118Z",
+			"20111118T145439+0000",
+		] {
+			assert_eq!(parse_global_datetime(s), None, "{}", s);
+		}
+	}
+
+	#[test]
+	fn test_parse_duration() {
+		assert_eq!(parse_duration("P1D"), Some(Duration::days(1)));
+		assert_eq!(parse_duration("+P2W"), Some(Duration::weeks(2)));
+		assert_eq!(
+			parse_duration("-P1DT2H"),
+			Some(-(Duration::days(1) + Duration::hours(2)))
+		);
+		assert_eq!(parse_duration("PT4H18M3S"), Some(Duration::seconds(15483)));
+		assert_eq!(parse_duration("PT18M3S"), Some(Duration::seconds(1083)));
+		assert_eq!(parse_duration("PT0S"), Some(Duration::zero()));
+
+		for s in [
+			"",
+			"P",
+			"PT",
+			"P1DT",
+			"1D",
+			"P1W2D",
+			"PW",
+			"P1D2H",
+			"PT3S4M",
+			"PT1H1S",
+			"PT1H1H",
+			"PT1.5S",
+			"pt1s",
+			"P-1D",
+			"--P1D",
+			"P1DT1H ",
+			"P99999999999999999999D",
+		] {
+			assert_eq!(parse_duration(s), None, "{}", s);
+		}
+	}
+
+	#[test]
+	fn test_serialize_duration() {
+		assert_eq!(serialize_duration(Duration::zero()), "PT0S");
+		assert_eq!(serialize_duration(Duration::days(2)), "P2D");
+		assert_eq!(serialize_duration(Duration::seconds(3601)), "PT1H0M1S");
+		assert_eq!(serialize_duration(Duration::seconds(-60)), "-PT1M");
+		assert_eq!(serialize_duration(Duration::milliseconds(1500)), "PT1S");
+
+		for s in ["P15DT5H0M20S", "-PT15M", "P1DT20S", "PT0S"] {
+			assert_eq!(serialize_duration(parse_duration(s).unwrap()), s);
+		}
+	}
+
+	#[test]
+	fn test_utc_offset() {
+		let tz_offset = TimeZoneOffset::new_opt(5, 30).unwrap();
+		assert_eq!(parse_utc_offset("+0530"), Some(tz_offset));
+		assert_eq!(parse_utc_offset("+053000"), Some(tz_offset));
+		assert_eq!(serialize_utc_offset(tz_offset), "+0530");
+
+		let tz_offset = TimeZoneOffset::new_opt(-8, 0).unwrap();
+		assert_eq!(parse_utc_offset("-0800"), Some(tz_offset));
+		assert_eq!(serialize_utc_offset(tz_offset), "-0800");
+
+		let tz_offset = parse_utc_offset("+0000").unwrap();
+		assert_eq!(tz_offset.designator(), TimeZoneDesignator::Plus);
+		assert_eq!(serialize_utc_offset(tz_offset), "+0000");
+		assert_eq!(serialize_utc_offset(TimeZoneOffset::utc()), "+0000");
+
+		for s in [
+			"", "0530", "+530", "+05:30", "+2400", "+0560", "+053001", "-0000",
+			"-000000",
+		] {
+			assert_eq!(parse_utc_offset(s), None, "{}", s);
+		}
+	}
+}
@@ -0,0 +1,366 @@
+use crate::{
+	parse_date, parse_local_datetime, parse_month, parse_time, parse_week, serialize_date,
+	serialize_local_datetime, serialize_month, serialize_time, serialize_week, ParseError,
+	YearMonth, YearWeek,
+};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::fmt;
+use std::str::FromStr;
+
+/// The date and time related states of the `type` attribute
+/// of an [HTML `<input>` element][whatwg-html-input].
+///
+/// Each variant knows how to parse and serialize its value, as well as
+/// the step-related metadata defined for its state.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_input_value, InputType, InputValue, YearWeek};
+///
+/// let parsed = parse_input_value(InputType::Week, "2011-W47");
+/// assert_eq!(parsed, Some(InputValue::Week(YearWeek::new_opt(2011, 47).unwrap())));
+/// assert_eq!(InputType::Week.serialize(&parsed.unwrap()), Some(String::from("2011-W47")));
+/// ```
+///
+/// [whatwg-html-input]: https://html.spec.whatwg.org/multipage/input.html#attr-input-type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputType {
+	/// The [`type=date`][whatwg-html-date] state
+	///
+	/// [whatwg-html-date]: https://html.spec.whatwg.org/multipage/input.html#date-state-(type=date)
+	Date,
+	/// The [`type=month`][whatwg-html-month] state
+	///
+	/// [whatwg-html-month]: https://html.spec.whatwg.org/multipage/input.html#month-state-(type=month)
+	Month,
+	/// The [`type=week`][whatwg-html-week] state
+	///
+	/// [whatwg-html-week]: https://html.spec.whatwg.org/multipage/input.html#week-state-(type=week)
+	Week,
+	/// The [`type=time`][whatwg-html-time] state
+	///
+	/// [whatwg-html-time]: https://html.spec.whatwg.org/multipage/input.html#time-state-(type=time)
+	Time,
+	/// The [`type=datetime-local`][whatwg-html-datetime-local] state
+	///
+	/// [whatwg-html-datetime-local]: https://html.spec.whatwg.org/multipage/input.html#local-date-and-time-state-(type=datetime-local)
+	DatetimeLocal,
+}
+
+impl InputType {
+	/// All of the date and time related input types.
+	pub const ALL: [InputType; 5] = [
+		InputType::Date,
+		InputType::Month,
+		InputType::Week,
+		InputType::Time,
+		InputType::DatetimeLocal,
+	];
+
+	/// The keyword used for this state in the `type` attribute.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::InputType;
+	///
+	/// assert_eq!(InputType::DatetimeLocal.as_str(), "datetime-local");
+	/// ```
+	pub const fn as_str(&self) -> &'static str {
+		match self {
+			InputType::Date => "date",
+			InputType::Month => "month",
+			InputType::Week => "week",
+			InputType::Time => "time",
+			InputType::DatetimeLocal => "datetime-local",
+		}
+	}
+
+	/// Parses a value string with the microsyntax of this input type,
+	/// returning `None` if it is not valid.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::{InputType, InputValue};
+	///
+	/// assert_eq!(
+	///     InputType::Date.parse("2011-11-18"),
+	///     Some(InputValue::Date(NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()))
+	/// );
+	/// assert_eq!(InputType::Date.parse("2011-11"), None);
+	/// ```
+	pub fn parse(&self, s: &str) -> Option<InputValue> {
+		match self {
+			InputType::Date => parse_date(s).map(InputValue::Date),
+			InputType::Month => parse_month(s).map(InputValue::Month),
+			InputType::Week => parse_week(s).map(InputValue::Week),
+			InputType::Time => parse_time(s).map(InputValue::Time),
+			InputType::DatetimeLocal => {
+				parse_local_datetime(s).map(InputValue::DatetimeLocal)
+			}
+		}
+	}
+
+	/// Serializes a typed value into a string, as it would be exposed
+	/// by the `value` IDL attribute.
+	///
+	/// This returns `None` if the value does not belong to this input type.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveTime;
+	/// use whatwg_datetime::{InputType, InputValue};
+	///
+	/// let value = InputValue::Time(NaiveTime::from_hms_opt(14, 54, 0).unwrap());
+	/// assert_eq!(InputType::Time.serialize(&value), Some(String::from("14:54")));
+	/// assert_eq!(InputType::Date.serialize(&value), None);
+	/// ```
+	pub fn serialize(&self, value: &InputValue) -> Option<String> {
+		if value.input_type() != *self {
+			return None;
+		}
+
+		Some(value.serialize())
+	}
+
+	/// The [default step][whatwg-html-default-step] of this input type,
+	/// in units of the input type (days, months, weeks, or seconds).
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::InputType;
+	///
+	/// assert_eq!(InputType::Date.default_step(), 1);
+	/// assert_eq!(InputType::Time.default_step(), 60);
+	/// ```
+	///
+	/// [whatwg-html-default-step]: https://html.spec.whatwg.org/multipage/input.html#concept-input-step-default
+	pub const fn default_step(&self) -> u32 {
+		match self {
+			InputType::Date | InputType::Month | InputType::Week => 1,
+			InputType::Time | InputType::DatetimeLocal => 60,
+		}
+	}
+
+	/// The [step scale factor][whatwg-html-step-scale] of this input type,
+	/// which converts a step into milliseconds (or months, for [`InputType::Month`]).
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::InputType;
+	///
+	/// assert_eq!(InputType::Date.step_scale_factor(), 86_400_000);
+	/// assert_eq!(InputType::Month.step_scale_factor(), 1);
+	/// ```
+	///
+	/// [whatwg-html-step-scale]: https://html.spec.whatwg.org/multipage/input.html#concept-input-step-scale
+	pub const fn step_scale_factor(&self) -> u64 {
+		match self {
+			InputType::Date => 86_400_000,
+			InputType::Month => 1,
+			InputType::Week => 604_800_000,
+			InputType::Time | InputType::DatetimeLocal => 1_000,
+		}
+	}
+
+	/// A human-readable placeholder describing the expected format of the value.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::InputType;
+	///
+	/// assert_eq!(InputType::Week.placeholder(), "yyyy-Www");
+	/// ```
+	pub const fn placeholder(&self) -> &'static str {
+		match self {
+			InputType::Date => "yyyy-mm-dd",
+			InputType::Month => "yyyy-mm",
+			InputType::Week => "yyyy-Www",
+			InputType::Time => "hh:mm",
+			InputType::DatetimeLocal => "yyyy-mm-ddThh:mm",
+		}
+	}
+
+	/// Whether the [`valueAsDate`][whatwg-html-value-as-date] IDL attribute
+	/// applies to this input type.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::InputType;
+	///
+	/// assert!(InputType::Date.value_as_date_applies());
+	/// assert!(!InputType::DatetimeLocal.value_as_date_applies());
+	/// ```
+	///
+	/// [whatwg-html-value-as-date]: https://html.spec.whatwg.org/multipage/input.html#dom-input-valueasdate
+	pub const fn value_as_date_applies(&self) -> bool {
+		!matches!(self, InputType::DatetimeLocal)
+	}
+}
+
+impl fmt::Display for InputType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl FromStr for InputType {
+	type Err = ParseError;
+
+	/// Parses the keyword of a `type` attribute, matched ASCII case-insensitively.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		InputType::ALL
+			.into_iter()
+			.find(|input_type| input_type.as_str().eq_ignore_ascii_case(s))
+			.ok_or(ParseError::Invalid)
+	}
+}
+
+/// A typed value of a date and time related `<input>` element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputValue {
+	Date(NaiveDate),
+	Month(YearMonth),
+	Week(YearWeek),
+	Time(NaiveTime),
+	DatetimeLocal(NaiveDateTime),
+}
+
+impl InputValue {
+	/// The input type this value belongs to.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveTime;
+	/// use whatwg_datetime::{InputType, InputValue};
+	///
+	/// let value = InputValue::Time(NaiveTime::from_hms_opt(14, 54, 0).unwrap());
+	/// assert_eq!(value.input_type(), InputType::Time);
+	/// ```
+	pub const fn input_type(&self) -> InputType {
+		match self {
+			InputValue::Date(_) => InputType::Date,
+			InputValue::Month(_) => InputType::Month,
+			InputValue::Week(_) => InputType::Week,
+			InputValue::Time(_) => InputType::Time,
+			InputValue::DatetimeLocal(_) => InputType::DatetimeLocal,
+		}
+	}
+
+	/// Serializes this value into a string, as it would be exposed
+	/// by the `value` IDL attribute.
+	pub fn serialize(&self) -> String {
+		match *self {
+			InputValue::Date(date) => serialize_date(date),
+			InputValue::Month(year_month) => serialize_month(year_month),
+			InputValue::Week(year_week) => serialize_week(year_week),
+			InputValue::Time(time) => serialize_time(time),
+			InputValue::DatetimeLocal(datetime) => serialize_local_datetime(datetime),
+		}
+	}
+}
+
+/// Parses the value of a date and time related `<input>` element
+/// for the given input type.
+///
+/// This is a shorthand for [`InputType::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_input_value, InputType, InputValue, YearMonth};
+///
+/// assert_eq!(
+///     parse_input_value(InputType::Month, "2011-11"),
+///     Some(InputValue::Month(YearMonth::new_opt(2011, 11).unwrap()))
+/// );
+/// assert_eq!(parse_input_value(InputType::Month, "2011-11-18"), None);
+/// ```
+#[inline]
+pub fn parse_input_value(input_type: InputType, s: &str) -> Option<InputValue> {
+	input_type.parse(s)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_input_value, InputType, InputValue};
+	use crate::{ParseError, YearMonth, YearWeek};
+	use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+	#[test]
+	fn test_input_type_from_str() {
+		assert_eq!("date".parse::<InputType>(), Ok(InputType::Date));
+		assert_eq!(
+			"DateTime-Local".parse::<InputType>(),
+			Ok(InputType::DatetimeLocal)
+		);
+		assert_eq!("datetime".parse::<InputType>(), Err(ParseError::Invalid));
+	}
+
+	#[test]
+	fn test_input_type_display_round_trips() {
+		for input_type in InputType::ALL {
+			assert_eq!(input_type.to_string().parse::<InputType>(), Ok(input_type));
+		}
+	}
+
+	#[test]
+	fn test_parse_input_value() {
+		assert_eq!(
+			parse_input_value(InputType::Date, "2011-11-18"),
+			Some(InputValue::Date(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+			))
+		);
+		assert_eq!(
+			parse_input_value(InputType::Month, "2011-11"),
+			Some(InputValue::Month(YearMonth::new(2011, 11)))
+		);
+		assert_eq!(
+			parse_input_value(InputType::Week, "2011-W47"),
+			Some(InputValue::Week(YearWeek::new(2011, 47)))
+		);
+		assert_eq!(
+			parse_input_value(InputType::Time, "14:54:39"),
+			Some(InputValue::Time(
+				NaiveTime::from_hms_opt(14, 54, 39).unwrap()
+			))
+		);
+		assert_eq!(
+			parse_input_value(InputType::DatetimeLocal, "2011-11-18 14:54"),
+			Some(InputValue::DatetimeLocal(NaiveDateTime::new(
+				NaiveDate::from_ymd_opt(2011, 11, 18).unwrap(),
+				NaiveTime::from_hms_opt(14, 54, 0).unwrap(),
+			)))
+		);
+	}
+
+	#[test]
+	fn test_parse_input_value_fails_wrong_type() {
+		assert_eq!(parse_input_value(InputType::Week, "2011-11-18"), None);
+		assert_eq!(parse_input_value(InputType::Time, "2011-11"), None);
+	}
+
+	#[test]
+	fn test_parse_input_value_fails_year_out_of_range() {
+		assert_eq!(parse_input_value(InputType::Week, "999999-W01"), None);
+		assert_eq!(parse_input_value(InputType::Month, "300000-01"), None);
+		assert_eq!(parse_input_value(InputType::Date, "300000-01-01"), None);
+	}
+
+	#[test]
+	fn test_input_value_serialize_normalizes() {
+		let parsed =
+			parse_input_value(InputType::DatetimeLocal, "2011-11-18 14:54:00").unwrap();
+		assert_eq!(parsed.serialize(), "2011-11-18T14:54");
+	}
+
+	#[test]
+	fn test_input_type_serialize_fails_mismatched_type() {
+		let value = InputValue::Month(YearMonth::new(2011, 11));
+		assert_eq!(InputType::Date.serialize(&value), None);
+		assert_eq!(
+			InputType::Month.serialize(&value),
+			Some(String::from("2011-11"))
+		);
+	}
+}
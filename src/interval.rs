@@ -0,0 +1,857 @@
+use crate::tokens::{TOKEN_P, TOKEN_SOLIDUS};
+use crate::{
+	parse_date, parse_duration, parse_global_datetime, serialize_date, serialize_duration,
+	serialize_global_datetime, DateOrTime, WhatwgDateTime, YearMonth, YearWeek,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+
+/// A half-open span of time, `[start, end)`.
+///
+/// An interval where `start == end` is empty, and represents a single instant,
+/// such as the one described by a local or global datetime.
+///
+/// # Examples
+/// ```
+/// use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+/// use whatwg_datetime::{parse_month, ToInterval};
+///
+/// let interval = parse_month("2011-11").unwrap().to_interval().unwrap();
+/// let midnight = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+///
+/// assert_eq!(
+///     interval.start(),
+///     NaiveDateTime::new(NaiveDate::from_ymd_opt(2011, 11, 1).unwrap(), midnight)
+/// );
+/// assert_eq!(
+///     interval.end(),
+///     NaiveDateTime::new(NaiveDate::from_ymd_opt(2011, 12, 1).unwrap(), midnight)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+	start: T,
+	end: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+	/// Creates a new interval from `start` (inclusive) to `end` (exclusive).
+	///
+	/// This returns `None` if `start` is after `end`.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::Interval;
+	///
+	/// assert!(Interval::new(1, 2).is_some());
+	/// assert!(Interval::new(1, 1).is_some());
+	/// assert!(Interval::new(2, 1).is_none());
+	/// ```
+	pub fn new(start: T, end: T) -> Option<Self> {
+		if start > end {
+			return None;
+		}
+
+		Some(Self { start, end })
+	}
+
+	/// Creates an empty interval describing a single instant.
+	#[inline]
+	pub fn instant(instant: T) -> Self {
+		Self {
+			start: instant,
+			end: instant,
+		}
+	}
+
+	/// The inclusive start of the interval.
+	#[inline]
+	pub fn start(&self) -> T {
+		self.start
+	}
+
+	/// The exclusive end of the interval.
+	#[inline]
+	pub fn end(&self) -> T {
+		self.end
+	}
+
+	/// Whether this interval describes a single instant.
+	#[inline]
+	pub fn is_instant(&self) -> bool {
+		self.start == self.end
+	}
+
+	/// Whether the given instant falls within this interval.
+	///
+	/// An instant interval only contains its own instant.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::Interval;
+	///
+	/// let interval = Interval::new(1, 3).unwrap();
+	/// assert!(interval.contains_instant(&1));
+	/// assert!(!interval.contains_instant(&3));
+	/// assert!(Interval::instant(3).contains_instant(&3));
+	/// ```
+	pub fn contains_instant(&self, instant: &T) -> bool {
+		if self.is_instant() {
+			return self.start == *instant;
+		}
+
+		self.start <= *instant && *instant < self.end
+	}
+}
+
+/// Converts a parsed value into the [`Interval`] of time that it covers.
+///
+/// A date covers a whole day, a [`YearWeek`] covers seven days starting on
+/// a Monday, and a [`YearMonth`] covers a whole month. Local and global
+/// datetimes cover a single instant.
+///
+/// Values without a time-zone offset are expressed in [`NaiveDateTime`],
+/// and global datetimes are expressed in [`DateTime<Utc>`]. Since a local
+/// value cannot be placed on the global timeline without knowing its
+/// offset, the two kinds can not be compared with each other.
+///
+/// Values that do not describe a fixed span of time, such as yearless dates,
+/// times, and time-zone offsets, do not implement this trait.
+///
+/// The values returned by [`parse_datetime_value`](crate::parse_datetime_value)
+/// and [`parse_date_or_time`](crate::parse_date_or_time), which may be either
+/// local or global, have a `to_interval` method returning a [`DateTimeInterval`].
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::{parse_date, parse_month, parse_week, ToInterval};
+///
+/// let month = parse_month("2011-11").unwrap();
+/// let week = parse_week("2011-W48").unwrap(); // 2011-11-28 through 2011-12-04
+///
+/// assert!(month.overlaps(&week));
+/// assert!(!month.contains(&week));
+/// assert!(month.contains(&parse_date("2011-11-18").unwrap()));
+/// ```
+pub trait ToInterval {
+	/// The type of instant the interval is expressed in.
+	type Instant: Ord + Copy;
+
+	/// The interval of time covered by this value.
+	///
+	/// This returns `None` if the end of the interval is out of range.
+	fn to_interval(&self) -> Option<Interval<Self::Instant>>;
+
+	/// Whether this value and another value share any span of time.
+	///
+	/// An instant overlaps an interval if the interval contains it.
+	fn overlaps<O>(&self, other: &O) -> bool
+	where
+		O: ToInterval<Instant = Self::Instant> + ?Sized,
+	{
+		let (lhs, rhs) = match (self.to_interval(), other.to_interval()) {
+			(Some(lhs), Some(rhs)) => (lhs, rhs),
+			_ => return false,
+		};
+
+		if lhs.is_instant() {
+			return rhs.contains_instant(&lhs.start);
+		}
+
+		if rhs.is_instant() {
+			return lhs.contains_instant(&rhs.start);
+		}
+
+		lhs.start < rhs.end && rhs.start < lhs.end
+	}
+
+	/// Whether the span of time of another value lies entirely within this value.
+	fn contains<O>(&self, other: &O) -> bool
+	where
+		O: ToInterval<Instant = Self::Instant> + ?Sized,
+	{
+		let (lhs, rhs) = match (self.to_interval(), other.to_interval()) {
+			(Some(lhs), Some(rhs)) => (lhs, rhs),
+			_ => return false,
+		};
+
+		if rhs.is_instant() {
+			return lhs.contains_instant(&rhs.start);
+		}
+
+		lhs.start <= rhs.start && rhs.end <= lhs.end
+	}
+}
+
+impl<T: Ord + Copy> ToInterval for Interval<T> {
+	type Instant = T;
+
+	#[inline]
+	fn to_interval(&self) -> Option<Interval<T>> {
+		Some(*self)
+	}
+}
+
+impl ToInterval for NaiveDate {
+	type Instant = NaiveDateTime;
+
+	fn to_interval(&self) -> Option<Interval<NaiveDateTime>> {
+		let end = self.succ_opt()?;
+		Interval::new(start_of_day(*self), start_of_day(end))
+	}
+}
+
+impl ToInterval for YearWeek {
+	type Instant = NaiveDateTime;
+
+	fn to_interval(&self) -> Option<Interval<NaiveDateTime>> {
+		let start = NaiveDate::from_isoywd_opt(self.year, self.week, Weekday::Mon)?;
+		let end = start.checked_add_signed(Duration::weeks(1))?;
+		Interval::new(start_of_day(start), start_of_day(end))
+	}
+}
+
+impl ToInterval for YearMonth {
+	type Instant = NaiveDateTime;
+
+	fn to_interval(&self) -> Option<Interval<NaiveDateTime>> {
+		let start = NaiveDate::from_ymd_opt(self.year, self.month, 1)?;
+		let last_day =
+			NaiveDate::from_ymd_opt(self.year, self.month, self.days_in_month())?;
+		Interval::new(start_of_day(start), start_of_day(last_day.succ_opt()?))
+	}
+}
+
+impl ToInterval for NaiveDateTime {
+	type Instant = NaiveDateTime;
+
+	#[inline]
+	fn to_interval(&self) -> Option<Interval<NaiveDateTime>> {
+		Some(Interval::instant(*self))
+	}
+}
+
+impl ToInterval for DateTime<Utc> {
+	type Instant = DateTime<Utc>;
+
+	#[inline]
+	fn to_interval(&self) -> Option<Interval<DateTime<Utc>>> {
+		Some(Interval::instant(*self))
+	}
+}
+
+/// The [`Interval`] of a value that may be either local or global, such as
+/// the [datetime value][whatwg-html-datetime-value] of a `<time>` element.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_datetime_value, parse_month, ParseMode, ToInterval};
+///
+/// let window = parse_month("2011-11").unwrap().to_interval().unwrap();
+/// let value = parse_datetime_value("2011-11-18 14:54", ParseMode::Attribute).unwrap();
+///
+/// assert!(value.to_interval().unwrap().overlaps(&window.into()));
+/// ```
+///
+/// [whatwg-html-datetime-value]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#concept-time-datetime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateTimeInterval {
+	/// The interval of a value without a time-zone offset.
+	Local(Interval<NaiveDateTime>),
+	/// The interval of a global datetime.
+	Global(Interval<DateTime<Utc>>),
+}
+
+impl DateTimeInterval {
+	/// Whether this interval and another interval share any span of time,
+	/// as in [`ToInterval::overlaps`].
+	///
+	/// A local interval never overlaps a global interval, since a local value
+	/// cannot be placed on the global timeline without knowing its offset.
+	pub fn overlaps(&self, other: &DateTimeInterval) -> bool {
+		match (self, other) {
+			(DateTimeInterval::Local(lhs), DateTimeInterval::Local(rhs)) => {
+				lhs.overlaps(rhs)
+			}
+			(DateTimeInterval::Global(lhs), DateTimeInterval::Global(rhs)) => {
+				lhs.overlaps(rhs)
+			}
+			_ => false,
+		}
+	}
+
+	/// Whether another interval lies entirely within this interval,
+	/// as in [`ToInterval::contains`].
+	///
+	/// A local interval never contains a global interval, and vice versa.
+	pub fn contains(&self, other: &DateTimeInterval) -> bool {
+		match (self, other) {
+			(DateTimeInterval::Local(lhs), DateTimeInterval::Local(rhs)) => {
+				lhs.contains(rhs)
+			}
+			(DateTimeInterval::Global(lhs), DateTimeInterval::Global(rhs)) => {
+				lhs.contains(rhs)
+			}
+			_ => false,
+		}
+	}
+}
+
+impl From<Interval<NaiveDateTime>> for DateTimeInterval {
+	#[inline]
+	fn from(interval: Interval<NaiveDateTime>) -> Self {
+		DateTimeInterval::Local(interval)
+	}
+}
+
+impl From<Interval<DateTime<Utc>>> for DateTimeInterval {
+	#[inline]
+	fn from(interval: Interval<DateTime<Utc>>) -> Self {
+		DateTimeInterval::Global(interval)
+	}
+}
+
+impl WhatwgDateTime {
+	/// The interval of time covered by this value, as described by [`ToInterval`].
+	///
+	/// A year covers a whole year. This returns `None` for values that do not
+	/// describe a fixed span of time, which are yearless dates, times,
+	/// time-zone offsets and durations, and if the end of the interval
+	/// is out of range.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use whatwg_datetime::{parse_datetime_value, DateTimeInterval, ParseMode};
+	///
+	/// let value = parse_datetime_value("2011", ParseMode::Attribute).unwrap();
+	/// match value.to_interval() {
+	///     Some(DateTimeInterval::Local(interval)) => {
+	///         assert_eq!(interval.start().date(), NaiveDate::from_ymd_opt(2011, 1, 1).unwrap());
+	///         assert_eq!(interval.end().date(), NaiveDate::from_ymd_opt(2012, 1, 1).unwrap());
+	///     }
+	///     _ => unreachable!(),
+	/// }
+	///
+	/// let value = parse_datetime_value("14:54", ParseMode::Attribute).unwrap();
+	/// assert_eq!(value.to_interval(), None);
+	/// ```
+	pub fn to_interval(&self) -> Option<DateTimeInterval> {
+		match *self {
+			WhatwgDateTime::Year(year) => {
+				year_interval(year).map(DateTimeInterval::Local)
+			}
+			WhatwgDateTime::Month(year_month) => {
+				year_month.to_interval().map(Into::into)
+			}
+			WhatwgDateTime::Week(year_week) => year_week.to_interval().map(Into::into),
+			WhatwgDateTime::Date(date) => date.to_interval().map(Into::into),
+			WhatwgDateTime::LocalDateTime(datetime) => {
+				datetime.to_interval().map(Into::into)
+			}
+			WhatwgDateTime::GlobalDateTime(datetime) => {
+				datetime.to_interval().map(Into::into)
+			}
+			WhatwgDateTime::YearlessDate(_)
+			| WhatwgDateTime::Time(_)
+			| WhatwgDateTime::TimeZoneOffset(_)
+			| WhatwgDateTime::Duration(_) => None,
+		}
+	}
+}
+
+impl DateOrTime {
+	/// The interval of time covered by this value, as described by [`ToInterval`].
+	///
+	/// This returns `None` for times, which do not describe a fixed span of
+	/// time, and if the end of the interval is out of range.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::{parse_date_or_time, DateTimeInterval, ParseMode};
+	///
+	/// let value = parse_date_or_time("2011-11-18T14:54Z", ParseMode::Attribute).unwrap();
+	/// assert!(matches!(value.to_interval(), Some(DateTimeInterval::Global(_))));
+	/// ```
+	#[inline]
+	pub fn to_interval(&self) -> Option<DateTimeInterval> {
+		WhatwgDateTime::from(*self).to_interval()
+	}
+}
+
+fn year_interval(year: i32) -> Option<Interval<NaiveDateTime>> {
+	let start = NaiveDate::from_ymd_opt(year, 1, 1)?;
+	let end = NaiveDate::from_ymd_opt(year.checked_add(1)?, 1, 1)?;
+	Interval::new(start_of_day(start), start_of_day(end))
+}
+
+/// Which of the ISO 8601 forms to serialize an [`Interval`] as.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntervalFormat {
+	/// The start and the end, such as `2011-11-18T09:00Z/2011-11-18T17:00Z`.
+	#[default]
+	StartEnd,
+	/// The start and a duration, such as `2011-11-18T09:00Z/PT8H`.
+	StartDuration,
+	/// A duration and the end, such as `PT8H/2011-11-18T17:00Z`.
+	DurationEnd,
+}
+
+/// Parse an ISO 8601 time interval between two global datetimes
+///
+/// The interval may be written as `start/end`, `start/duration` or
+/// `duration/end`, where each datetime is a
+/// [global date and time string][whatwg-html-global-datetime], and the duration
+/// is a [duration string][whatwg-html-durations] in the ISO 8601 based form.
+///
+/// This returns `None` if the start is after the end.
+///
+/// # Examples
+/// ```
+/// use chrono::Duration;
+/// use whatwg_datetime::{parse_global_datetime, parse_global_interval};
+///
+/// let start = parse_global_datetime("2011-11-18T09:00Z").unwrap();
+/// let end = parse_global_datetime("2011-11-18T17:00Z").unwrap();
+///
+/// let interval = parse_global_interval("2011-11-18T09:00Z/2011-11-18T17:00Z").unwrap();
+/// assert_eq!((interval.start(), interval.end()), (start, end));
+///
+/// let interval = parse_global_interval("PT2H/2011-11-18T17:00Z").unwrap();
+/// assert_eq!(interval.start(), end - Duration::hours(2));
+///
+/// assert_eq!(parse_global_interval("2011-11-18T17:00Z/2011-11-18T09:00Z"), None);
+/// ```
+///
+/// [whatwg-html-global-datetime]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#global-dates-and-times
+/// [whatwg-html-durations]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#durations
+pub fn parse_global_interval(s: &str) -> Option<Interval<DateTime<Utc>>> {
+	parse_interval(s, parse_global_datetime, Some, |datetime, duration| {
+		datetime.checked_add_signed(duration)
+	})
+}
+
+/// Parse an ISO 8601 time interval between two dates
+///
+/// The interval may be written as `start/end`, `start/duration` or
+/// `duration/end`, where each date is a [date string][whatwg-html-date],
+/// and the duration is a [duration string][whatwg-html-durations] in the
+/// ISO 8601 based form, of a whole number of days.
+///
+/// As with [`ToInterval`], the end date is included in the interval, so
+/// the end of the returned interval is the day after it.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::parse_date_interval;
+///
+/// let interval = parse_date_interval("2011-11-18/P3D").unwrap();
+/// assert_eq!(interval.start(), NaiveDate::from_ymd_opt(2011, 11, 18).unwrap());
+/// assert_eq!(interval.end(), NaiveDate::from_ymd_opt(2011, 11, 21).unwrap());
+///
+/// assert_eq!(parse_date_interval("2011-11-18/2011-11-20"), Some(interval));
+/// assert_eq!(parse_date_interval("2011-11-18/PT2H"), None);
+/// ```
+///
+/// [whatwg-html-date]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#dates
+/// [whatwg-html-durations]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#durations
+pub fn parse_date_interval(s: &str) -> Option<Interval<NaiveDate>> {
+	parse_interval(
+		s,
+		parse_date,
+		|date| date.succ_opt(),
+		|date, duration| {
+			if duration.num_milliseconds() % Duration::days(1).num_milliseconds() != 0 {
+				return None;
+			}
+			date.checked_add_signed(duration)
+		},
+	)
+}
+
+/// Serialize an interval between two global datetimes as an ISO 8601 time interval
+///
+/// Durations are truncated to milliseconds.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_global_interval, serialize_global_interval, IntervalFormat};
+///
+/// let interval = parse_global_interval("2011-11-18T09:00Z/2011-11-18T17:00Z").unwrap();
+/// assert_eq!(
+///     serialize_global_interval(interval, IntervalFormat::StartDuration),
+///     "2011-11-18T09:00Z/PT8H"
+/// );
+/// assert_eq!(
+///     serialize_global_interval(interval, IntervalFormat::DurationEnd),
+///     "PT8H/2011-11-18T17:00Z"
+/// );
+/// ```
+pub fn serialize_global_interval(
+	interval: Interval<DateTime<Utc>>,
+	format: IntervalFormat,
+) -> String {
+	let duration = interval.end.signed_duration_since(interval.start);
+	match format {
+		IntervalFormat::StartEnd => format!(
+			"{}{}{}",
+			serialize_global_datetime(interval.start),
+			TOKEN_SOLIDUS,
+			serialize_global_datetime(interval.end)
+		),
+		IntervalFormat::StartDuration => format!(
+			"{}{}{}",
+			serialize_global_datetime(interval.start),
+			TOKEN_SOLIDUS,
+			serialize_duration(duration)
+		),
+		IntervalFormat::DurationEnd => format!(
+			"{}{}{}",
+			serialize_duration(duration),
+			TOKEN_SOLIDUS,
+			serialize_global_datetime(interval.end)
+		),
+	}
+}
+
+/// Serialize an interval between two dates as an ISO 8601 time interval
+///
+/// The end of the interval is excluded, so the serialized end date is the
+/// day before it. An empty interval has no end date, so it is always
+/// serialized with a duration.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_date_interval, serialize_date_interval, IntervalFormat};
+///
+/// let interval = parse_date_interval("2011-11-18/P3D").unwrap();
+/// assert_eq!(
+///     serialize_date_interval(interval, IntervalFormat::StartEnd),
+///     "2011-11-18/2011-11-20"
+/// );
+/// ```
+pub fn serialize_date_interval(interval: Interval<NaiveDate>, format: IntervalFormat) -> String {
+	let duration = interval.end.signed_duration_since(interval.start);
+	let last_day = interval.end.pred_opt().filter(|_| !interval.is_instant());
+	match (format, last_day) {
+		(IntervalFormat::StartEnd, Some(last_day)) => format!(
+			"{}{}{}",
+			serialize_date(interval.start),
+			TOKEN_SOLIDUS,
+			serialize_date(last_day)
+		),
+		(IntervalFormat::DurationEnd, Some(last_day)) => format!(
+			"{}{}{}",
+			serialize_duration(duration),
+			TOKEN_SOLIDUS,
+			serialize_date(last_day)
+		),
+		_ => format!(
+			"{}{}{}",
+			serialize_date(interval.start),
+			TOKEN_SOLIDUS,
+			serialize_duration(duration)
+		),
+	}
+}
+
+/// Parses the `start/end`, `start/duration` and `duration/end` forms of an
+/// interval, given how to parse each end, how to find the instant at which
+/// a parsed end ends, and how to apply a duration.
+fn parse_interval<T, P, E, A>(s: &str, parse: P, end_of: E, add: A) -> Option<Interval<T>>
+where
+	T: Ord + Copy,
+	P: Fn(&str) -> Option<T>,
+	E: Fn(T) -> Option<T>,
+	A: Fn(T, Duration) -> Option<T>,
+{
+	let (start, end) = s.split_once(TOKEN_SOLIDUS)?;
+	match (parse_iso_duration(start), parse_iso_duration(end)) {
+		(None, None) => {
+			let (start, end) = (parse(start)?, parse(end)?);
+			if start > end {
+				return None;
+			}
+			Interval::new(start, end_of(end)?)
+		}
+		(None, Some(duration)) => {
+			let start = parse(start)?;
+			Interval::new(start, add(start, duration)?)
+		}
+		(Some(duration), None) => {
+			let end = end_of(parse(end)?)?;
+			Interval::new(add(end, -duration)?, end)
+		}
+		(Some(_), Some(_)) => None,
+	}
+}
+
+/// Parses a duration in the ISO 8601 based form, such as `PT2H`.
+fn parse_iso_duration(s: &str) -> Option<Duration> {
+	if !s.starts_with(TOKEN_P) {
+		return None;
+	}
+
+	parse_duration(s)
+}
+
+#[inline]
+fn start_of_day(date: NaiveDate) -> NaiveDateTime {
+	NaiveDateTime::new(date, NaiveTime::MIN)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		parse_date_interval, parse_global_interval, serialize_date_interval,
+		serialize_global_interval, DateTimeInterval, Interval, IntervalFormat, ToInterval,
+	};
+	use crate::{
+		parse_date, parse_date_or_time, parse_datetime_value, parse_global_datetime,
+		parse_local_datetime, ParseMode, YearMonth, YearWeek,
+	};
+	use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+	fn midnight(year: i32, month: u32, day: u32) -> NaiveDateTime {
+		NaiveDateTime::new(
+			NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+			NaiveTime::MIN,
+		)
+	}
+
+	#[test]
+	fn test_interval_new_fails_start_after_end() {
+		assert_eq!(Interval::new(2, 1), None);
+	}
+
+	#[test]
+	fn test_naive_date_to_interval() {
+		let interval = parse_date("2004-12-31").unwrap().to_interval().unwrap();
+		assert_eq!(interval.start(), midnight(2004, 12, 31));
+		assert_eq!(interval.end(), midnight(2005, 1, 1));
+	}
+
+	#[test]
+	fn test_year_week_to_interval() {
+		let interval = YearWeek::new(2004, 53).to_interval().unwrap();
+		assert_eq!(interval.start(), midnight(2004, 12, 27));
+		assert_eq!(interval.end(), midnight(2005, 1, 3));
+	}
+
+	#[test]
+	fn test_year_month_to_interval() {
+		let interval = YearMonth::new(2012, 2).to_interval().unwrap();
+		assert_eq!(interval.start(), midnight(2012, 2, 1));
+		assert_eq!(interval.end(), midnight(2012, 3, 1));
+	}
+
+	#[test]
+	fn test_to_interval_out_of_range_is_none() {
+		let max_year = NaiveDate::MAX.year();
+		assert_eq!(YearMonth::new(max_year + 1, 1).to_interval(), None);
+		assert_eq!(YearMonth::new(max_year, 12).to_interval(), None);
+		assert_eq!(YearWeek::new(max_year + 1, 1).to_interval(), None);
+		assert!(YearMonth::new(max_year, 11).to_interval().is_some());
+	}
+
+	#[test]
+	fn test_local_datetime_to_interval_is_instant() {
+		let datetime = parse_local_datetime("2004-12-31T12:31").unwrap();
+		let interval = datetime.to_interval().unwrap();
+		assert!(interval.is_instant());
+		assert_eq!(interval.start(), datetime);
+	}
+
+	#[test]
+	fn test_overlaps_across_types() {
+		let month = YearMonth::new(2004, 12);
+		assert!(month.overlaps(&YearWeek::new(2004, 53)));
+		assert!(YearWeek::new(2004, 53).overlaps(&month));
+		assert!(!month.overlaps(&YearWeek::new(2005, 1)));
+		assert!(!month.overlaps(&parse_date("2005-01-01").unwrap()));
+	}
+
+	#[test]
+	fn test_overlaps_instant() {
+		let date = parse_date("2004-12-31").unwrap();
+		let inside = parse_local_datetime("2004-12-31T23:59").unwrap();
+		let outside = parse_local_datetime("2005-01-01T00:00").unwrap();
+		assert!(date.overlaps(&inside));
+		assert!(inside.overlaps(&date));
+		assert!(!date.overlaps(&outside));
+	}
+
+	#[test]
+	fn test_contains_across_types() {
+		let month = YearMonth::new(2004, 12);
+		assert!(month.contains(&parse_date("2004-12-01").unwrap()));
+		assert!(!month.contains(&YearWeek::new(2004, 53)));
+		assert!(YearWeek::new(2004, 53).contains(&parse_date("2005-01-02").unwrap()));
+		assert!(month.contains(&month));
+	}
+
+	#[test]
+	fn test_global_datetime_interval() {
+		let start = parse_global_datetime("2004-12-31T12:00Z").unwrap();
+		let end = parse_global_datetime("2004-12-31T14:00Z").unwrap();
+		let window = Interval::new(start, end).unwrap();
+		let meeting = parse_global_datetime("2004-12-31T18:30+05:00").unwrap();
+
+		assert!(window.contains(&meeting));
+		assert!(!window.contains(&end));
+	}
+
+	#[test]
+	fn test_datetime_value_to_interval() {
+		let interval = |s| {
+			parse_datetime_value(s, ParseMode::Attribute)
+				.unwrap()
+				.to_interval()
+		};
+
+		assert_eq!(
+			interval("2004"),
+			Interval::new(midnight(2004, 1, 1), midnight(2005, 1, 1)).map(Into::into)
+		);
+		assert_eq!(
+			interval("2004-12"),
+			Interval::new(midnight(2004, 12, 1), midnight(2005, 1, 1)).map(Into::into)
+		);
+		assert_eq!(
+			interval("2004-W53"),
+			Interval::new(midnight(2004, 12, 27), midnight(2005, 1, 3)).map(Into::into)
+		);
+		assert_eq!(
+			interval("2004-12-31"),
+			Interval::new(midnight(2004, 12, 31), midnight(2005, 1, 1)).map(Into::into)
+		);
+		assert_eq!(
+			interval("2004-12-31T12:00"),
+			Some(DateTimeInterval::Local(Interval::instant(
+				midnight(2004, 12, 31) + Duration::hours(12)
+			)))
+		);
+		assert_eq!(
+			interval("2004-12-31T12:00Z"),
+			Some(DateTimeInterval::Global(Interval::instant(
+				parse_global_datetime("2004-12-31T12:00Z").unwrap()
+			)))
+		);
+		for s in ["12-31", "12:00", "+05:00", "PT4H"] {
+			assert_eq!(interval(s), None, "{}", s);
+		}
+		assert_eq!(interval("262142"), None);
+	}
+
+	#[test]
+	fn test_date_or_time_to_interval() {
+		let interval = |s| {
+			parse_date_or_time(s, ParseMode::Attribute)
+				.unwrap()
+				.to_interval()
+		};
+
+		assert_eq!(
+			interval("2004-12-31"),
+			Interval::new(midnight(2004, 12, 31), midnight(2005, 1, 1)).map(Into::into)
+		);
+		assert!(matches!(
+			interval("2004-12-31T12:00Z"),
+			Some(DateTimeInterval::Global(_))
+		));
+		assert_eq!(interval("12:00"), None);
+	}
+
+	#[test]
+	fn test_date_time_interval_overlaps_and_contains() {
+		let window: DateTimeInterval =
+			YearMonth::new(2004, 12).to_interval().unwrap().into();
+		let value = |s| {
+			parse_datetime_value(s, ParseMode::Attribute)
+				.unwrap()
+				.to_interval()
+				.unwrap()
+		};
+
+		assert!(window.overlaps(&value("2004")));
+		assert!(window.overlaps(&value("2004-W53")));
+		assert!(!window.contains(&value("2004-W53")));
+		assert!(window.contains(&value("2004-12-31T12:00")));
+		assert!(!window.overlaps(&value("2005-01-01")));
+
+		// Local and global intervals can not be compared
+		assert!(!window.overlaps(&value("2004-12-31T12:00Z")));
+		assert!(!value("2004-12-31T12:00Z").contains(&window));
+		assert!(value("2004-12-31T12:00Z").overlaps(&value("2004-12-31T17:00+05:00")));
+	}
+
+	#[test]
+	fn test_parse_global_interval_each_form() {
+		let start = parse_global_datetime("2011-11-18T09:00Z").unwrap();
+		let end = parse_global_datetime("2011-11-18T17:00Z").unwrap();
+		let expected = Interval::new(start, end);
+
+		assert_eq!(
+			parse_global_interval("2011-11-18T09:00Z/2011-11-18T17:00Z"),
+			expected
+		);
+		assert_eq!(
+			parse_global_interval("2011-11-18T14:00+05:00/PT8H"),
+			expected
+		);
+		assert_eq!(parse_global_interval("PT8H/2011-11-18T17:00Z"), expected);
+	}
+
+	#[test]
+	fn test_parse_global_interval_fails() {
+		assert_eq!(parse_global_interval("2011-11-18T09:00Z"), None);
+		assert_eq!(parse_global_interval("PT2H/PT3H"), None);
+		assert_eq!(parse_global_interval("2011-11-18T09:00Z/2h"), None);
+		assert_eq!(parse_global_interval("2011-11-18/2011-11-19"), None);
+		assert_eq!(
+			parse_global_interval("2011-11-18T09:00Z/2011-11-18T17:00Z/PT1H"),
+			None
+		);
+	}
+
+	#[test]
+	fn test_parse_date_interval_each_form() {
+		let expected = Interval::new(
+			NaiveDate::from_ymd_opt(2011, 11, 18).unwrap(),
+			NaiveDate::from_ymd_opt(2011, 11, 21).unwrap(),
+		);
+
+		assert_eq!(parse_date_interval("2011-11-18/2011-11-20"), expected);
+		assert_eq!(parse_date_interval("2011-11-18/P3D"), expected);
+		assert_eq!(parse_date_interval("P3D/2011-11-20"), expected);
+		assert_eq!(parse_date_interval("2011-11-18/2011-11-17"), None);
+		assert_eq!(
+			parse_date_interval("2011-11-18/P0D").map(|interval| interval.is_instant()),
+			Some(true)
+		);
+	}
+
+	#[test]
+	fn test_serialize_intervals_round_trip() {
+		for format in [
+			IntervalFormat::StartEnd,
+			IntervalFormat::StartDuration,
+			IntervalFormat::DurationEnd,
+		] {
+			let interval = parse_global_interval("2011-11-18T09:00Z/P1DT30M").unwrap();
+			let serialized = serialize_global_interval(interval, format);
+			assert_eq!(parse_global_interval(&serialized), Some(interval));
+
+			let interval = parse_date_interval("2011-11-18/P14D").unwrap();
+			let serialized = serialize_date_interval(interval, format);
+			assert_eq!(parse_date_interval(&serialized), Some(interval));
+		}
+
+		let empty = Interval::instant(NaiveDate::from_ymd_opt(2011, 11, 18).unwrap());
+		assert_eq!(
+			serialize_date_interval(empty, IntervalFormat::StartEnd),
+			"2011-11-18/PT0S"
+		);
+	}
+}
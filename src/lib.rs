@@ -1,9 +1,35 @@
 #![doc = include_str!("../README.md")]
 
 mod components;
+mod convert;
+pub mod cookie_date;
+pub mod ecmascript;
+mod find;
+#[cfg(feature = "html")]
+pub mod html;
+pub mod http_date;
+pub mod icalendar;
+mod input_type;
+mod interval;
+pub mod lint;
+#[doc(hidden)]
+pub mod literal;
+pub mod microformats;
+mod normalize;
+mod options;
+mod partial;
+#[cfg(feature = "rewrite")]
+pub mod rewrite;
+pub mod syntax;
 mod utils;
 
 pub use crate::components::*;
+pub use crate::convert::*;
+pub use crate::find::*;
+pub use crate::input_type::*;
+pub use crate::interval::*;
+pub use crate::options::*;
+pub use crate::partial::*;
 
 pub type ParseStringFn<T> = dyn Fn(&str) -> Option<T>;
 pub type ParseComponentFn<T> = dyn Fn(&str, &mut usize) -> Option<T>;
@@ -0,0 +1,371 @@
+//! Diagnosing near-miss datetime strings, and suggesting fixes for them.
+
+use crate::syntax::has_two_digit_fields;
+use crate::tokens::{TOKEN_DOT, TOKEN_HYPHEN, TOKEN_SOLIDUS};
+use crate::utils::collect_ascii_digits;
+use crate::{parse_datetime_value, ParseMode, WhatwgDateTime};
+use std::fmt;
+use std::ops::Range;
+
+/// Why a datetime string is not valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintKind {
+	/// A two-digit field is written with a single digit,
+	/// such as the day in `2011-11-1`, or the minute in `11:5`.
+	MissingLeadingZero,
+	/// The fields of a date are separated by something other than `-`,
+	/// such as in `2011/11/18` or `2011.11.18`.
+	DateSeparator,
+	/// A designator is written in lowercase, such as
+	/// the `t` and `z` in `2011-11-18t14:54z`, or the `w` in `2011-w47`.
+	LowercaseDesignator,
+	/// The string is not a near-miss of any datetime microsyntax.
+	Unrecognized,
+}
+
+impl fmt::Display for LintKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			LintKind::MissingLeadingZero => "field is missing a leading zero",
+			LintKind::DateSeparator => "date fields must be separated by `-`",
+			LintKind::LowercaseDesignator => "designator must be uppercase",
+			LintKind::Unrecognized => "not a valid datetime string",
+		})
+	}
+}
+
+/// A machine-applicable fix, replacing a byte range of the linted string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fix {
+	pub(crate) span: Range<usize>,
+	pub(crate) replacement: String,
+}
+
+impl Fix {
+	/// The byte range of the linted string to replace.
+	#[inline]
+	pub fn span(&self) -> Range<usize> {
+		self.span.clone()
+	}
+
+	/// The string to replace the span with.
+	#[inline]
+	pub fn replacement(&self) -> &str {
+		&self.replacement
+	}
+}
+
+/// A problem found in a datetime string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+	pub(crate) kind: LintKind,
+	pub(crate) span: Range<usize>,
+	pub(crate) fix: Option<Fix>,
+}
+
+impl Diagnostic {
+	fn with_fix(kind: LintKind, span: Range<usize>, replacement: String) -> Self {
+		Self {
+			kind,
+			span: span.clone(),
+			fix: Some(Fix { span, replacement }),
+		}
+	}
+
+	/// Why the string is not valid.
+	#[inline]
+	pub const fn kind(&self) -> LintKind {
+		self.kind
+	}
+
+	/// The byte range of the linted string the problem was found in.
+	#[inline]
+	pub fn span(&self) -> Range<usize> {
+		self.span.clone()
+	}
+
+	/// The fix for this problem, if one is known.
+	#[inline]
+	pub fn fix(&self) -> Option<&Fix> {
+		self.fix.as_ref()
+	}
+}
+
+/// Lint a string against the datetime microsyntaxes
+///
+/// This returns no diagnostics if the string is a valid
+/// [datetime value][whatwg-html-datetime-value]. Otherwise, each near-miss
+/// is reported with a fix, and applying all of them with [`apply_fixes`]
+/// is guaranteed to produce a valid string. Strings that can not be fixed
+/// are reported with a single [`LintKind::Unrecognized`] diagnostic.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::lint::{apply_fixes, lint, LintKind};
+///
+/// let diagnostics = lint("2011/11/1");
+/// assert_eq!(diagnostics[0].kind(), LintKind::DateSeparator);
+/// assert_eq!(diagnostics[2].kind(), LintKind::MissingLeadingZero);
+/// assert_eq!(diagnostics[2].span(), 8..9);
+/// assert_eq!(apply_fixes("2011/11/1", &diagnostics), "2011-11-01");
+///
+/// assert!(lint("2011-11-18T14:54Z").is_empty());
+///
+/// // The parser accepts seconds written with a single digit, but they are not valid
+/// assert_eq!(lint("14:54:5")[0].kind(), LintKind::MissingLeadingZero);
+/// ```
+///
+/// [whatwg-html-datetime-value]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#concept-time-datetime
+pub fn lint(s: &str) -> Vec<Diagnostic> {
+	if is_valid(s) {
+		return Vec::new();
+	}
+
+	let diagnostics = collect_near_misses(s);
+	if diagnostics.is_empty() {
+		return vec![Diagnostic {
+			kind: LintKind::Unrecognized,
+			span: 0..s.len(),
+			fix: None,
+		}];
+	}
+
+	diagnostics
+}
+
+/// Lint a string, and return the value of its fixed version
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveTime;
+/// use whatwg_datetime::lint::fix;
+/// use whatwg_datetime::WhatwgDateTime;
+///
+/// assert_eq!(
+///     fix("11:5"),
+///     Some((String::from("11:05"), WhatwgDateTime::Time(NaiveTime::from_hms_opt(11, 5, 0).unwrap())))
+/// );
+/// assert_eq!(fix("next tuesday"), None);
+/// ```
+pub fn fix(s: &str) -> Option<(String, WhatwgDateTime)> {
+	let fixed = apply_fixes(s, &lint(s));
+	let value = parse_datetime_value(&fixed, ParseMode::Attribute)?;
+	Some((fixed, value))
+}
+
+/// Applies the fixes of the given diagnostics to a string.
+///
+/// The diagnostics must have been produced by linting the same string.
+pub fn apply_fixes(s: &str, diagnostics: &[Diagnostic]) -> String {
+	let mut fixes: Vec<&Fix> = diagnostics.iter().filter_map(Diagnostic::fix).collect();
+	fixes.sort_by_key(|fix| fix.span.start);
+
+	let mut fixed = String::with_capacity(s.len() + fixes.len());
+	let mut position = 0usize;
+	for fix in fixes {
+		fixed.push_str(&s[position..fix.span.start]);
+		fixed.push_str(&fix.replacement);
+		position = fix.span.end;
+	}
+	fixed.push_str(&s[position..]);
+
+	fixed
+}
+
+/// The most ambiguous fixes that are tried in every combination.
+const MAX_AMBIGUOUS_FIXES: usize = 8;
+
+/// Collects the fixes that turn a near-miss into a valid string.
+///
+/// Some fixes are always needed, such as for a `/` or a lowercase designator,
+/// since those never appear in a valid string. Others are ambiguous: a `.`
+/// separates the fields of a date, but also comes before the fraction of a
+/// second, and a single digit is a field missing its leading zero, unless it
+/// is that fraction. Rather than deciding these with a grammar of its own,
+/// the lint lets the component parsers decide, through
+/// [`parse_datetime_value`] and the components they read, by trying
+/// the fewest ambiguous fixes first.
+fn collect_near_misses(s: &str) -> Vec<Diagnostic> {
+	// All positions below are byte offsets, which only equal
+	// the character positions used by the component parsers for ASCII.
+	if !s.is_ascii() {
+		return Vec::new();
+	}
+
+	let mut required = Vec::new();
+	let mut ambiguous = Vec::new();
+	let mut position = 0usize;
+	while position < s.len() {
+		let start = position;
+		let digits = collect_ascii_digits(s, &mut position);
+		if digits.len() == 1 {
+			ambiguous.push(Diagnostic::with_fix(
+				LintKind::MissingLeadingZero,
+				start..position,
+				format!("0{}", digits),
+			));
+		}
+		if !digits.is_empty() {
+			continue;
+		}
+
+		let c = s.as_bytes()[position] as char;
+		let span = position..position + 1;
+		match c {
+			TOKEN_SOLIDUS => required.push(Diagnostic::with_fix(
+				LintKind::DateSeparator,
+				span,
+				TOKEN_HYPHEN.to_string(),
+			)),
+			TOKEN_DOT => ambiguous.push(Diagnostic::with_fix(
+				LintKind::DateSeparator,
+				span,
+				TOKEN_HYPHEN.to_string(),
+			)),
+			't' | 'z' | 'w' => required.push(Diagnostic::with_fix(
+				LintKind::LowercaseDesignator,
+				span,
+				c.to_ascii_uppercase().to_string(),
+			)),
+			_ => (),
+		}
+		position += 1;
+	}
+
+	if ambiguous.len() > MAX_AMBIGUOUS_FIXES {
+		return Vec::new();
+	}
+
+	let mut combinations: Vec<u32> = (0..1u32 << ambiguous.len()).collect();
+	combinations.sort_by_key(|combination| combination.count_ones());
+	for combination in combinations {
+		let mut diagnostics = required.clone();
+		diagnostics.extend(ambiguous
+			.iter()
+			.enumerate()
+			.filter(|(i, _)| combination & (1 << i) != 0)
+			.map(|(_, diagnostic)| diagnostic.clone()));
+		diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+
+		if is_valid(&apply_fixes(s, &diagnostics)) {
+			return diagnostics;
+		}
+	}
+
+	Vec::new()
+}
+
+/// Whether a string is a valid datetime value.
+fn is_valid(s: &str) -> bool {
+	parse_datetime_value(s, ParseMode::Attribute).is_some() && has_two_digit_fields(s)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{apply_fixes, fix, lint, LintKind};
+
+	fn kinds(s: &str) -> Vec<LintKind> {
+		lint(s).iter().map(|diagnostic| diagnostic.kind()).collect()
+	}
+
+	fn fixed(s: &str) -> String {
+		apply_fixes(s, &lint(s))
+	}
+
+	#[test]
+	fn test_lint_valid() {
+		for s in ["2011-11-18", "14:54:39.9", "2011-W47", "2011", "+05:30"] {
+			assert!(lint(s).is_empty());
+		}
+	}
+
+	#[test]
+	fn test_lint_missing_leading_zero() {
+		assert_eq!(kinds("2011-11-1"), [LintKind::MissingLeadingZero]);
+		assert_eq!(fixed("2011-11-1"), "2011-11-01");
+		assert_eq!(fixed("11:5"), "11:05");
+		assert_eq!(fixed("1:5:9"), "01:05:09");
+		assert_eq!(fixed("2011-W7"), "2011-W07");
+		assert_eq!(fixed("2011-1"), "2011-01");
+	}
+
+	#[test]
+	fn test_lint_single_digit_seconds_parsed_but_invalid() {
+		let diagnostics = lint("14:54:5");
+		assert_eq!(diagnostics.len(), 1);
+		assert_eq!(diagnostics[0].kind(), LintKind::MissingLeadingZero);
+		assert_eq!(diagnostics[0].span(), 6..7);
+		assert_eq!(fixed("14:54:5"), "14:54:05");
+
+		assert_eq!(kinds("2011-11-18T14:54:5Z"), [LintKind::MissingLeadingZero]);
+		assert_eq!(fixed("2011-11-18T14:54:5Z"), "2011-11-18T14:54:05Z");
+		assert_eq!(
+			fix("2011-11-18T14:54:5Z").unwrap().0,
+			"2011-11-18T14:54:05Z"
+		);
+	}
+
+	#[test]
+	fn test_lint_keeps_fraction_digits() {
+		assert_eq!(fixed("14:5:39.9"), "14:05:39.9");
+	}
+
+	#[test]
+	fn test_lint_date_separator() {
+		assert_eq!(
+			kinds("2011/11/18"),
+			[LintKind::DateSeparator, LintKind::DateSeparator]
+		);
+		assert_eq!(fixed("2011/11/18"), "2011-11-18");
+		assert_eq!(fixed("2011.11.18 14:54"), "2011-11-18 14:54");
+	}
+
+	#[test]
+	fn test_lint_lowercase_designator() {
+		let diagnostics = lint("2011-11-18t14:54z");
+		assert_eq!(diagnostics.len(), 2);
+		assert_eq!(diagnostics[0].kind(), LintKind::LowercaseDesignator);
+		assert_eq!(diagnostics[0].span(), 10..11);
+		assert_eq!(diagnostics[0].fix().unwrap().replacement(), "T");
+		assert_eq!(fixed("2011-11-18t14:54z"), "2011-11-18T14:54Z");
+		assert_eq!(fixed("2011-w47"), "2011-W47");
+	}
+
+	#[test]
+	fn test_lint_combined() {
+		assert_eq!(fixed("2011/11/1t9:5z"), "2011-11-01T09:05Z");
+	}
+
+	#[test]
+	fn test_lint_component_parsers_decide_ambiguous_fixes() {
+		// A `.` before a fraction of a second, and a fraction of one digit, are kept
+		assert_eq!(fixed("2011.11.18 14:54:39.9"), "2011-11-18 14:54:39.9");
+		assert_eq!(fixed("2011.1.1T1:05:09.5Z"), "2011-01-01T01:05:09.5Z");
+		assert_eq!(fixed("11.18"), "11-18");
+		assert_eq!(kinds("2011.11.18"), [LintKind::DateSeparator; 2]);
+	}
+
+	#[test]
+	fn test_lint_unrecognized() {
+		for s in [
+			"next tuesday",
+			"2011-13-01",
+			"2011/11/31",
+			"",
+			"2011-11-18\u{a0}",
+		] {
+			let diagnostics = lint(s);
+			assert_eq!(diagnostics.len(), 1, "{}", s);
+			assert_eq!(diagnostics[0].kind(), LintKind::Unrecognized);
+			assert_eq!(diagnostics[0].span(), 0..s.len());
+			assert_eq!(diagnostics[0].fix(), None);
+		}
+	}
+
+	#[test]
+	fn test_fix() {
+		assert_eq!(fix("2011-11-18").unwrap().0, "2011-11-18");
+		assert_eq!(fix("2011/11/1").unwrap().0, "2011-11-01");
+		assert_eq!(fix("2011-02-30"), None);
+	}
+}
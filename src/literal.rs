@@ -0,0 +1,485 @@
+//! Support for the literal macros, such as [`date!`](crate::date).
+//!
+//! The scanners in this module follow the same steps as the component
+//! parsers, but are `const fn`s, so that literals are validated at
+//! compile time.
+
+use crate::tokens::{
+	TOKEN_ABBR_WEEK, TOKEN_COLON, TOKEN_DOT, TOKEN_HYPHEN, TOKEN_MINUS, TOKEN_PLUS, TOKEN_Z,
+};
+use crate::utils::max_days_in_month_year;
+use crate::{TimeZoneDesignator, TimeZoneOffset, YearMonth, YearWeek};
+use chrono::{NaiveDate, NaiveTime};
+
+/// Parse a [date][whatwg-html-date] literal into a [`NaiveDate`], at compile time
+///
+/// Invalid literals fail compilation.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::date;
+///
+/// assert_eq!(date!("2011-11-18"), NaiveDate::from_ymd_opt(2011, 11, 18).unwrap());
+/// ```
+///
+/// ```compile_fail
+/// let date = whatwg_datetime::date!("2011-02-29");
+/// ```
+///
+/// ```compile_fail
+/// // The year is later than `NaiveDate` can represent
+/// let date = whatwg_datetime::date!("300000-01-01");
+/// ```
+///
+/// [whatwg-html-date]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#dates
+#[macro_export]
+macro_rules! date {
+	($s:literal) => {{
+		const VALUE: (i32, u32, u32) = match $crate::literal::scan_date($s) {
+			Some(value) => value,
+			None => panic!(concat!("invalid date literal: ", $s)),
+		};
+		$crate::literal::naive_date(VALUE)
+	}};
+}
+
+/// Parse a [month][whatwg-html-month] literal into a [`YearMonth`](crate::YearMonth),
+/// at compile time
+///
+/// Invalid literals fail compilation. The result is a constant expression.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{month, YearMonth};
+///
+/// const NOVEMBER: YearMonth = month!("2011-11");
+/// assert_eq!(NOVEMBER, YearMonth::new_opt(2011, 11).unwrap());
+/// ```
+///
+/// ```compile_fail
+/// let month = whatwg_datetime::month!("2011-13");
+/// ```
+///
+/// [whatwg-html-month]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#months
+#[macro_export]
+macro_rules! month {
+	($s:literal) => {{
+		const VALUE: $crate::YearMonth = match $crate::literal::scan_month($s) {
+			Some((year, month)) => $crate::literal::year_month(year, month),
+			None => panic!(concat!("invalid month literal: ", $s)),
+		};
+		VALUE
+	}};
+}
+
+/// Parse a [week][whatwg-html-week] literal into a [`YearWeek`](crate::YearWeek),
+/// at compile time
+///
+/// Invalid literals fail compilation. The result is a constant expression.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{week, YearWeek};
+///
+/// const WEEK: YearWeek = week!("2011-W47");
+/// assert_eq!(WEEK, YearWeek::new_opt(2011, 47).unwrap());
+/// ```
+///
+/// ```compile_fail
+/// // 2011 only has 52 weeks
+/// let week = whatwg_datetime::week!("2011-W53");
+/// ```
+///
+/// [whatwg-html-week]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#weeks
+#[macro_export]
+macro_rules! week {
+	($s:literal) => {{
+		const VALUE: $crate::YearWeek = match $crate::literal::scan_week($s) {
+			Some((year, week)) => $crate::literal::year_week(year, week),
+			None => panic!(concat!("invalid week literal: ", $s)),
+		};
+		VALUE
+	}};
+}
+
+/// Parse a [time][whatwg-html-time] literal into a [`NaiveTime`], at compile time
+///
+/// Invalid literals fail compilation.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveTime;
+/// use whatwg_datetime::time;
+///
+/// assert_eq!(time!("14:54:39.929"), NaiveTime::from_hms_milli_opt(14, 54, 39, 929).unwrap());
+/// ```
+///
+/// ```compile_fail
+/// let time = whatwg_datetime::time!("24:00");
+/// ```
+///
+/// [whatwg-html-time]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#times
+#[macro_export]
+macro_rules! time {
+	($s:literal) => {{
+		const VALUE: (u32, u32, u32, u32) = match $crate::literal::scan_time($s) {
+			Some(value) => value,
+			None => panic!(concat!("invalid time literal: ", $s)),
+		};
+		$crate::literal::naive_time(VALUE)
+	}};
+}
+
+/// Parse a [time-zone offset][whatwg-html-tzoffset] literal into
+/// a [`TimeZoneOffset`](crate::TimeZoneOffset), at compile time
+///
+/// Invalid literals fail compilation. The result is a constant expression.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{offset, TimeZoneOffset};
+///
+/// const IST: TimeZoneOffset = offset!("+05:30");
+/// assert_eq!(IST, TimeZoneOffset::new_opt(5, 30).unwrap());
+/// assert!(offset!("Z").is_utc());
+/// ```
+///
+/// ```compile_fail
+/// let offset = whatwg_datetime::offset!("+5:30");
+/// ```
+///
+/// [whatwg-html-tzoffset]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#time-zones
+#[macro_export]
+macro_rules! offset {
+	($s:literal) => {{
+		const VALUE: $crate::TimeZoneOffset =
+			match $crate::literal::scan_timezone_offset($s) {
+				Some(offset) => offset,
+				None => panic!(concat!("invalid time-zone offset literal: ", $s)),
+			};
+		VALUE
+	}};
+}
+
+pub const fn scan_date(s: &str) -> Option<(i32, u32, u32)> {
+	let s = s.as_bytes();
+	let (year, position) = match scan_year(s) {
+		Some(year) => year,
+		None => return None,
+	};
+	let month = match scan_hyphen_field(s, position, 1, 12) {
+		Some(month) => month,
+		None => return None,
+	};
+	let max_day = match max_days_in_month_year(month, year as u32) {
+		Some(max_day) => max_day,
+		None => return None,
+	};
+	match scan_hyphen_field(s, position + 3, 1, max_day) {
+		Some(day) if s.len() == position + 6 => Some((year, month, day)),
+		_ => None,
+	}
+}
+
+pub const fn scan_month(s: &str) -> Option<(i32, u32)> {
+	let s = s.as_bytes();
+	let (year, position) = match scan_year(s) {
+		Some(year) => year,
+		None => return None,
+	};
+	match scan_hyphen_field(s, position, 1, 12) {
+		Some(month) if s.len() == position + 3 => Some((year, month)),
+		_ => None,
+	}
+}
+
+pub const fn scan_week(s: &str) -> Option<(i32, u32)> {
+	let s = s.as_bytes();
+	let (year, position) = match scan_year(s) {
+		Some(year) => year,
+		None => return None,
+	};
+	if s.len() != position + 4
+		|| s[position] != TOKEN_HYPHEN as u8
+		|| s[position + 1] != TOKEN_ABBR_WEEK as u8
+	{
+		return None;
+	}
+	match scan_field(s, position + 2, 1, weeks_in_year(year)) {
+		Some(week) => Some((year, week)),
+		None => None,
+	}
+}
+
+pub const fn scan_time(s: &str) -> Option<(u32, u32, u32, u32)> {
+	let s = s.as_bytes();
+	let hour = match scan_field(s, 0, 0, 23) {
+		Some(hour) => hour,
+		None => return None,
+	};
+	if s.len() < 5 || s[2] != TOKEN_COLON as u8 {
+		return None;
+	}
+	let minute = match scan_field(s, 3, 0, 59) {
+		Some(minute) => minute,
+		None => return None,
+	};
+	if s.len() == 5 {
+		return Some((hour, minute, 0, 0));
+	}
+
+	if s.len() < 8 || s[5] != TOKEN_COLON as u8 {
+		return None;
+	}
+	let second = match scan_field(s, 6, 0, 59) {
+		Some(second) => second,
+		None => return None,
+	};
+	if s.len() == 8 {
+		return Some((hour, minute, second, 0));
+	}
+
+	// The fraction of a second, truncated to nanoseconds
+	if s.len() < 10 || s[8] != TOKEN_DOT as u8 {
+		return None;
+	}
+	let mut nanosecond = 0u32;
+	let mut position = 9;
+	while position < s.len() {
+		if !s[position].is_ascii_digit() {
+			return None;
+		}
+		if position < 18 {
+			nanosecond = nanosecond * 10 + (s[position] - b'0') as u32;
+		}
+		position += 1;
+	}
+	while position < 18 {
+		nanosecond *= 10;
+		position += 1;
+	}
+
+	Some((hour, minute, second, nanosecond))
+}
+
+pub const fn scan_timezone_offset(s: &str) -> Option<TimeZoneOffset> {
+	let s = s.as_bytes();
+	if s.len() == 1 && s[0] == TOKEN_Z as u8 {
+		return Some(TimeZoneOffset::utc());
+	}
+
+	let designator = match s {
+		[b, ..] if *b == TOKEN_PLUS as u8 => TimeZoneDesignator::Plus,
+		[b, ..] if *b == TOKEN_MINUS as u8 => TimeZoneDesignator::Minus,
+		_ => return None,
+	};
+	let hours = match scan_field(s, 1, 0, 23) {
+		Some(hours) => hours,
+		None => return None,
+	};
+	let minutes_position = match s.len() {
+		5 => 3,
+		6 if s[3] == TOKEN_COLON as u8 => 4,
+		_ => return None,
+	};
+	let minutes = match scan_field(s, minutes_position, 0, 59) {
+		Some(minutes) => minutes,
+		None => return None,
+	};
+
+	let total_minutes = (hours * 60 + minutes) as i32;
+	Some(TimeZoneOffset::with_designator(
+		match designator {
+			TimeZoneDesignator::Minus => -total_minutes,
+			_ => total_minutes,
+		},
+		designator,
+	))
+}
+
+#[inline]
+pub const fn year_month(year: i32, month: u32) -> YearMonth {
+	YearMonth::new(year, month)
+}
+
+#[inline]
+pub const fn year_week(year: i32, week: u32) -> YearWeek {
+	YearWeek::new(year, week)
+}
+
+pub fn naive_date((year, month, day): (i32, u32, u32)) -> NaiveDate {
+	// The date was validated by scan_date
+	NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+pub fn naive_time((hour, minute, second, nanosecond): (u32, u32, u32, u32)) -> NaiveTime {
+	// The time was validated by scan_time
+	NaiveTime::from_hms_nano_opt(hour, minute, second, nanosecond).unwrap()
+}
+
+/// The year of [`NaiveDate::MAX`], which can not be read in a `const fn`.
+const MAX_YEAR: i32 = 262_142;
+
+/// Scans a year of four or more digits, which must be greater than 0 and
+/// no later than [`MAX_YEAR`], returning it along with the position after it.
+const fn scan_year(s: &[u8]) -> Option<(i32, usize)> {
+	let mut year = 0i32;
+	let mut position = 0;
+	while position < s.len() && s[position].is_ascii_digit() {
+		year = match year.checked_mul(10) {
+			Some(year) => match year.checked_add((s[position] - b'0') as i32) {
+				Some(year) => year,
+				None => return None,
+			},
+			None => return None,
+		};
+		position += 1;
+	}
+
+	if position < 4 || year == 0 || year > MAX_YEAR {
+		return None;
+	}
+
+	Some((year, position))
+}
+
+/// Scans a hyphen followed by a two-digit field.
+const fn scan_hyphen_field(s: &[u8], position: usize, min: u32, max: u32) -> Option<u32> {
+	if position >= s.len() || s[position] != TOKEN_HYPHEN as u8 {
+		return None;
+	}
+	scan_field(s, position + 1, min, max)
+}
+
+/// Scans a two-digit field, which must be within `min..=max`.
+const fn scan_field(s: &[u8], position: usize, min: u32, max: u32) -> Option<u32> {
+	if position + 1 >= s.len()
+		|| !s[position].is_ascii_digit()
+		|| !s[position + 1].is_ascii_digit()
+	{
+		return None;
+	}
+
+	let value = (s[position] - b'0') as u32 * 10 + (s[position + 1] - b'0') as u32;
+	if value < min || value > max {
+		return None;
+	}
+
+	Some(value)
+}
+
+/// The number of weeks in a year, following the same rules as
+/// `week_number_of_year`, with the weekday of January 1st
+/// found by Sakamoto's method.
+const fn weeks_in_year(year: i32) -> u32 {
+	let y = year as i64 - 1;
+	// 0 is Sunday
+	let weekday = (y + y / 4 - y / 100 + y / 400 + 1).rem_euclid(7);
+	let is_leap_year = year % 400 == 0 || (year % 4 == 0 && year % 100 != 0);
+
+	match weekday {
+		4 => 53,
+		3 if is_leap_year => 53,
+		_ => 52,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		scan_date, scan_month, scan_time, scan_timezone_offset, scan_week, weeks_in_year,
+		MAX_YEAR,
+	};
+	use crate::utils::week_number_of_year;
+	use crate::{parse_time, TimeZoneOffset, YearMonth, YearWeek};
+	use chrono::{Datelike, NaiveDate, NaiveTime};
+
+	#[test]
+	fn test_literal_macros() {
+		assert_eq!(
+			date!("2011-11-18"),
+			NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+		);
+		assert_eq!(month!("2011-11"), YearMonth::new(2011, 11));
+		assert_eq!(week!("2004-W53"), YearWeek::new(2004, 53));
+		assert_eq!(
+			time!("14:54:39.929"),
+			NaiveTime::from_hms_milli_opt(14, 54, 39, 929).unwrap()
+		);
+		assert_eq!(offset!("-0800"), TimeZoneOffset::new(-8, 0));
+	}
+
+	#[test]
+	fn test_scan_date() {
+		assert_eq!(scan_date("2012-02-29"), Some((2012, 2, 29)));
+		assert_eq!(scan_date("12011-11-18"), Some((12011, 11, 18)));
+		assert_eq!(scan_date("2011-02-29"), None);
+		assert_eq!(scan_date("0000-01-01"), None);
+		assert_eq!(scan_date("2011-11-18T"), None);
+		assert_eq!(scan_date("2011-11-1"), None);
+		assert_eq!(scan_date("99999999999-01-01"), None);
+	}
+
+	#[test]
+	fn test_scan_year_is_within_naive_date_range() {
+		assert_eq!(MAX_YEAR, NaiveDate::MAX.year());
+		assert_eq!(scan_date("262142-12-31"), Some((262142, 12, 31)));
+		assert_eq!(scan_date("262143-01-01"), None);
+		assert_eq!(scan_date("300000-01-01"), None);
+		assert_eq!(scan_month("262143-01"), None);
+		assert_eq!(scan_week("262143-W01"), None);
+	}
+
+	#[test]
+	fn test_scan_month_and_week() {
+		assert_eq!(scan_month("2011-12"), Some((2011, 12)));
+		assert_eq!(scan_month("2011-00"), None);
+		assert_eq!(scan_week("2011-W52"), Some((2011, 52)));
+		assert_eq!(scan_week("2011-W53"), None);
+		assert_eq!(scan_week("2011-W00"), None);
+	}
+
+	#[test]
+	fn test_scan_time_matches_parse_time() {
+		for s in [
+			"00:00",
+			"23:59:59",
+			"14:54:39.5",
+			"14:54:39.1234567891",
+			"24:00",
+			"14:60",
+			"14:54:",
+			"14:54:39.",
+		] {
+			let scanned = scan_time(s).map(|(hour, minute, second, nanosecond)| {
+				NaiveTime::from_hms_nano_opt(hour, minute, second, nanosecond)
+					.unwrap()
+			});
+			assert_eq!(scanned, parse_time(s), "{}", s);
+		}
+	}
+
+	#[test]
+	fn test_scan_timezone_offset() {
+		assert_eq!(
+			scan_timezone_offset("+05:30"),
+			Some(TimeZoneOffset::new(5, 30))
+		);
+		assert!(scan_timezone_offset("Z").unwrap().is_utc());
+		assert_eq!(scan_timezone_offset("+24:00"), None);
+		assert_eq!(scan_timezone_offset("+05:3"), None);
+	}
+
+	#[test]
+	fn test_weeks_in_year_matches_week_number_of_year() {
+		for year in 1..=2400 {
+			assert_eq!(
+				Some(weeks_in_year(year)),
+				week_number_of_year(year),
+				"{}",
+				year
+			);
+		}
+	}
+}
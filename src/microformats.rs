@@ -0,0 +1,357 @@
+//! Parsing the dates and times of microformats `dt-*` properties that are split
+//! across elements, following the [value-class-pattern][mf-vcp].
+//!
+//! Each element with a `value` class gives one value string, such as a date,
+//! a time, or a time-zone offset:
+//!
+//! ```html
+//! <span class="dt-start">
+//!   <span class="value">2011-11-18</span> at
+//!   <span class="value">2:54pm</span><span class="value">-0800</span>
+//! </span>
+//! ```
+//!
+//! [mf-vcp]: https://microformats.org/wiki/value-class-pattern#Date_and_time_parsing
+
+use crate::tokens::{TOKEN_COLON, TOKEN_HYPHEN, TOKEN_MINUS, TOKEN_PLUS, TOKEN_SPACE, TOKEN_T};
+use crate::utils::collect_ascii_digits;
+use crate::{
+	parse_date_component, parse_time_component, parse_timezone_offset_component,
+	to_utc_datetime, TimeZoneDesignator, TimeZoneOffset, WhatwgDateTime,
+};
+use chrono::{NaiveDate, NaiveTime};
+use whatwg_infra::{collect_codepoints, trim_ascii_whitespace};
+
+/// The `am` and `pm` markers of a 12-hour time, and whether each is after noon.
+const MERIDIEMS: [(&str, bool); 4] = [("am", false), ("pm", true), ("a.m.", false), ("p.m.", true)];
+
+/// The parts of a datetime found in one value string.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ValueParts {
+	date: Option<NaiveDate>,
+	time: Option<NaiveTime>,
+	offset: Option<TimeZoneOffset>,
+}
+
+/// Combine the value strings of a microformats `dt-*` property, in document order,
+/// into a single value, following the [value-class-pattern][mf-vcp]
+///
+/// Each value string, with surrounding ASCII whitespace trimmed, may be:
+///
+/// - a date, as `YYYY-MM-DD` or the ordinal `YYYY-DDD`
+/// - a time, as `hh:mm[:ss[.sss]]`, or a 12-hour time followed by `am`, `pm`,
+///   `a.m.` or `p.m.` in any case, such as `2pm` or `2:54:39 p.m.`
+/// - a time-zone offset, as `Z`, `±hh:mm`, `±hhmm` or `±hh`
+/// - a time followed by a time-zone offset, such as `14:54-0800`
+/// - a date and a time, separated by `T` or a space, and an optional offset
+///
+/// The first date, the first time, and the first time-zone offset are used,
+/// and value strings that match none of these forms are ignored. The result is a
+/// [`WhatwgDateTime::GlobalDateTime`] when there is a date, a time and an offset,
+/// a [`WhatwgDateTime::LocalDateTime`] when there is a date and a time, and
+/// otherwise a [`WhatwgDateTime::Date`] or a [`WhatwgDateTime::Time`] on its own.
+/// This returns `None` when there is neither a date nor a time.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveTime;
+/// use whatwg_datetime::{microformats, parse_global_datetime, WhatwgDateTime};
+///
+/// assert_eq!(
+///     microformats::parse(["2011-11-18", "14:54", "-0800"]),
+///     parse_global_datetime("2011-11-18T14:54-08:00").map(WhatwgDateTime::GlobalDateTime)
+/// );
+/// assert_eq!(
+///     microformats::parse(["2:54 p.m."]),
+///     NaiveTime::from_hms_opt(14, 54, 0).map(WhatwgDateTime::Time)
+/// );
+/// assert_eq!(microformats::parse(["tomorrow", "-08:00"]), None);
+/// ```
+///
+/// [mf-vcp]: https://microformats.org/wiki/value-class-pattern#Date_and_time_parsing
+pub fn parse<I, S>(values: I) -> Option<WhatwgDateTime>
+where
+	I: IntoIterator<Item = S>,
+	S: AsRef<str>,
+{
+	let mut combined = ValueParts::default();
+	for value in values {
+		if let Some(parts) = parse_value(trim_ascii_whitespace(value.as_ref())) {
+			combined.date = combined.date.or(parts.date);
+			combined.time = combined.time.or(parts.time);
+			combined.offset = combined.offset.or(parts.offset);
+		}
+	}
+
+	match combined {
+		ValueParts {
+			date: Some(date),
+			time: Some(time),
+			offset: Some(offset),
+		} => to_utc_datetime(date, time, offset).map(WhatwgDateTime::GlobalDateTime),
+		ValueParts {
+			date: Some(date),
+			time: Some(time),
+			offset: None,
+		} => Some(WhatwgDateTime::LocalDateTime(date.and_time(time))),
+		ValueParts {
+			date: Some(date), ..
+		} => Some(WhatwgDateTime::Date(date)),
+		ValueParts {
+			time: Some(time), ..
+		} => Some(WhatwgDateTime::Time(time)),
+		_ => None,
+	}
+}
+
+/// Parses a single value string, which must match one of the forms in full.
+fn parse_value(s: &str) -> Option<ValueParts> {
+	if s.is_empty() || !s.is_ascii() {
+		return None;
+	}
+
+	let mut position = 0usize;
+	let date = try_component(s, &mut position, parse_date_or_ordinal_date);
+	if date.is_some() {
+		if position == s.len() {
+			return Some(ValueParts {
+				date,
+				..ValueParts::default()
+			});
+		}
+
+		match s.as_bytes()[position] as char {
+			TOKEN_T | TOKEN_SPACE => position += 1,
+			_ => return None,
+		}
+	}
+
+	let time = try_component(s, &mut position, parse_meridiem_time_component)
+		.or_else(|| try_component(s, &mut position, parse_time_component));
+	if date.is_some() && time.is_none() {
+		return None;
+	}
+
+	let offset = if position < s.len() {
+		Some(parse_offset_component(s, &mut position)?)
+	} else {
+		None
+	};
+	if position < s.len() {
+		return None;
+	}
+
+	Some(ValueParts { date, time, offset })
+}
+
+/// Runs a component parser, only advancing the position if it succeeds.
+fn try_component<T, F>(s: &str, position: &mut usize, parse_fn: F) -> Option<T>
+where
+	F: FnOnce(&str, &mut usize) -> Option<T>,
+{
+	let mut next_position = *position;
+	let parsed = parse_fn(s, &mut next_position)?;
+	*position = next_position;
+	Some(parsed)
+}
+
+/// Parses `YYYY-MM-DD`, or the ordinal date `YYYY-DDD`.
+fn parse_date_or_ordinal_date(s: &str, position: &mut usize) -> Option<NaiveDate> {
+	if let Some(date) = try_component(s, position, parse_date_component) {
+		return Some(date);
+	}
+
+	let parsed_year = collect_ascii_digits(s, position);
+	if parsed_year.len() < 4 || s.chars().nth(*position) != Some(TOKEN_HYPHEN) {
+		return None;
+	}
+	*position += 1;
+
+	let parsed_ordinal = collect_ascii_digits(s, position);
+	if parsed_ordinal.len() != 3 {
+		return None;
+	}
+
+	NaiveDate::from_yo_opt(parsed_year.parse().ok()?, parsed_ordinal.parse().ok()?)
+}
+
+/// Parses a 12-hour time, `h[:mm[:ss]]` followed by optional ASCII whitespace
+/// and an `am` or `pm` marker, in any case and with or without periods.
+fn parse_meridiem_time_component(s: &str, position: &mut usize) -> Option<NaiveTime> {
+	let parsed_hour = collect_ascii_digits(s, position);
+	if !(1..=2).contains(&parsed_hour.len()) {
+		return None;
+	}
+	let hour = parsed_hour.parse::<u32>().ok()?;
+	if !(1..=12).contains(&hour) {
+		return None;
+	}
+
+	let mut minute_and_second = [0u32; 2];
+	for field in minute_and_second.iter_mut() {
+		if s.chars().nth(*position) != Some(TOKEN_COLON) {
+			break;
+		}
+		*position += 1;
+
+		let parsed_field = collect_ascii_digits(s, position);
+		if parsed_field.len() != 2 {
+			return None;
+		}
+		*field = parsed_field.parse().ok()?;
+	}
+
+	collect_codepoints(s, position, |c| c.is_ascii_whitespace());
+	let rest = &s[*position..];
+	let (marker, is_pm) = MERIDIEMS.iter().find(|(marker, _)| {
+		rest.get(..marker.len())
+			.map_or(false, |prefix| prefix.eq_ignore_ascii_case(marker))
+	})?;
+	*position += marker.len();
+
+	let hour = hour % 12 + if *is_pm { 12 } else { 0 };
+	NaiveTime::from_hms_opt(hour, minute_and_second[0], minute_and_second[1])
+}
+
+/// Parses a time-zone offset component, which here may also be `±hh`.
+fn parse_offset_component(s: &str, position: &mut usize) -> Option<TimeZoneOffset> {
+	if let Some(offset) = try_component(s, position, parse_timezone_offset_component) {
+		return Some(offset);
+	}
+
+	let designator = match s.chars().nth(*position)? {
+		TOKEN_PLUS => TimeZoneDesignator::Plus,
+		TOKEN_MINUS => TimeZoneDesignator::Minus,
+		_ => return None,
+	};
+	*position += 1;
+
+	let parsed_hours = collect_ascii_digits(s, position);
+	if parsed_hours.len() != 2 {
+		return None;
+	}
+	let hours = parsed_hours.parse::<i32>().ok()?;
+	if hours > 23 {
+		return None;
+	}
+
+	let minutes = match designator {
+		TimeZoneDesignator::Minus => -hours * 60,
+		_ => hours * 60,
+	};
+	Some(TimeZoneOffset::with_designator(minutes, designator))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse, parse_value, ValueParts};
+	use crate::{parse_global_datetime, TimeZoneOffset, WhatwgDateTime};
+	use chrono::{NaiveDate, NaiveTime};
+
+	fn time(h: u32, m: u32, s: u32) -> NaiveTime {
+		NaiveTime::from_hms_opt(h, m, s).unwrap()
+	}
+
+	fn global(s: &str) -> Option<WhatwgDateTime> {
+		parse_global_datetime(s).map(WhatwgDateTime::GlobalDateTime)
+	}
+
+	#[test]
+	fn test_parse_combines_parts() {
+		assert_eq!(
+			parse(["2011-11-18", "14:54:39.929", "+05:30"]),
+			global("2011-11-18T14:54:39.929+05:30")
+		);
+		assert_eq!(
+			parse(vec![String::from(" 2011-11-18\n"), String::from("14:54")]),
+			Some(WhatwgDateTime::LocalDateTime(
+				NaiveDate::from_ymd_opt(2011, 11, 18)
+					.unwrap()
+					.and_time(time(14, 54, 0))
+			))
+		);
+		assert_eq!(
+			parse(["2011-11-18", "-08:00"]),
+			NaiveDate::from_ymd_opt(2011, 11, 18).map(WhatwgDateTime::Date)
+		);
+		assert_eq!(
+			parse(["14:54", "Z"]),
+			Some(WhatwgDateTime::Time(time(14, 54, 0)))
+		);
+	}
+
+	#[test]
+	fn test_parse_first_of_each_part_wins() {
+		assert_eq!(
+			parse(["2011-11-18", "at", "14:54-0800", "2012-01-01", "15:00", "Z"]),
+			global("2011-11-18T14:54-08:00")
+		);
+		assert_eq!(
+			parse(["2011-11-18T14:54", "-08", "15:00"]),
+			global("2011-11-18T14:54-08:00")
+		);
+	}
+
+	#[test]
+	fn test_parse_fails_without_date_or_time() {
+		assert_eq!(parse(Vec::<&str>::new()), None);
+		assert_eq!(parse(["", "Z", "+05:30"]), None);
+	}
+
+	#[test]
+	fn test_parse_value_meridiem_times() {
+		for (s, expected) in [
+			("12am", time(0, 0, 0)),
+			("12 PM", time(12, 0, 0)),
+			("1:30a.m.", time(1, 30, 0)),
+			("11:59:59 p.m.", time(23, 59, 59)),
+			("2:54pm", time(14, 54, 0)),
+		] {
+			assert_eq!(
+				parse_value(s).and_then(|parts| parts.time),
+				Some(expected),
+				"{}",
+				s
+			);
+		}
+
+		for s in ["0am", "13pm", "2:5pm", "2pmx", "2 p.m", "14:54 pm"] {
+			assert_eq!(parse_value(s), None, "{}", s);
+		}
+	}
+
+	#[test]
+	fn test_parse_value_each_form() {
+		let date = NaiveDate::from_ymd_opt(2011, 11, 18);
+		assert_eq!(parse_value("2011-322").and_then(|parts| parts.date), date);
+		assert_eq!(
+			parse_value("2011-11-18 2pm-08"),
+			Some(ValueParts {
+				date,
+				time: Some(time(14, 0, 0)),
+				offset: TimeZoneOffset::new_opt(-8, 0),
+			})
+		);
+		assert_eq!(
+			parse_value("+0530"),
+			Some(ValueParts {
+				offset: TimeZoneOffset::new_opt(5, 30),
+				..ValueParts::default()
+			})
+		);
+
+		for s in [
+			"",
+			"2011-11",
+			"2011-11-18T",
+			"2011-11-18/14:54",
+			"2011-366",
+			"14:54 tomorrow",
+			"+24",
+			"+5",
+			"2011–11–18",
+		] {
+			assert_eq!(parse_value(s), None, "{}", s);
+		}
+	}
+}
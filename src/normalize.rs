@@ -0,0 +1,227 @@
+use crate::tokens::{TOKEN_ABBR_WEEK, TOKEN_COLON, TOKEN_DOT, TOKEN_HYPHEN, TOKEN_SPACE, TOKEN_T};
+use crate::utils::collect_ascii_digits;
+use crate::Leniency;
+use std::ops::Range;
+use whatwg_infra::trim_ascii_whitespace;
+
+/// A replacement of a byte range of a near-miss datetime string,
+/// which brings it closer to a valid one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Edit {
+	pub(crate) leniency: Leniency,
+	pub(crate) span: Range<usize>,
+	pub(crate) replacement: String,
+}
+
+impl Edit {
+	fn new(leniency: Leniency, span: Range<usize>, replacement: String) -> Self {
+		Self {
+			leniency,
+			span,
+			replacement,
+		}
+	}
+}
+
+/// Scans a string for the shape of a datetime string, collecting the edits
+/// that would turn each near-miss into its valid form, in order.
+///
+/// Two-digit years are resolved into the hundred years starting at `year_pivot`.
+pub(crate) fn collect_edits(s: &str, year_pivot: i32) -> Vec<Edit> {
+	let mut edits = Vec::new();
+	// All positions below are byte offsets, which only equal
+	// the character positions used by the scanners for ASCII.
+	if !s.is_ascii() {
+		return edits;
+	}
+
+	let trimmed = trim_ascii_whitespace(s);
+	let offset = trimmed.as_ptr() as usize - s.as_ptr() as usize;
+	if trimmed.len() != s.len() {
+		if offset > 0 {
+			edits.push(Edit::new(
+				Leniency::SurroundingWhitespace,
+				0..offset,
+				String::new(),
+			));
+		}
+		if offset + trimmed.len() < s.len() {
+			edits.push(Edit::new(
+				Leniency::SurroundingWhitespace,
+				offset + trimmed.len()..s.len(),
+				String::new(),
+			));
+		}
+	}
+	let s = trimmed;
+	let bytes = s.as_bytes();
+
+	// A string with a colon and no date before it is only a time (and offset)
+	let date_end = match s.find(TOKEN_COLON) {
+		Some(colon) if !s[..colon].contains(['-', '/', '.']) => 0,
+		_ => s.find(|c: char| matches!(c.to_ascii_uppercase(), TOKEN_T | TOKEN_SPACE))
+			.unwrap_or(s.len()),
+	};
+
+	let mut position = 0usize;
+	let mut date_fields = 0usize;
+	while position < s.len() {
+		let start = position;
+		let digits = collect_ascii_digits(s, &mut position);
+		let in_date = start < date_end;
+		if !digits.is_empty() {
+			if in_date {
+				date_fields += 1;
+			}
+
+			if in_date
+				&& date_fields == 1 && digits.len() == 2
+				&& is_two_digit_year(&s[..date_end])
+			{
+				// A year out of the range of `i32` is left as is, and fails to parse
+				if let Some(year) =
+					resolve_two_digit_year(digits.parse().unwrap(), year_pivot)
+				{
+					edits.push(Edit::new(
+						Leniency::TwoDigitYear,
+						offset + start..offset + position,
+						format!("{:04}", year),
+					));
+				}
+			}
+
+			// Fractions of a second may be any number of digits
+			let is_fraction =
+				!in_date && start > 0 && matches!(bytes[start - 1], b'.' | b',');
+			if digits.len() == 1 && !is_fraction {
+				edits.push(Edit::new(
+					Leniency::SingleDigitField,
+					offset + start..offset + position,
+					format!("0{}", digits),
+				));
+			}
+			continue;
+		}
+
+		let c = bytes[position] as char;
+		let span = offset + position..offset + position + 1;
+		match c {
+			'/' | '.' if in_date => {
+				edits.push(Edit::new(
+					Leniency::DateSeparator,
+					span,
+					TOKEN_HYPHEN.to_string(),
+				));
+			}
+			',' if !in_date && start > 0 && bytes[start - 1].is_ascii_digit() => {
+				edits.push(Edit::new(
+					Leniency::DecimalComma,
+					span,
+					TOKEN_DOT.to_string(),
+				));
+			}
+			't' | 'z' | 'w' => {
+				edits.push(Edit::new(
+					Leniency::LowercaseDesignator,
+					span,
+					c.to_ascii_uppercase().to_string(),
+				));
+			}
+			_ => (),
+		}
+		position += 1;
+	}
+
+	edits
+}
+
+/// Applies edits collected from a string to it.
+pub(crate) fn apply_edits<'a, I>(s: &str, edits: I) -> String
+where
+	I: IntoIterator<Item = &'a Edit>,
+{
+	let mut edits: Vec<&Edit> = edits.into_iter().collect();
+	edits.sort_by_key(|edit| edit.span.start);
+
+	let mut edited = String::with_capacity(s.len() + edits.len());
+	let mut position = 0usize;
+	for edit in edits {
+		edited.push_str(&s[position..edit.span.start]);
+		edited.push_str(&edit.replacement);
+		position = edit.span.end;
+	}
+	edited.push_str(&s[position..]);
+
+	edited
+}
+
+/// Whether the date part of a string starts with a two-digit year, which is
+/// only unambiguous for full dates (`yy-mm-dd`) and weeks (`yy-Www`).
+/// A two-digit year and month can not be told apart from a yearless date.
+fn is_two_digit_year(date: &str) -> bool {
+	let fields = date
+		.split(|c: char| !c.is_ascii_digit())
+		.filter(|field| !field.is_empty())
+		.count();
+	let is_week = date
+		.as_bytes()
+		.get(3)
+		.map_or(false, |c| c.eq_ignore_ascii_case(&(TOKEN_ABBR_WEEK as u8)));
+
+	fields == 3 || is_week
+}
+
+#[inline]
+fn resolve_two_digit_year(year: i32, pivot: i32) -> Option<i32> {
+	let (year, pivot) = (i64::from(year), i64::from(pivot));
+	i32::try_from(pivot + (year - pivot).rem_euclid(100)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{apply_edits, collect_edits, resolve_two_digit_year};
+	use crate::Leniency;
+
+	fn normalize(s: &str) -> String {
+		apply_edits(s, &collect_edits(s, 1970))
+	}
+
+	#[test]
+	fn test_collect_edits() {
+		let edits = collect_edits(" 11:5,25", 1970);
+		let leniencies: Vec<Leniency> = edits.iter().map(|edit| edit.leniency).collect();
+		assert_eq!(
+			leniencies,
+			[
+				Leniency::SurroundingWhitespace,
+				Leniency::SingleDigitField,
+				Leniency::DecimalComma
+			]
+		);
+		assert_eq!(edits[1].span, 4..5);
+	}
+
+	#[test]
+	fn test_normalize_two_digit_years() {
+		assert_eq!(normalize("11-11-18"), "2011-11-18");
+		assert_eq!(normalize("69/7/4"), "2069-07-04");
+		assert_eq!(normalize("70/7/4"), "1970-07-04");
+		assert_eq!(normalize("11-w7"), "2011-W07");
+		assert_eq!(normalize("11-18"), "11-18");
+	}
+
+	#[test]
+	fn test_resolve_two_digit_year_extreme_pivots() {
+		assert_eq!(resolve_two_digit_year(11, 1970), Some(2011));
+		assert_eq!(resolve_two_digit_year(11, -50), Some(11));
+		assert_eq!(resolve_two_digit_year(11, i32::MAX), None);
+		assert_eq!(resolve_two_digit_year(47, i32::MAX - 99), Some(2147483647));
+		assert_eq!(resolve_two_digit_year(11, i32::MIN), Some(-2147483589));
+
+		// The year is left as is
+		assert_eq!(
+			apply_edits("11-11-18", &collect_edits("11-11-18", i32::MAX)),
+			"11-11-18"
+		);
+	}
+}
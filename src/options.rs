@@ -0,0 +1,1043 @@
+use crate::normalize::{apply_edits, collect_edits};
+use crate::syntax::{has_two_digit_fields, scan_components, ComponentKind, Token, TokenKind};
+use crate::tokens::TOKEN_T;
+use crate::{
+	parse_date, parse_date_or_time, parse_datetime_value, parse_duration,
+	parse_global_datetime, parse_local_datetime, parse_month, parse_time,
+	parse_timezone_offset, parse_week, parse_yearless_date, DateOrTime, ParseMode,
+	TimeZoneOffset, WhatwgDateTime, YearMonth, YearWeek, YearlessDate,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// A deviation from the datetime microsyntaxes that can be
+/// accepted when parsing with [`ParseOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Leniency {
+	/// A two-digit field written with a single digit, such as `2011-11-1` or `9:05`.
+	SingleDigitField,
+	/// Date fields separated by `/` or `.`, such as `2011/11/18` or `2011.11.18`.
+	DateSeparator,
+	/// A lowercase `t`, `z`, or `w` designator, such as `2011-11-18t14:54z`.
+	LowercaseDesignator,
+	/// A comma separating the fraction of a second, such as `14:54:39,929`.
+	DecimalComma,
+	/// A two-digit year in a date or a week, such as `11-11-18` or `11-W47`.
+	///
+	/// The year is resolved with [`ParseOptions::two_digit_year_pivot`].
+	TwoDigitYear,
+	/// Leading or trailing ASCII whitespace, such as ` 2011-11-18 `.
+	SurroundingWhitespace,
+}
+
+impl Leniency {
+	/// All of the leniencies.
+	pub const ALL: [Leniency; 6] = [
+		Leniency::SingleDigitField,
+		Leniency::DateSeparator,
+		Leniency::LowercaseDesignator,
+		Leniency::DecimalComma,
+		Leniency::TwoDigitYear,
+		Leniency::SurroundingWhitespace,
+	];
+
+	#[inline]
+	const fn bit(self) -> u8 {
+		1 << self as u8
+	}
+}
+
+/// A rule of a stricter profile of the datetime microsyntaxes,
+/// enforced when parsing with [`ParseOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Restriction {
+	/// A date and a time must be separated by `T`, not by a space.
+	TSeparator,
+	/// Times must include seconds, such as `14:54:00` instead of `14:54`.
+	SecondsRequired,
+	/// The fraction of a second must not have more digits than
+	/// [`ParseOptions::max_fraction_digits`] allows.
+	MaxFractionDigits,
+	/// Global datetimes must be in UTC, written with a `Z` offset.
+	UtcOnly,
+	/// Years must have exactly four digits.
+	FourDigitYears,
+	/// Years must be within [`ParseOptions::year_range`].
+	YearRange,
+}
+
+impl fmt::Display for Restriction {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Restriction::TSeparator => "date and time must be separated by `T`",
+			Restriction::SecondsRequired => "time must include seconds",
+			Restriction::MaxFractionDigits => {
+				"fraction of a second has too many digits"
+			}
+			Restriction::UtcOnly => "global datetime must be in UTC",
+			Restriction::FourDigitYears => "year must have four digits",
+			Restriction::YearRange => "year is out of range",
+		})
+	}
+}
+
+/// Why parsing a string failed, either with [`ParseOptions`], or through
+/// the [`FromStr`](std::str::FromStr) and [`TryFrom`] implementations of
+/// the value types, which only return [`ParseError::Invalid`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{ParseError, YearWeek};
+///
+/// assert_eq!("2011-W53".parse::<YearWeek>(), Err(ParseError::Invalid));
+/// assert_eq!(ParseError::Invalid.to_string(), "not a valid datetime string");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseError {
+	/// The string is not valid, even with the allowed leniencies.
+	Invalid,
+	/// The string is valid, but violates a restriction.
+	Violation(Restriction),
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ParseError::Invalid => f.write_str("not a valid datetime string"),
+			ParseError::Violation(restriction) => restriction.fmt(f),
+		}
+	}
+}
+
+impl Error for ParseError {}
+
+/// Options for parsing strings that deviate from the datetime microsyntaxes,
+/// either by being more forgiving or by being stricter.
+///
+/// The options created by [`ParseOptions::new`] accept nothing but
+/// the microsyntaxes themselves. Each [`Leniency`] can be allowed on top of
+/// them for user-typed input, and [`ParseOptions::lenient`] allows all of them.
+/// Stricter profiles are built by adding each [`Restriction`] with
+/// its own builder method, such as [`ParseOptions::seconds_required`].
+///
+/// Strings are normalized according to the allowed leniencies, parsed with
+/// the given parser, such as [`parse_date`], and then checked against the
+/// restrictions. Every parser of the datetime microsyntaxes has a counterpart
+/// that takes the options, such as [`parse_date_with`] and
+/// [`parse_datetime_value_with`], and any other parser can be used with
+/// [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use whatwg_datetime::{parse_date, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new()
+///     .allow(Leniency::DateSeparator)
+///     .allow(Leniency::SingleDigitField);
+/// let parsed = options.parse("2011/11/1", parse_date).unwrap();
+///
+/// assert_eq!(parsed.value(), &NaiveDate::from_ymd_opt(2011, 11, 1).unwrap());
+/// assert_eq!(
+///     parsed.leniencies(),
+///     [Leniency::DateSeparator, Leniency::SingleDigitField]
+/// );
+///
+/// assert!(options.parse(" 2011/11/1", parse_date).is_err());
+/// ```
+///
+/// A stricter profile:
+/// ```
+/// use whatwg_datetime::{parse_global_datetime, ParseError, ParseOptions, Restriction};
+///
+/// let options = ParseOptions::new()
+///     .t_separator_only()
+///     .seconds_required()
+///     .max_fraction_digits(3)
+///     .utc_only()
+///     .four_digit_years()
+///     .year_range(1900..=2100);
+///
+/// assert!(options.parse("2011-11-18T14:54:39.929Z", parse_global_datetime).is_ok());
+/// assert_eq!(
+///     options.parse("2011-11-18T14:54Z", parse_global_datetime),
+///     Err(ParseError::Violation(Restriction::SecondsRequired))
+/// );
+/// assert_eq!(
+///     options.parse("2111-11-18T14:54:39Z", parse_global_datetime),
+///     Err(ParseError::Violation(Restriction::YearRange))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+	pub(crate) leniencies: u8,
+	pub(crate) two_digit_year_pivot: i32,
+	pub(crate) restrictions: u8,
+	pub(crate) max_fraction_digits: usize,
+	pub(crate) year_range: (i32, i32),
+}
+
+impl ParseOptions {
+	/// The default start of the hundred years that two-digit years are resolved into.
+	pub const DEFAULT_YEAR_PIVOT: i32 = 1970;
+
+	/// Creates options that only accept the datetime microsyntaxes.
+	pub const fn new() -> Self {
+		Self {
+			leniencies: 0,
+			two_digit_year_pivot: Self::DEFAULT_YEAR_PIVOT,
+			restrictions: 0,
+			max_fraction_digits: usize::MAX,
+			year_range: (i32::MIN, i32::MAX),
+		}
+	}
+
+	/// Creates options that allow every [`Leniency`].
+	pub const fn lenient() -> Self {
+		let mut options = Self::new();
+		let mut i = 0;
+		while i < Leniency::ALL.len() {
+			options = options.allow(Leniency::ALL[i]);
+			i += 1;
+		}
+
+		options
+	}
+
+	/// Allows a leniency.
+	#[inline]
+	pub const fn allow(mut self, leniency: Leniency) -> Self {
+		self.leniencies |= leniency.bit();
+		self
+	}
+
+	/// Disallows a leniency.
+	#[inline]
+	pub const fn disallow(mut self, leniency: Leniency) -> Self {
+		self.leniencies &= !leniency.bit();
+		self
+	}
+
+	/// Whether a leniency is allowed.
+	#[inline]
+	pub const fn allows(&self, leniency: Leniency) -> bool {
+		self.leniencies & leniency.bit() != 0
+	}
+
+	/// Sets the start of the hundred years that two-digit years are resolved into,
+	/// which defaults to [`ParseOptions::DEFAULT_YEAR_PIVOT`].
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::{parse_date, ParseOptions};
+	///
+	/// let options = ParseOptions::lenient();
+	/// assert_eq!(options.parse("69-07-20", parse_date).unwrap().value().to_string(), "2069-07-20");
+	///
+	/// let options = options.two_digit_year_pivot(1950);
+	/// assert_eq!(options.parse("69-07-20", parse_date).unwrap().value().to_string(), "1969-07-20");
+	/// ```
+	#[inline]
+	pub const fn two_digit_year_pivot(mut self, pivot: i32) -> Self {
+		self.two_digit_year_pivot = pivot;
+		self
+	}
+
+	/// Requires dates and times to be separated by `T`.
+	#[inline]
+	pub const fn t_separator_only(self) -> Self {
+		self.restrict(Restriction::TSeparator)
+	}
+
+	/// Requires times to include seconds.
+	#[inline]
+	pub const fn seconds_required(self) -> Self {
+		self.restrict(Restriction::SecondsRequired)
+	}
+
+	/// Limits the number of digits in the fraction of a second.
+	#[inline]
+	pub const fn max_fraction_digits(mut self, digits: usize) -> Self {
+		self.max_fraction_digits = digits;
+		self.restrict(Restriction::MaxFractionDigits)
+	}
+
+	/// Requires global datetimes to be in UTC, written with a `Z` offset.
+	#[inline]
+	pub const fn utc_only(self) -> Self {
+		self.restrict(Restriction::UtcOnly)
+	}
+
+	/// Requires years to have exactly four digits.
+	#[inline]
+	pub const fn four_digit_years(self) -> Self {
+		self.restrict(Restriction::FourDigitYears)
+	}
+
+	/// Limits years to a range, such as `1900..=2100`.
+	///
+	/// The year is the one written in the string, which may differ from
+	/// the year in UTC for global datetimes with a non-zero offset.
+	#[inline]
+	pub const fn year_range(mut self, range: RangeInclusive<i32>) -> Self {
+		self.year_range = (*range.start(), *range.end());
+		self.restrict(Restriction::YearRange)
+	}
+
+	/// Whether a restriction is enforced.
+	#[inline]
+	pub const fn enforces(&self, restriction: Restriction) -> bool {
+		self.restrictions & (1 << restriction as u8) != 0
+	}
+
+	#[inline]
+	const fn restrict(mut self, restriction: Restriction) -> Self {
+		self.restrictions |= 1 << restriction as u8;
+		self
+	}
+
+	/// Parses a string with any of the `parse_*` functions of this crate,
+	/// after normalizing the leniencies allowed by these options, and checks
+	/// the value as written against the enforced restrictions.
+	///
+	/// Strings that are valid as they are are parsed without any leniency.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::{parse_time, serialize_time, Leniency, ParseOptions};
+	///
+	/// let parsed = ParseOptions::lenient().parse(" 9:5:39,5 ", parse_time).unwrap();
+	///
+	/// assert_eq!(serialize_time(*parsed.value()), "09:05:39.5");
+	/// assert_eq!(
+	///     parsed.leniencies(),
+	///     [
+	///         Leniency::SurroundingWhitespace,
+	///         Leniency::SingleDigitField,
+	///         Leniency::DecimalComma
+	///     ]
+	/// );
+	/// ```
+	pub fn parse<T, F>(&self, s: &str, parse_fn: F) -> Result<Parsed<T>, ParseError>
+	where
+		F: Fn(&str) -> Option<T>,
+	{
+		// The parsers accept some strings that are not valid, such as seconds
+		// written with a single digit, which are only accepted as leniencies
+		if let Some(value) = parse_fn(s).filter(|_| has_two_digit_fields(s)) {
+			self.check_restrictions(s).map_err(ParseError::Violation)?;
+			return Ok(Parsed {
+				value,
+				leniencies: Vec::new(),
+			});
+		}
+
+		let edits = collect_edits(s, self.two_digit_year_pivot);
+		if edits.is_empty() {
+			return Err(ParseError::Invalid);
+		}
+
+		let allowed = edits.iter().filter(|edit| self.allows(edit.leniency));
+		let normalized = apply_edits(s, allowed.clone());
+		let value = parse_fn(&normalized)
+			.filter(|_| has_two_digit_fields(&normalized))
+			.ok_or(ParseError::Invalid)?;
+		self.check_restrictions(&normalized)
+			.map_err(ParseError::Violation)?;
+
+		let mut leniencies = Vec::new();
+		for edit in allowed {
+			if !leniencies.contains(&edit.leniency) {
+				leniencies.push(edit.leniency);
+			}
+		}
+
+		Ok(Parsed { value, leniencies })
+	}
+
+	/// Checks a valid datetime string against the enforced restrictions,
+	/// using the components and tokens that the component parsers read.
+	fn check_restrictions(&self, s: &str) -> Result<(), Restriction> {
+		if self.restrictions == 0 {
+			return Ok(());
+		}
+
+		let components = scan_components(s);
+		let has = |kind| components.iter().any(|component| component.kind() == kind);
+		let is_global = has(ComponentKind::Date) && has(ComponentKind::TimeZoneOffset);
+		for component in &components {
+			let has_seconds = component
+				.tokens()
+				.iter()
+				.any(|token| token.kind() == TokenKind::Second);
+			if self.enforces(Restriction::SecondsRequired)
+				&& component.kind() == ComponentKind::Time
+				&& !has_seconds
+			{
+				return Err(Restriction::SecondsRequired);
+			}
+
+			for token in component.tokens() {
+				self.check_token(token, is_global)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn check_token(&self, token: &Token<'_>, is_global: bool) -> Result<(), Restriction> {
+		let text = token.text();
+		match token.kind() {
+			TokenKind::Year => {
+				if self.enforces(Restriction::FourDigitYears) && text.len() != 4 {
+					return Err(Restriction::FourDigitYears);
+				}
+
+				let (min_year, max_year) = self.year_range;
+				let in_range = text
+					.parse::<i32>()
+					.map_or(false, |year| min_year <= year && year <= max_year);
+				if self.enforces(Restriction::YearRange) && !in_range {
+					return Err(Restriction::YearRange);
+				}
+			}
+			TokenKind::DateTimeSeparator
+				if self.enforces(Restriction::TSeparator)
+					&& text != TOKEN_T.to_string() =>
+			{
+				return Err(Restriction::TSeparator);
+			}
+			TokenKind::Fraction
+				if self.enforces(Restriction::MaxFractionDigits)
+					&& text.len() > self.max_fraction_digits =>
+			{
+				return Err(Restriction::MaxFractionDigits);
+			}
+			TokenKind::Sign if self.enforces(Restriction::UtcOnly) && is_global => {
+				return Err(Restriction::UtcOnly);
+			}
+			_ => (),
+		}
+
+		Ok(())
+	}
+}
+
+impl Default for ParseOptions {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// A value parsed with [`ParseOptions`], along with the leniencies
+/// that were needed to parse it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Parsed<T> {
+	pub(crate) value: T,
+	pub(crate) leniencies: Vec<Leniency>,
+}
+
+impl<T> Parsed<T> {
+	/// The parsed value.
+	#[inline]
+	pub fn value(&self) -> &T {
+		&self.value
+	}
+
+	/// Consumes this, returning the parsed value.
+	#[inline]
+	pub fn into_value(self) -> T {
+		self.value
+	}
+
+	/// The leniencies that were needed to parse the value,
+	/// in the order they first appear in the string.
+	#[inline]
+	pub fn leniencies(&self) -> &[Leniency] {
+		&self.leniencies
+	}
+
+	/// Whether the string was valid without any leniency.
+	#[inline]
+	pub fn is_strict(&self) -> bool {
+		self.leniencies.is_empty()
+	}
+}
+
+/// Parse a [date][whatwg-html-date] string with [`ParseOptions`]
+///
+/// This is [`parse_date`](crate::parse_date), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_date_with, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::DateSeparator);
+/// let parsed = parse_date_with("2011/11/18", &options).unwrap();
+/// assert_eq!(parsed.leniencies(), [Leniency::DateSeparator]);
+/// ```
+///
+/// [whatwg-html-date]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#dates
+#[inline]
+pub fn parse_date_with(s: &str, options: &ParseOptions) -> Result<Parsed<NaiveDate>, ParseError> {
+	options.parse(s, parse_date)
+}
+
+/// Parse a [month][whatwg-html-month] string with [`ParseOptions`]
+///
+/// This is [`parse_month`](crate::parse_month), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_month_with, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::SingleDigitField);
+/// let parsed = parse_month_with("2011-1", &options).unwrap();
+/// assert_eq!(parsed.leniencies(), [Leniency::SingleDigitField]);
+/// ```
+///
+/// [whatwg-html-month]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#months
+#[inline]
+pub fn parse_month_with(s: &str, options: &ParseOptions) -> Result<Parsed<YearMonth>, ParseError> {
+	options.parse(s, parse_month)
+}
+
+/// Parse a [week][whatwg-html-week] string with [`ParseOptions`]
+///
+/// This is [`parse_week`](crate::parse_week), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_week_with, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::LowercaseDesignator);
+/// let parsed = parse_week_with("2011-w47", &options).unwrap();
+/// assert_eq!(parsed.leniencies(), [Leniency::LowercaseDesignator]);
+/// ```
+///
+/// [whatwg-html-week]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#weeks
+#[inline]
+pub fn parse_week_with(s: &str, options: &ParseOptions) -> Result<Parsed<YearWeek>, ParseError> {
+	options.parse(s, parse_week)
+}
+
+/// Parse a [yearless date][whatwg-html-yearless-date] string with [`ParseOptions`]
+///
+/// This is [`parse_yearless_date`](crate::parse_yearless_date), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_yearless_date_with, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::DateSeparator);
+/// let parsed = parse_yearless_date_with("11/18", &options).unwrap();
+/// assert_eq!(parsed.leniencies(), [Leniency::DateSeparator]);
+/// ```
+///
+/// [whatwg-html-yearless-date]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#yearless-dates
+#[inline]
+pub fn parse_yearless_date_with(
+	s: &str,
+	options: &ParseOptions,
+) -> Result<Parsed<YearlessDate>, ParseError> {
+	options.parse(s, parse_yearless_date)
+}
+
+/// Parse a [time][whatwg-html-time] string with [`ParseOptions`]
+///
+/// This is [`parse_time`](crate::parse_time), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_time_with, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::SingleDigitField);
+/// let parsed = parse_time_with("9:05", &options).unwrap();
+/// assert_eq!(parsed.leniencies(), [Leniency::SingleDigitField]);
+/// ```
+///
+/// [whatwg-html-time]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#times
+#[inline]
+pub fn parse_time_with(s: &str, options: &ParseOptions) -> Result<Parsed<NaiveTime>, ParseError> {
+	options.parse(s, parse_time)
+}
+
+/// Parse a [local date and time][whatwg-html-local-datetime] string with [`ParseOptions`]
+///
+/// This is [`parse_local_datetime`](crate::parse_local_datetime), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_local_datetime_with, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::LowercaseDesignator);
+/// let parsed = parse_local_datetime_with("2011-11-18t14:54", &options).unwrap();
+/// assert_eq!(parsed.leniencies(), [Leniency::LowercaseDesignator]);
+/// ```
+///
+/// [whatwg-html-local-datetime]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#local-dates-and-times
+#[inline]
+pub fn parse_local_datetime_with(
+	s: &str,
+	options: &ParseOptions,
+) -> Result<Parsed<NaiveDateTime>, ParseError> {
+	options.parse(s, parse_local_datetime)
+}
+
+/// Parse a [global date and time][whatwg-html-global-datetime] string with [`ParseOptions`]
+///
+/// This is [`parse_global_datetime`](crate::parse_global_datetime), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_global_datetime_with, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::DecimalComma);
+/// let parsed = parse_global_datetime_with("2011-11-18T14:54:39,929Z", &options).unwrap();
+/// assert_eq!(parsed.leniencies(), [Leniency::DecimalComma]);
+/// ```
+///
+/// [whatwg-html-global-datetime]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#global-dates-and-times
+#[inline]
+pub fn parse_global_datetime_with(
+	s: &str,
+	options: &ParseOptions,
+) -> Result<Parsed<DateTime<Utc>>, ParseError> {
+	options.parse(s, parse_global_datetime)
+}
+
+/// Parse a [time-zone offset][whatwg-html-tzoffset] string with [`ParseOptions`]
+///
+/// This is [`parse_timezone_offset`](crate::parse_timezone_offset), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_timezone_offset_with, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::SurroundingWhitespace);
+/// let parsed = parse_timezone_offset_with(" +05:30 ", &options).unwrap();
+/// assert_eq!(parsed.leniencies(), [Leniency::SurroundingWhitespace]);
+/// ```
+///
+/// [whatwg-html-tzoffset]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#time-zones
+#[inline]
+pub fn parse_timezone_offset_with(
+	s: &str,
+	options: &ParseOptions,
+) -> Result<Parsed<TimeZoneOffset>, ParseError> {
+	options.parse(s, parse_timezone_offset)
+}
+
+/// Parse a [duration][whatwg-html-duration] string with [`ParseOptions`]
+///
+/// This is [`parse_duration`](crate::parse_duration), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`].
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_duration_with, Leniency, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::SurroundingWhitespace);
+/// let parsed = parse_duration_with(" PT4H18M3S ", &options).unwrap();
+/// assert_eq!(parsed.leniencies(), [Leniency::SurroundingWhitespace]);
+/// ```
+///
+/// [whatwg-html-duration]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#durations
+#[inline]
+pub fn parse_duration_with(
+	s: &str,
+	options: &ParseOptions,
+) -> Result<Parsed<Duration>, ParseError> {
+	options.parse(s, parse_duration)
+}
+
+/// Parse a [datetime value][whatwg-html-datetime-value] string with [`ParseOptions`]
+///
+/// This is [`parse_datetime_value`](crate::parse_datetime_value), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`]. With [`ParseMode::Content`],
+/// leading and trailing ASCII whitespace is skipped before the options apply, and is not
+/// reported as a leniency.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_datetime_value_with, Leniency, ParseMode, ParseOptions};
+///
+/// let options = ParseOptions::new().allow(Leniency::DateSeparator);
+/// let parsed = parse_datetime_value_with(" 2011/11/18 ", ParseMode::Content, &options).unwrap();
+/// assert_eq!(parsed.value().serialize(), "2011-11-18");
+/// assert_eq!(parsed.leniencies(), [Leniency::DateSeparator]);
+/// ```
+///
+/// [whatwg-html-datetime-value]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#concept-time-datetime
+#[inline]
+pub fn parse_datetime_value_with(
+	s: &str,
+	mode: ParseMode,
+	options: &ParseOptions,
+) -> Result<Parsed<WhatwgDateTime>, ParseError> {
+	options.parse(mode.prepare(s), |s| parse_datetime_value(s, mode))
+}
+
+/// Parse a [date or time][whatwg-html-parse] string with [`ParseOptions`]
+///
+/// This is [`parse_date_or_time`](crate::parse_date_or_time), with the leniencies and
+/// restrictions of the options, as in [`ParseOptions::parse`]. With [`ParseMode::Content`],
+/// leading and trailing ASCII whitespace is skipped before the options apply, and is not
+/// reported as a leniency.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::{parse_date_or_time_with, ParseError, ParseMode, ParseOptions, Restriction};
+///
+/// let options = ParseOptions::new().utc_only();
+/// let parsed = parse_date_or_time_with("2011-11-18T14:54Z", ParseMode::Attribute, &options);
+/// assert!(parsed.is_ok());
+/// assert_eq!(
+///     parse_date_or_time_with("2011-11-18T14:54+05:30", ParseMode::Attribute, &options),
+///     Err(ParseError::Violation(Restriction::UtcOnly))
+/// );
+/// ```
+///
+/// [whatwg-html-parse]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#parse-a-date-or-time-string
+#[inline]
+pub fn parse_date_or_time_with(
+	s: &str,
+	mode: ParseMode,
+	options: &ParseOptions,
+) -> Result<Parsed<DateOrTime>, ParseError> {
+	options.parse(mode.prepare(s), |s| parse_date_or_time(s, mode))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		parse_date_or_time_with, parse_date_with, parse_datetime_value_with,
+		parse_duration_with, parse_global_datetime_with, parse_local_datetime_with,
+		parse_time_with, Leniency, ParseError, ParseOptions, Restriction,
+	};
+	use crate::{
+		parse_date, parse_datetime_value, parse_duration, parse_global_datetime,
+		parse_local_datetime, parse_month, parse_time, parse_timezone_offset, parse_week,
+		serialize_global_datetime, serialize_local_datetime, ParseMode, YearWeek,
+	};
+	use chrono::NaiveDate;
+	use std::error::Error;
+
+	#[test]
+	fn test_parse_options_new_is_strict() {
+		let options = ParseOptions::new();
+		for leniency in Leniency::ALL {
+			assert!(!options.allows(leniency));
+		}
+		assert!(options.parse("2011-11-18", parse_date).unwrap().is_strict());
+		assert_eq!(
+			options.parse("2011/11/18", parse_date),
+			Err(ParseError::Invalid)
+		);
+	}
+
+	#[test]
+	fn test_parse_single_digit_seconds_is_a_leniency() {
+		// The parser accepts seconds written with a single digit, but they are not valid
+		assert!(parse_time("14:54:5").is_some());
+		assert_eq!(
+			ParseOptions::new().parse("14:54:5", parse_time),
+			Err(ParseError::Invalid)
+		);
+		assert_eq!(
+			parse_global_datetime_with("2011-11-18T14:54:5Z", &ParseOptions::new()),
+			Err(ParseError::Invalid)
+		);
+
+		let parsed = ParseOptions::lenient()
+			.parse("14:54:5", parse_time)
+			.unwrap();
+		assert!(!parsed.is_strict());
+		assert_eq!(parsed.leniencies(), [Leniency::SingleDigitField]);
+	}
+
+	#[test]
+	fn test_parse_options_allow_disallow() {
+		let options = ParseOptions::lenient().disallow(Leniency::DecimalComma);
+		assert!(options.allows(Leniency::DateSeparator));
+		assert!(!options.allows(Leniency::DecimalComma));
+		assert!(options.parse("14:54:39,9", parse_time).is_err());
+		assert!(options.parse("14:54:39.9", parse_time).is_ok());
+	}
+
+	#[test]
+	fn test_parse_lenient_date() {
+		let parsed = ParseOptions::lenient()
+			.parse("11.11.18", parse_date)
+			.unwrap();
+		assert_eq!(
+			parsed.value(),
+			&NaiveDate::from_ymd_opt(2011, 11, 18).unwrap()
+		);
+		assert_eq!(
+			parsed.leniencies(),
+			[Leniency::TwoDigitYear, Leniency::DateSeparator]
+		);
+	}
+
+	#[test]
+	fn test_parse_lenient_week() {
+		let parsed = ParseOptions::lenient()
+			.parse("2011-w7", parse_week)
+			.unwrap();
+		assert_eq!(parsed.value(), &YearWeek::new(2011, 7));
+		assert_eq!(
+			parsed.leniencies(),
+			[Leniency::LowercaseDesignator, Leniency::SingleDigitField]
+		);
+	}
+
+	#[test]
+	fn test_parse_lenient_local_datetime() {
+		let parsed = ParseOptions::lenient()
+			.parse("\t2011/11/18 9:5\n", parse_local_datetime)
+			.unwrap();
+		assert_eq!(
+			serialize_local_datetime(*parsed.value()),
+			"2011-11-18T09:05"
+		);
+	}
+
+	#[test]
+	fn test_parse_lenient_global_datetime() {
+		let parsed = ParseOptions::lenient()
+			.parse("2011-11-18t14:54:39,5z", parse_global_datetime)
+			.unwrap();
+		assert_eq!(
+			serialize_global_datetime(*parsed.value()),
+			"2011-11-18T14:54:39.5Z"
+		);
+		assert_eq!(
+			parsed.leniencies(),
+			[Leniency::LowercaseDesignator, Leniency::DecimalComma]
+		);
+	}
+
+	#[test]
+	fn test_parse_lenient_fails() {
+		let options = ParseOptions::lenient();
+		assert!(options.parse("2011-13-18", parse_date).is_err());
+		assert!(options.parse("next tuesday", parse_date).is_err());
+		assert!(options.parse("11/18/2011", parse_date).is_err());
+	}
+
+	#[test]
+	fn test_parse_lenient_extreme_year_pivots() {
+		for pivot in [i32::MAX, i32::MIN] {
+			let options = ParseOptions::lenient().two_digit_year_pivot(pivot);
+			assert_eq!(
+				options.parse("11-11-18", parse_date),
+				Err(ParseError::Invalid)
+			);
+		}
+	}
+
+	fn violation<T, F>(options: ParseOptions, s: &str, parse_fn: F) -> Option<Restriction>
+	where
+		F: Fn(&str) -> Option<T>,
+	{
+		match options.parse(s, parse_fn) {
+			Err(ParseError::Violation(restriction)) => Some(restriction),
+			_ => None,
+		}
+	}
+
+	#[test]
+	fn test_restriction_t_separator() {
+		let options = ParseOptions::new().t_separator_only();
+		assert!(options.enforces(Restriction::TSeparator));
+		assert_eq!(
+			violation(options, "2011-11-18 14:54", parse_local_datetime),
+			Some(Restriction::TSeparator)
+		);
+		assert!(options
+			.parse("2011-11-18T14:54", parse_local_datetime)
+			.is_ok());
+	}
+
+	#[test]
+	fn test_restriction_seconds_required() {
+		let options = ParseOptions::new().seconds_required();
+		assert_eq!(
+			violation(options, "14:54", parse_time),
+			Some(Restriction::SecondsRequired)
+		);
+		assert!(options.parse("14:54:00", parse_time).is_ok());
+		assert!(options.parse("2011-11-18", parse_date).is_ok());
+	}
+
+	#[test]
+	fn test_restriction_max_fraction_digits() {
+		let options = ParseOptions::new().max_fraction_digits(3);
+		assert_eq!(
+			violation(options, "2011-11-18T14:54:39.9291Z", parse_global_datetime),
+			Some(Restriction::MaxFractionDigits)
+		);
+		assert!(options.parse("14:54:39.929", parse_time).is_ok());
+	}
+
+	#[test]
+	fn test_restriction_utc_only() {
+		let options = ParseOptions::new().utc_only();
+		assert_eq!(
+			violation(options, "2011-11-18T14:54+00:00", parse_global_datetime),
+			Some(Restriction::UtcOnly)
+		);
+		assert!(options
+			.parse("2011-11-18T14:54Z", parse_global_datetime)
+			.is_ok());
+		let any = |s: &str| parse_datetime_value(s, ParseMode::Attribute);
+		assert!(options.parse("+05:30", any).is_ok());
+	}
+
+	#[test]
+	fn test_restriction_years() {
+		let options = ParseOptions::new().four_digit_years();
+		assert_eq!(
+			violation(options, "12011-11", parse_month),
+			Some(Restriction::FourDigitYears)
+		);
+		assert!(options.parse("2011-W47", parse_week).is_ok());
+
+		let options = ParseOptions::new().year_range(1900..=2100);
+		assert_eq!(
+			violation(options, "1899-12-31", parse_date),
+			Some(Restriction::YearRange)
+		);
+		assert!(options.parse("2100-12-31", parse_date).is_ok());
+		assert!(options
+			.parse("11-18", |s| parse_datetime_value(s, ParseMode::Attribute))
+			.is_ok());
+	}
+
+	#[test]
+	fn test_restrictions_only_apply_to_their_components() {
+		// Durations have a `T` and may end without seconds, but have no time component
+		let options = ParseOptions::new().seconds_required().t_separator_only();
+		assert!(options.parse("PT4H", parse_duration).is_ok());
+		assert!(options.parse("P1DT4H30M", parse_duration).is_ok());
+
+		// The hour and the offset hour are not years
+		let options = ParseOptions::new()
+			.four_digit_years()
+			.year_range(1900..=2100);
+		assert!(options.parse("14:54:39", parse_time).is_ok());
+		assert!(options.parse("-08:00", parse_timezone_offset).is_ok());
+		assert_eq!(
+			violation(options, "2011-11-18T14:54-08:00", parse_global_datetime),
+			None
+		);
+	}
+
+	#[test]
+	fn test_parse_with_functions_enforce_restrictions() {
+		let options = ParseOptions::new().seconds_required().utc_only();
+		assert!(parse_time_with("14:54:39", &options).is_ok());
+		assert_eq!(
+			parse_time_with("14:54", &options),
+			Err(ParseError::Violation(Restriction::SecondsRequired))
+		);
+		assert_eq!(
+			parse_global_datetime_with("2011-11-18T14:54:39.929+00:00", &options),
+			Err(ParseError::Violation(Restriction::UtcOnly))
+		);
+		assert_eq!(
+			parse_local_datetime_with("2011-11-18 14:54:39", &options)
+				.map(|parsed| parsed.is_strict()),
+			Ok(true)
+		);
+		assert_eq!(
+			parse_date_with("2011-02-30", &options),
+			Err(ParseError::Invalid)
+		);
+	}
+
+	#[test]
+	fn test_parse_with_functions_for_modes_and_durations() {
+		let options = ParseOptions::lenient();
+		let parsed =
+			parse_datetime_value_with(" 2011/11/18 ", ParseMode::Content, &options)
+				.unwrap();
+		assert_eq!(parsed.leniencies(), [Leniency::DateSeparator]);
+
+		let parsed =
+			parse_datetime_value_with(" 2011/11/18 ", ParseMode::Attribute, &options)
+				.unwrap();
+		assert_eq!(
+			parsed.leniencies(),
+			[Leniency::SurroundingWhitespace, Leniency::DateSeparator]
+		);
+		assert_eq!(
+			parse_datetime_value_with(
+				" 2011-11-18 ",
+				ParseMode::Attribute,
+				&ParseOptions::new()
+			),
+			Err(ParseError::Invalid)
+		);
+
+		let options = ParseOptions::new().seconds_required();
+		assert_eq!(
+			parse_date_or_time_with(
+				"\t2011-11-18 14:54Z\n",
+				ParseMode::Content,
+				&options
+			),
+			Err(ParseError::Violation(Restriction::SecondsRequired))
+		);
+		assert!(
+			parse_date_or_time_with("2011-11-18", ParseMode::Attribute, &options)
+				.is_ok()
+		);
+
+		assert!(parse_duration_with("4h 18m 3s", &ParseOptions::new())
+			.unwrap()
+			.is_strict());
+		assert_eq!(
+			parse_duration_with(" PT4H ", &ParseOptions::new()),
+			Err(ParseError::Invalid)
+		);
+	}
+
+	#[test]
+	fn test_restrictions_apply_after_leniencies() {
+		let options = ParseOptions::lenient().year_range(2000..=2099);
+		assert!(options.parse("11/11/18", parse_date).is_ok());
+		assert_eq!(
+			violation(options.two_digit_year_pivot(1900), "11/11/18", parse_date),
+			Some(Restriction::YearRange)
+		);
+	}
+
+	#[test]
+	fn test_parse_error_from_value_types_works_with_question_mark() {
+		fn parse(
+			month: &str,
+			offset: &str,
+		) -> Result<(crate::YearMonth, crate::TimeZoneOffset), Box<dyn Error>> {
+			Ok((month.parse()?, offset.parse()?))
+		}
+
+		assert!(parse("2011-11", "+05:30").is_ok());
+		let error = parse("2011-13", "+05:30").unwrap_err();
+		assert_eq!(error.downcast_ref(), Some(&ParseError::Invalid));
+		let error = parse("2011-11", "+24:00").unwrap_err();
+		assert_eq!(error.to_string(), "not a valid datetime string");
+	}
+}
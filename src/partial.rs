@@ -0,0 +1,488 @@
+use crate::syntax::TokenKind;
+use crate::tokens::{TOKEN_ABBR_WEEK, TOKEN_COLON, TOKEN_DOT, TOKEN_HYPHEN, TOKEN_SPACE, TOKEN_T};
+use crate::utils::{is_valid_year, max_days_in_month_year, week_number_of_year};
+use crate::InputType;
+use chrono::{Datelike, NaiveDate};
+
+/// A class of characters that may come next in a partially typed value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharClass {
+	/// An ASCII digit from `min` to `max`, inclusive.
+	Digit { min: u8, max: u8 },
+	/// Exactly this character.
+	Char(char),
+}
+
+impl CharClass {
+	/// Whether a character belongs to this class.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_datetime::CharClass;
+	///
+	/// assert!(CharClass::Digit { min: 0, max: 2 }.matches('1'));
+	/// assert!(!CharClass::Digit { min: 0, max: 2 }.matches('3'));
+	/// assert!(CharClass::Char('T').matches('T'));
+	/// ```
+	pub fn matches(&self, c: char) -> bool {
+		match *self {
+			CharClass::Digit { min, max } => c
+				.to_digit(10)
+				.map_or(false, |digit| (min as u32..=max as u32).contains(&digit)),
+			CharClass::Char(expected) => c == expected,
+		}
+	}
+}
+
+/// The state of a partially typed value of a date and time related `<input>`
+/// element, as returned by [`parse_partial`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PartialState {
+	pub(crate) component: TokenKind,
+	pub(crate) next: Vec<CharClass>,
+	pub(crate) is_complete: bool,
+	pub(crate) error: Option<usize>,
+}
+
+impl PartialState {
+	/// The field being typed: the field of the last character, or the
+	/// next field if the last character was a separator.
+	///
+	/// If there is an error, this is the field the error was found in.
+	#[inline]
+	pub const fn component(&self) -> TokenKind {
+		self.component
+	}
+
+	/// The classes of characters that may come next without making
+	/// the value invalid. If there is an error, these are the characters
+	/// that were expected instead of the erroneous one.
+	#[inline]
+	pub fn next(&self) -> &[CharClass] {
+		&self.next
+	}
+
+	/// Whether the string is already a complete, valid value.
+	#[inline]
+	pub const fn is_complete(&self) -> bool {
+		self.is_complete
+	}
+
+	/// The byte position of the first character that can not
+	/// be part of a valid value, if there is one.
+	#[inline]
+	pub const fn error(&self) -> Option<usize> {
+		self.error
+	}
+
+	/// Whether the string can still become a valid value.
+	#[inline]
+	pub const fn is_viable(&self) -> bool {
+		self.error.is_none()
+	}
+}
+
+/// Parse a partially typed value of a date and time related `<input>` element
+///
+/// This reports which field is being typed, which characters may come next,
+/// and the position of the earliest error, if any. Fields are checked
+/// against their ranges as soon as possible, including the number of days
+/// in the month and the number of weeks in the year. A year is out of range
+/// as soon as it is later than the last year that a [`NaiveDate`] can represent.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::syntax::TokenKind;
+/// use whatwg_datetime::{parse_partial, CharClass, InputType};
+///
+/// let state = parse_partial(InputType::Date, "2011-1");
+/// assert_eq!(state.component(), TokenKind::Month);
+/// assert_eq!(state.next(), [CharClass::Digit { min: 0, max: 2 }]);
+/// assert!(state.is_viable());
+/// assert!(!state.is_complete());
+///
+/// let state = parse_partial(InputType::Date, "2011-02-3");
+/// assert_eq!(state.component(), TokenKind::Day);
+/// assert_eq!(state.error(), Some(8));
+/// ```
+pub fn parse_partial(input_type: InputType, s: &str) -> PartialState {
+	let steps = steps_of(input_type);
+	let mut cursor = Cursor {
+		steps,
+		step: 0,
+		digits: String::new(),
+		year: None,
+		month: None,
+		component: first_field(steps, 0),
+	};
+
+	let mut error = None;
+	for (position, c) in s.char_indices() {
+		if !cursor.advance(c) {
+			error = Some(position);
+			break;
+		}
+	}
+
+	PartialState {
+		component: cursor.component,
+		next: cursor.next(),
+		is_complete: error.is_none() && input_type.parse(s).is_some(),
+		error,
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+	/// A field of exactly two digits.
+	Field(TokenKind),
+	/// A field of at least this many digits.
+	Digits(TokenKind, usize),
+	/// One of these separators.
+	Separator(&'static [char]),
+	/// The value may end before the next step, but only
+	/// if it does not start it.
+	Optional,
+}
+
+const DATE: &[Step] = &[
+	Step::Digits(TokenKind::Year, 4),
+	Step::Separator(&[TOKEN_HYPHEN]),
+	Step::Field(TokenKind::Month),
+	Step::Separator(&[TOKEN_HYPHEN]),
+	Step::Field(TokenKind::Day),
+];
+
+const MONTH: &[Step] = &[
+	Step::Digits(TokenKind::Year, 4),
+	Step::Separator(&[TOKEN_HYPHEN]),
+	Step::Field(TokenKind::Month),
+];
+
+const WEEK: &[Step] = &[
+	Step::Digits(TokenKind::Year, 4),
+	Step::Separator(&[TOKEN_HYPHEN]),
+	Step::Separator(&[TOKEN_ABBR_WEEK]),
+	Step::Field(TokenKind::Week),
+];
+
+const TIME: &[Step] = &[
+	Step::Field(TokenKind::Hour),
+	Step::Separator(&[TOKEN_COLON]),
+	Step::Field(TokenKind::Minute),
+	Step::Optional,
+	Step::Separator(&[TOKEN_COLON]),
+	Step::Field(TokenKind::Second),
+	Step::Optional,
+	Step::Separator(&[TOKEN_DOT]),
+	Step::Digits(TokenKind::Fraction, 1),
+];
+
+const DATETIME_LOCAL: &[Step] = &[
+	Step::Digits(TokenKind::Year, 4),
+	Step::Separator(&[TOKEN_HYPHEN]),
+	Step::Field(TokenKind::Month),
+	Step::Separator(&[TOKEN_HYPHEN]),
+	Step::Field(TokenKind::Day),
+	Step::Separator(&[TOKEN_T, TOKEN_SPACE]),
+	Step::Field(TokenKind::Hour),
+	Step::Separator(&[TOKEN_COLON]),
+	Step::Field(TokenKind::Minute),
+	Step::Optional,
+	Step::Separator(&[TOKEN_COLON]),
+	Step::Field(TokenKind::Second),
+	Step::Optional,
+	Step::Separator(&[TOKEN_DOT]),
+	Step::Digits(TokenKind::Fraction, 1),
+];
+
+const fn steps_of(input_type: InputType) -> &'static [Step] {
+	match input_type {
+		InputType::Date => DATE,
+		InputType::Month => MONTH,
+		InputType::Week => WEEK,
+		InputType::Time => TIME,
+		InputType::DatetimeLocal => DATETIME_LOCAL,
+	}
+}
+
+/// The kind of the first field at or after a step.
+fn first_field(steps: &[Step], from: usize) -> TokenKind {
+	steps[from..]
+		.iter()
+		.find_map(|step| match *step {
+			Step::Field(kind) | Step::Digits(kind, _) => Some(kind),
+			_ => None,
+		})
+		.unwrap_or(TokenKind::Unknown)
+}
+
+struct Cursor {
+	steps: &'static [Step],
+	step: usize,
+	/// The digits typed so far in the current field
+	digits: String,
+	year: Option<i32>,
+	month: Option<u32>,
+	component: TokenKind,
+}
+
+impl Cursor {
+	/// Consumes a character, returning whether it was allowed.
+	fn advance(&mut self, c: char) -> bool {
+		if !self.next().iter().any(|class| class.matches(c)) {
+			return false;
+		}
+
+		match self.steps.get(self.step) {
+			Some(Step::Field(kind)) | Some(Step::Digits(kind, _))
+				if c.is_ascii_digit() =>
+			{
+				self.component = *kind;
+				self.digits.push(c);
+				if matches!(self.steps[self.step], Step::Field(_))
+					&& self.digits.len() == 2
+				{
+					self.finish_field();
+				}
+			}
+			_ => {
+				// A separator, which also finishes a field of a variable number of digits
+				if let Some(Step::Digits(..)) = self.steps.get(self.step) {
+					self.finish_field();
+				}
+				while self.steps.get(self.step) == Some(&Step::Optional) {
+					self.step += 1;
+				}
+				self.step += 1;
+				self.component = first_field(self.steps, self.step);
+			}
+		}
+
+		true
+	}
+
+	fn finish_field(&mut self) {
+		let value = self.digits.parse::<i64>().ok();
+		match self.steps[self.step] {
+			Step::Digits(TokenKind::Year, _) => {
+				self.year = value.and_then(|year| i32::try_from(year).ok())
+			}
+			Step::Field(TokenKind::Month) => {
+				self.month = value.and_then(|month| u32::try_from(month).ok())
+			}
+			_ => (),
+		}
+		self.digits.clear();
+		self.step += 1;
+	}
+
+	/// The classes of characters allowed next.
+	fn next(&self) -> Vec<CharClass> {
+		let mut next = Vec::new();
+		let mut step = self.step;
+		while let Some(current) = self.steps.get(step) {
+			match *current {
+				Step::Field(kind) => {
+					let (min, max) = self.range_of(kind);
+					let typed = self.digits.parse::<u32>().ok();
+					let (min, max) = match typed {
+						None => (min / 10, max / 10),
+						Some(tens) => (
+							min.saturating_sub(tens * 10),
+							max.saturating_sub(tens * 10).min(9),
+						),
+					};
+					next.push(CharClass::Digit {
+						min: min as u8,
+						max: max as u8,
+					});
+					return next;
+				}
+				Step::Digits(TokenKind::Year, min_len) => {
+					// The year can never be made valid again once it is out of
+					// range, so digits are only allowed while it is in range.
+					let typed = self.digits.parse::<i64>().unwrap_or(0);
+					let max_digit = (i64::from(NaiveDate::MAX.year())
+						- typed * 10)
+						.min(9);
+					if max_digit >= 0 {
+						next.push(CharClass::Digit {
+							min: 0,
+							max: max_digit as u8,
+						});
+					}
+					let is_valid =
+						i32::try_from(typed).map_or(false, is_valid_year);
+					if self.digits.len() < min_len || !is_valid {
+						return next;
+					}
+					step += 1;
+				}
+				Step::Digits(_, min_len) => {
+					next.push(CharClass::Digit { min: 0, max: 9 });
+					if self.digits.len() < min_len {
+						return next;
+					}
+					step += 1;
+				}
+				Step::Separator(chars) => {
+					next.extend(chars.iter().map(|c| CharClass::Char(*c)));
+					return next;
+				}
+				Step::Optional => step += 1,
+			}
+		}
+
+		next
+	}
+
+	/// The range of values of a two-digit field.
+	fn range_of(&self, kind: TokenKind) -> (u32, u32) {
+		match kind {
+			TokenKind::Month => (1, 12),
+			TokenKind::Day => {
+				let max_day = match (self.year, self.month) {
+					(Some(year), Some(month)) => {
+						max_days_in_month_year(month, year as u32)
+					}
+					_ => None,
+				};
+				(1, max_day.unwrap_or(31))
+			}
+			TokenKind::Week => {
+				let max_week = self.year.and_then(week_number_of_year);
+				(1, max_week.unwrap_or(53))
+			}
+			TokenKind::Hour => (0, 23),
+			_ => (0, 59),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_partial, CharClass};
+	use crate::syntax::TokenKind;
+	use crate::InputType;
+
+	const ANY_DIGIT: CharClass = CharClass::Digit { min: 0, max: 9 };
+
+	#[test]
+	fn test_parse_partial_empty() {
+		let state = parse_partial(InputType::Date, "");
+		assert_eq!(state.component(), TokenKind::Year);
+		assert_eq!(state.next(), [ANY_DIGIT]);
+		assert!(state.is_viable());
+
+		let state = parse_partial(InputType::Time, "");
+		assert_eq!(state.component(), TokenKind::Hour);
+		assert_eq!(state.next(), [CharClass::Digit { min: 0, max: 2 }]);
+	}
+
+	#[test]
+	fn test_parse_partial_year() {
+		assert_eq!(parse_partial(InputType::Month, "201").next(), [ANY_DIGIT]);
+		assert_eq!(
+			parse_partial(InputType::Month, "2011").next(),
+			[ANY_DIGIT, CharClass::Char('-')]
+		);
+		assert_eq!(parse_partial(InputType::Month, "0000").next(), [ANY_DIGIT]);
+		assert_eq!(parse_partial(InputType::Month, "0000-").error(), Some(4));
+	}
+
+	#[test]
+	fn test_parse_partial_year_out_of_range() {
+		assert_eq!(
+			parse_partial(InputType::Week, "26214").next(),
+			[CharClass::Digit { min: 0, max: 2 }, CharClass::Char('-')]
+		);
+		assert_eq!(parse_partial(InputType::Week, "262142-W").error(), None);
+		assert_eq!(parse_partial(InputType::Week, "262143-W").error(), Some(5));
+		assert_eq!(parse_partial(InputType::Week, "999999-W").error(), Some(5));
+		assert_eq!(
+			parse_partial(InputType::Week, "4294967297-W").error(),
+			Some(5)
+		);
+		assert_eq!(parse_partial(InputType::Date, "2621420").error(), Some(6));
+	}
+
+	#[test]
+	fn test_parse_partial_separator_moves_to_next_field() {
+		let state = parse_partial(InputType::Date, "2011-");
+		assert_eq!(state.component(), TokenKind::Month);
+		assert_eq!(state.next(), [CharClass::Digit { min: 0, max: 1 }]);
+
+		let state = parse_partial(InputType::Date, "2011-11");
+		assert_eq!(state.component(), TokenKind::Month);
+		assert_eq!(state.next(), [CharClass::Char('-')]);
+	}
+
+	#[test]
+	fn test_parse_partial_day_of_month() {
+		assert_eq!(
+			parse_partial(InputType::Date, "2011-02-2").next(),
+			[CharClass::Digit { min: 0, max: 8 }]
+		);
+		assert_eq!(
+			parse_partial(InputType::Date, "2012-02-2").next(),
+			[CharClass::Digit { min: 0, max: 9 }]
+		);
+		assert_eq!(
+			parse_partial(InputType::Date, "2011-02-0").next(),
+			[CharClass::Digit { min: 1, max: 9 }]
+		);
+	}
+
+	#[test]
+	fn test_parse_partial_week() {
+		let state = parse_partial(InputType::Week, "2011-");
+		assert_eq!(state.component(), TokenKind::Week);
+		assert_eq!(state.next(), [CharClass::Char('W')]);
+		assert_eq!(
+			parse_partial(InputType::Week, "2011-W5").next(),
+			[CharClass::Digit { min: 0, max: 2 }]
+		);
+		assert_eq!(parse_partial(InputType::Week, "2011-W53").error(), Some(7));
+		assert_eq!(parse_partial(InputType::Week, "2011-w").error(), Some(5));
+	}
+
+	#[test]
+	fn test_parse_partial_time_optional_parts() {
+		let state = parse_partial(InputType::Time, "14:54");
+		assert!(state.is_complete());
+		assert_eq!(state.next(), [CharClass::Char(':')]);
+
+		let state = parse_partial(InputType::Time, "14:54:");
+		assert!(!state.is_complete());
+		assert_eq!(state.component(), TokenKind::Second);
+
+		let state = parse_partial(InputType::Time, "14:54:39.9");
+		assert!(state.is_complete());
+		assert_eq!(state.component(), TokenKind::Fraction);
+		assert_eq!(state.next(), [ANY_DIGIT]);
+	}
+
+	#[test]
+	fn test_parse_partial_datetime_local() {
+		let state = parse_partial(InputType::DatetimeLocal, "2011-11-18");
+		assert_eq!(state.next(), [CharClass::Char('T'), CharClass::Char(' ')]);
+
+		let state = parse_partial(InputType::DatetimeLocal, "2011-11-18 2");
+		assert_eq!(state.component(), TokenKind::Hour);
+		assert_eq!(state.next(), [CharClass::Digit { min: 0, max: 3 }]);
+	}
+
+	#[test]
+	fn test_parse_partial_earliest_error() {
+		let state = parse_partial(InputType::Date, "2011-13-45");
+		assert_eq!(state.error(), Some(6));
+		assert_eq!(state.component(), TokenKind::Month);
+		assert_eq!(state.next(), [CharClass::Digit { min: 0, max: 2 }]);
+		assert!(!state.is_viable());
+		assert!(!state.is_complete());
+
+		assert_eq!(parse_partial(InputType::Time, "14:54Z").error(), Some(5));
+		assert_eq!(parse_partial(InputType::Month, "2011-11-").error(), Some(7));
+	}
+}
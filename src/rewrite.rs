@@ -0,0 +1,538 @@
+//! Normalizing the datetime attributes of HTML documents while streaming them.
+//!
+//! This module is only available with the `rewrite` feature enabled.
+
+use crate::tokens::{TOKEN_SPACE, TOKEN_T};
+use crate::{
+	parse_date_component, parse_date_or_time, parse_datetime_value, parse_time_component,
+	parse_timezone_offset_component, serialize_local_datetime, serialize_timezone_offset,
+	DateOrTime, InputType, ParseMode, WhatwgDateTime,
+};
+use chrono::NaiveDateTime;
+use lol_html::errors::{RewritingError, SelectorError};
+use lol_html::html_content::Element;
+use lol_html::{ElementContentHandlers, HtmlRewriter, OutputSink, Selector, Settings};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// The microsyntax an attribute value is parsed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeSyntax {
+	/// Any of the datetime microsyntaxes, like the
+	/// [`datetime` attribute][whatwg-html-time-datetime] of a `<time>` element.
+	///
+	/// [whatwg-html-time-datetime]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#attr-time-datetime
+	DateTimeValue,
+	/// A date or a global datetime, like the
+	/// [`datetime` attribute][whatwg-html-mod-datetime] of an `<ins>` or `<del>` element.
+	///
+	/// [whatwg-html-mod-datetime]: https://html.spec.whatwg.org/multipage/edits.html#attr-mod-datetime
+	DateOrGlobalDateTime,
+	/// The value of an `<input>` element, parsed according to its `type` attribute.
+	/// Elements that are not date and time related, and empty values, are left untouched.
+	InputValue,
+}
+
+/// What to do with an attribute whose value can not be parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InvalidValueAction {
+	/// Leave the attribute as is, and only report it.
+	#[default]
+	Report,
+	/// Remove the attribute from the element, and report it.
+	Strip,
+}
+
+#[derive(Debug, Clone)]
+struct RewriteRule {
+	selector: Selector,
+	attribute: String,
+	syntax: AttributeSyntax,
+}
+
+/// Which attributes a [`DateTimeRewriter`] normalizes, and how.
+///
+/// The default options, also returned by [`RewriteOptions::new`], normalize
+/// the `datetime` attribute of `<time>`, `<ins>`, and `<del>` elements, and
+/// the `value` attribute of date and time related `<input>` elements.
+/// Start from [`RewriteOptions::empty`] to normalize only the attributes
+/// of your own rules.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::rewrite::{AttributeSyntax, InvalidValueAction, RewriteOptions};
+///
+/// let options = RewriteOptions::empty()
+///     .rule("[data-published]", "data-published", AttributeSyntax::DateOrGlobalDateTime)
+///     .unwrap()
+///     .on_invalid(InvalidValueAction::Strip);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RewriteOptions {
+	rules: Vec<RewriteRule>,
+	on_invalid: InvalidValueAction,
+}
+
+impl RewriteOptions {
+	/// Creates the default options, with the built-in rules.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates options without any rules.
+	pub fn empty() -> Self {
+		Self {
+			rules: Vec::new(),
+			on_invalid: InvalidValueAction::default(),
+		}
+	}
+
+	/// Adds a rule normalizing `attribute` of the elements matching
+	/// the CSS `selector`, using the given microsyntax.
+	///
+	/// This returns an error if the selector is invalid or unsupported.
+	pub fn rule(
+		mut self,
+		selector: &str,
+		attribute: &str,
+		syntax: AttributeSyntax,
+	) -> Result<Self, SelectorError> {
+		self.rules.push(RewriteRule {
+			selector: selector.parse()?,
+			attribute: attribute.to_owned(),
+			syntax,
+		});
+
+		Ok(self)
+	}
+
+	/// Sets what to do with attributes whose value can not be parsed.
+	pub fn on_invalid(mut self, action: InvalidValueAction) -> Self {
+		self.on_invalid = action;
+		self
+	}
+}
+
+impl Default for RewriteOptions {
+	fn default() -> Self {
+		// The selectors are static, so they are known to be valid
+		RewriteOptions::empty()
+			.rule("time[datetime]", "datetime", AttributeSyntax::DateTimeValue)
+			.and_then(|options| {
+				options.rule(
+					"ins[datetime], del[datetime]",
+					"datetime",
+					AttributeSyntax::DateOrGlobalDateTime,
+				)
+			})
+			.and_then(|options| {
+				options.rule("input[value]", "value", AttributeSyntax::InputValue)
+			})
+			.unwrap()
+	}
+}
+
+/// An attribute whose value could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidValue {
+	pub(crate) tag_name: String,
+	pub(crate) attribute: String,
+	pub(crate) value: String,
+	pub(crate) location: Range<usize>,
+}
+
+impl InvalidValue {
+	/// The lowercased tag name of the element.
+	#[inline]
+	pub fn tag_name(&self) -> &str {
+		&self.tag_name
+	}
+
+	/// The name of the attribute.
+	#[inline]
+	pub fn attribute(&self) -> &str {
+		&self.attribute
+	}
+
+	/// The invalid value of the attribute.
+	#[inline]
+	pub fn value(&self) -> &str {
+		&self.value
+	}
+
+	/// The byte range of the start tag of the element in the input.
+	#[inline]
+	pub fn location(&self) -> Range<usize> {
+		self.location.clone()
+	}
+}
+
+/// A streaming HTML rewriter that replaces datetime attribute values
+/// with their normalized serialization, such as `2011-11-18 14:54`
+/// with `2011-11-18T14:54`, or `+0530` with `+05:30`.
+///
+/// Global datetimes keep the time-zone offset they were written with,
+/// including the `-00:00` designator for an unknown local offset, so
+/// `2011-11-18 14:54-0800` becomes `2011-11-18T14:54-08:00` rather than
+/// being converted to UTC.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::rewrite::{DateTimeRewriter, RewriteOptions};
+///
+/// let mut output = Vec::new();
+/// let mut rewriter = DateTimeRewriter::new(&RewriteOptions::default(), |chunk: &[u8]| {
+///     output.extend_from_slice(chunk)
+/// });
+///
+/// rewriter.write(b"<time datetime='2011-11-18 14:").unwrap();
+/// rewriter.write(b"54'>Friday</time>").unwrap();
+/// let invalid_values = rewriter.end().unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     r#"<time datetime="2011-11-18T14:54">Friday</time>"#
+/// );
+/// assert!(invalid_values.is_empty());
+/// ```
+pub struct DateTimeRewriter<'h, O: OutputSink> {
+	rewriter: HtmlRewriter<'h, O>,
+	invalid_values: Rc<RefCell<Vec<InvalidValue>>>,
+}
+
+impl<'h, O: OutputSink> DateTimeRewriter<'h, O> {
+	/// Creates a rewriter that writes its output to `output_sink`.
+	pub fn new(options: &RewriteOptions, output_sink: O) -> Self {
+		let invalid_values = Rc::new(RefCell::new(Vec::new()));
+		let on_invalid = options.on_invalid;
+
+		let element_content_handlers = options
+			.rules
+			.iter()
+			.map(|rule| {
+				let attribute = rule.attribute.clone();
+				let syntax = rule.syntax;
+				let invalid_values = Rc::clone(&invalid_values);
+				let handler = move |element: &mut Element<'_, '_>| {
+					if let Some(invalid_value) =
+						normalize_attribute(element, &attribute, syntax)
+					{
+						if on_invalid == InvalidValueAction::Strip {
+							element.remove_attribute(&attribute);
+						}
+						invalid_values.borrow_mut().push(invalid_value);
+					}
+					Ok(())
+				};
+
+				(
+					Cow::Borrowed(&rule.selector),
+					ElementContentHandlers::default().element(handler),
+				)
+			})
+			.collect();
+
+		let settings = Settings {
+			element_content_handlers,
+			..Settings::new()
+		};
+
+		Self {
+			rewriter: HtmlRewriter::new(settings, output_sink),
+			invalid_values,
+		}
+	}
+
+	/// Writes a chunk of the input document.
+	#[inline]
+	pub fn write(&mut self, chunk: &[u8]) -> Result<(), RewritingError> {
+		self.rewriter.write(chunk)
+	}
+
+	/// Finishes rewriting the document, returning every
+	/// attribute whose value could not be parsed.
+	pub fn end(self) -> Result<Vec<InvalidValue>, RewritingError> {
+		self.rewriter.end()?;
+		Ok(self.invalid_values.take())
+	}
+}
+
+/// Rewrites a whole document at once with a [`DateTimeRewriter`],
+/// returning the rewritten document along with every attribute
+/// whose value could not be parsed.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::rewrite::{rewrite_datetime_attributes, InvalidValueAction, RewriteOptions};
+///
+/// let options = RewriteOptions::default().on_invalid(InvalidValueAction::Strip);
+/// let (html, invalid_values) =
+///     rewrite_datetime_attributes(r#"<del datetime="tomorrow">old</del>"#, &options).unwrap();
+///
+/// assert_eq!(html, "<del>old</del>");
+/// assert_eq!(invalid_values[0].value(), "tomorrow");
+/// ```
+pub fn rewrite_datetime_attributes(
+	html: &str,
+	options: &RewriteOptions,
+) -> Result<(String, Vec<InvalidValue>), RewritingError> {
+	let mut output = Vec::with_capacity(html.len());
+	let mut rewriter =
+		DateTimeRewriter::new(options, |chunk: &[u8]| output.extend_from_slice(chunk));
+	rewriter.write(html.as_bytes())?;
+	let invalid_values = rewriter.end()?;
+
+	// The input is UTF-8, and only UTF-8 strings are written back into it
+	Ok((String::from_utf8(output).unwrap(), invalid_values))
+}
+
+/// Replaces the value of an attribute with its normalized serialization,
+/// returning the attribute if its value is invalid.
+fn normalize_attribute(
+	element: &mut Element<'_, '_>,
+	attribute: &str,
+	syntax: AttributeSyntax,
+) -> Option<InvalidValue> {
+	let value = element.get_attribute(attribute)?;
+	let normalized = match syntax {
+		AttributeSyntax::DateTimeValue => {
+			parse_datetime_value(&value, ParseMode::Attribute)
+				.map(|parsed| serialize_datetime_value(parsed, &value))
+		}
+		AttributeSyntax::DateOrGlobalDateTime => {
+			match parse_date_or_time(&value, ParseMode::Attribute) {
+				Some(DateOrTime::Time(_)) | None => None,
+				Some(parsed) => Some(serialize_datetime_value(
+					WhatwgDateTime::from(parsed),
+					&value,
+				)),
+			}
+		}
+		AttributeSyntax::InputValue => {
+			let input_type = element
+				.get_attribute("type")
+				.and_then(|s| s.parse::<InputType>().ok())?;
+			// An empty value is what value sanitization leaves of an invalid
+			// value, and the normal state of an input that is not filled in
+			if value.is_empty() {
+				return None;
+			}
+			input_type.parse(&value).map(|parsed| parsed.serialize())
+		}
+	};
+
+	match normalized {
+		Some(normalized) => {
+			if normalized != value {
+				// The attribute name comes from the element, so it is valid
+				element.set_attribute(attribute, &normalized).ok();
+			}
+			None
+		}
+		None => Some(InvalidValue {
+			tag_name: element.tag_name(),
+			attribute: attribute.to_owned(),
+			value,
+			location: element.source_location().bytes(),
+		}),
+	}
+}
+
+/// Serializes a value parsed from `s`, keeping the time-zone offset
+/// of global datetimes instead of converting them to UTC.
+fn serialize_datetime_value(parsed: WhatwgDateTime, s: &str) -> String {
+	match parsed {
+		// The value was parsed from `s` as a global datetime, so it can be read again
+		WhatwgDateTime::GlobalDateTime(_) => {
+			serialize_global_datetime_with_offset(s).unwrap()
+		}
+		parsed => parsed.serialize(),
+	}
+}
+
+/// Serializes a global datetime string as its local date and time
+/// followed by the time-zone offset it was written with.
+fn serialize_global_datetime_with_offset(s: &str) -> Option<String> {
+	let mut position = 0usize;
+	let date = parse_date_component(s, &mut position)?;
+	if !matches!(s.chars().nth(position), Some(TOKEN_T) | Some(TOKEN_SPACE)) {
+		return None;
+	}
+	position += 1;
+
+	let time = parse_time_component(s, &mut position)?;
+	let timezone_offset = parse_timezone_offset_component(s, &mut position)?;
+
+	Some(format!(
+		"{}{}",
+		serialize_local_datetime(NaiveDateTime::new(date, time)),
+		serialize_timezone_offset(timezone_offset)
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		rewrite_datetime_attributes, AttributeSyntax, DateTimeRewriter, InvalidValueAction,
+		RewriteOptions,
+	};
+
+	fn rewrite(html: &str) -> String {
+		rewrite_datetime_attributes(html, &RewriteOptions::default())
+			.unwrap()
+			.0
+	}
+
+	#[test]
+	fn test_rewrite_time_datetime() {
+		assert_eq!(
+			rewrite(r#"<time datetime="2011-11-18 14:54">x</time>"#),
+			r#"<time datetime="2011-11-18T14:54">x</time>"#
+		);
+		assert_eq!(
+			rewrite(r#"<time datetime="+0530">x</time>"#),
+			r#"<time datetime="+05:30">x</time>"#
+		);
+		assert_eq!(
+			rewrite(r#"<time datetime="14:54:00.000">x</time>"#),
+			r#"<time datetime="14:54">x</time>"#
+		);
+	}
+
+	#[test]
+	fn test_rewrite_leaves_normalized_values_untouched() {
+		let html = r#"<time datetime='2011-11-18'>x</time>"#;
+		assert_eq!(rewrite(html), html);
+	}
+
+	#[test]
+	fn test_rewrite_ins_del_global_datetime_keeps_offset() {
+		assert_eq!(
+			rewrite(r#"<ins datetime="2011-11-18 14:54:00-0800">x</ins>"#),
+			r#"<ins datetime="2011-11-18T14:54-08:00">x</ins>"#
+		);
+		assert_eq!(
+			rewrite(r#"<del datetime="2011-11-18T14:54Z">x</del>"#),
+			r#"<del datetime="2011-11-18T14:54Z">x</del>"#
+		);
+		assert_eq!(
+			rewrite(r#"<time datetime="2011-11-18T23:30+05:30">x</time>"#),
+			r#"<time datetime="2011-11-18T23:30+05:30">x</time>"#
+		);
+	}
+
+	#[test]
+	fn test_rewrite_keeps_unknown_local_offset() {
+		assert_eq!(
+			rewrite(r#"<ins datetime="2011-11-18T14:54-0000">x</ins>"#),
+			r#"<ins datetime="2011-11-18T14:54-00:00">x</ins>"#
+		);
+		assert_eq!(
+			rewrite(r#"<time datetime="2011-11-18 14:54+0000">x</time>"#),
+			r#"<time datetime="2011-11-18T14:54+00:00">x</time>"#
+		);
+	}
+
+	#[test]
+	fn test_rewrite_input_value() {
+		assert_eq!(
+			rewrite(r#"<input type="datetime-local" value="2011-11-18 14:54:00">"#),
+			r#"<input type="datetime-local" value="2011-11-18T14:54">"#
+		);
+		let html = r#"<input type="text" value="2011-11-18 14:54:00">"#;
+		assert_eq!(rewrite(html), html);
+	}
+
+	#[test]
+	fn test_rewrite_leaves_empty_input_values_untouched() {
+		let options = RewriteOptions::default().on_invalid(InvalidValueAction::Strip);
+		let html = r#"<input type="date" value=""><input type=time value>"#;
+		let (output, invalid_values) = rewrite_datetime_attributes(html, &options).unwrap();
+
+		assert_eq!(output, html);
+		assert!(invalid_values.is_empty());
+	}
+
+	#[test]
+	fn test_rewrite_reports_invalid_values() {
+		let html = "<p>\n<ins datetime=\"14:54\">x</ins>";
+		let (output, invalid_values) =
+			rewrite_datetime_attributes(html, &RewriteOptions::default()).unwrap();
+
+		assert_eq!(output, html);
+		assert_eq!(invalid_values.len(), 1);
+		assert_eq!(invalid_values[0].tag_name(), "ins");
+		assert_eq!(invalid_values[0].attribute(), "datetime");
+		assert_eq!(invalid_values[0].value(), "14:54");
+		assert_eq!(invalid_values[0].location().start, 4);
+	}
+
+	#[test]
+	fn test_rewrite_strips_invalid_values() {
+		let options = RewriteOptions::default().on_invalid(InvalidValueAction::Strip);
+		let (output, invalid_values) = rewrite_datetime_attributes(
+			r#"<input type="week" value="2011-W54">"#,
+			&options,
+		)
+		.unwrap();
+
+		assert_eq!(output, r#"<input type="week">"#);
+		assert_eq!(invalid_values.len(), 1);
+	}
+
+	#[test]
+	fn test_rewrite_custom_rule() {
+		let options = RewriteOptions::empty()
+			.rule("article", "data-updated", AttributeSyntax::DateTimeValue)
+			.unwrap();
+		let (output, _) = rewrite_datetime_attributes(
+			r#"<article data-updated="2011-11-18 14:54"><time datetime="+0530"></time></article>"#,
+			&options,
+		)
+		.unwrap();
+
+		assert_eq!(
+			output,
+			r#"<article data-updated="2011-11-18T14:54"><time datetime="+0530"></time></article>"#
+		);
+	}
+
+	#[test]
+	fn test_rewrite_invalid_selector() {
+		assert!(RewriteOptions::empty()
+			.rule("time[", "datetime", AttributeSyntax::DateTimeValue)
+			.is_err());
+	}
+
+	#[test]
+	fn test_rewrite_options_new_is_default() {
+		let html = r#"<time datetime="2011-11-18 14:54">x</time>"#;
+		assert_eq!(
+			rewrite_datetime_attributes(html, &RewriteOptions::new()).unwrap(),
+			rewrite_datetime_attributes(html, &RewriteOptions::default()).unwrap()
+		);
+		assert_eq!(
+			rewrite_datetime_attributes(html, &RewriteOptions::empty())
+				.unwrap()
+				.0,
+			html
+		);
+	}
+
+	#[test]
+	fn test_rewriter_streams_chunks() {
+		let mut output = Vec::new();
+		let mut rewriter =
+			DateTimeRewriter::new(&RewriteOptions::default(), |chunk: &[u8]| {
+				output.extend_from_slice(chunk)
+			});
+		for chunk in [&b"<time date"[..], b"time=\"2011-W4", b"7\"></time>"] {
+			rewriter.write(chunk).unwrap();
+		}
+		assert!(rewriter.end().unwrap().is_empty());
+		assert_eq!(output, br#"<time datetime="2011-W47"></time>"#);
+	}
+}
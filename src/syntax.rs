@@ -0,0 +1,576 @@
+//! Spans of the components and tokens of datetime strings, for syntax
+//! highlighting and editor tooling.
+//!
+//! Components are read by the same component parsers as the rest of the
+//! crate, such as [`parse_month_component`] and [`parse_time_component`],
+//! and then split into their tokens.
+
+use crate::tokens::{TOKEN_SPACE, TOKEN_T};
+use crate::{
+	parse_date_component, parse_datetime_value, parse_month_component, parse_time_component,
+	parse_timezone_offset_component, parse_week_component, parse_year_component,
+	parse_yearless_date_component, ParseComponentFn, ParseMode, WhatwgDateTime,
+};
+use std::fmt;
+use std::ops::Range;
+
+/// The kind of a [`Token`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+	Year,
+	Month,
+	Day,
+	Week,
+	Hour,
+	Minute,
+	Second,
+	/// The digits of the fraction of a second
+	Fraction,
+	OffsetHour,
+	OffsetMinute,
+	/// A `-` between the fields of a date, or the `--` prefix of a yearless date
+	Hyphen,
+	/// A `:` between the fields of a time or a time-zone offset
+	Colon,
+	/// The `.` before the fraction of a second
+	DecimalPoint,
+	/// The `W` before the week of a week
+	WeekDesignator,
+	/// The `T` or space between a date and a time
+	DateTimeSeparator,
+	/// The `+` or `-` of a time-zone offset
+	Sign,
+	/// The `Z` of a UTC time-zone offset
+	Utc,
+	/// Text that does not fit the datetime microsyntaxes
+	Unknown,
+}
+
+/// A token of a datetime string, with its byte span in the string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Token<'a> {
+	pub(crate) kind: TokenKind,
+	pub(crate) span: (usize, usize),
+	pub(crate) text: &'a str,
+}
+
+impl<'a> Token<'a> {
+	/// The kind of this token.
+	#[inline]
+	pub const fn kind(&self) -> TokenKind {
+		self.kind
+	}
+
+	/// The byte range of this token in the string.
+	#[inline]
+	pub const fn span(&self) -> Range<usize> {
+		self.span.0..self.span.1
+	}
+
+	/// The text of this token, exactly as written in the string.
+	#[inline]
+	pub const fn text(&self) -> &'a str {
+		self.text
+	}
+}
+
+/// The kind of a [`Component`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+	Year,
+	Month,
+	Date,
+	YearlessDate,
+	Week,
+	Time,
+	TimeZoneOffset,
+	/// The `T` or space between a date and a time
+	DateTimeSeparator,
+	/// Text that does not fit the datetime microsyntaxes
+	Unknown,
+}
+
+/// A component of a datetime string, such as the date of a local datetime,
+/// along with the tokens it is made of.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Component<'a> {
+	pub(crate) kind: ComponentKind,
+	pub(crate) tokens: Vec<Token<'a>>,
+}
+
+impl<'a> Component<'a> {
+	/// The kind of this component.
+	#[inline]
+	pub const fn kind(&self) -> ComponentKind {
+		self.kind
+	}
+
+	/// The tokens of this component.
+	#[inline]
+	pub fn tokens(&self) -> &[Token<'a>] {
+		&self.tokens
+	}
+
+	/// The byte range of this component in the string.
+	pub fn span(&self) -> Range<usize> {
+		match (self.tokens.first(), self.tokens.last()) {
+			(Some(first), Some(last)) => first.span.0..last.span.1,
+			_ => 0..0,
+		}
+	}
+}
+
+/// The concrete syntax tree of a valid datetime string, which round-trips
+/// to the exact string it was parsed from through its [`Display`](fmt::Display)
+/// implementation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxTree<'a> {
+	pub(crate) value: WhatwgDateTime,
+	pub(crate) components: Vec<Component<'a>>,
+}
+
+impl<'a> SyntaxTree<'a> {
+	/// The parsed value.
+	#[inline]
+	pub const fn value(&self) -> WhatwgDateTime {
+		self.value
+	}
+
+	/// The components of the string, in order.
+	#[inline]
+	pub fn components(&self) -> &[Component<'a>] {
+		&self.components
+	}
+
+	/// The tokens of the string, in order.
+	pub fn tokens(&self) -> impl Iterator<Item = &Token<'a>> {
+		self.components
+			.iter()
+			.flat_map(|component| component.tokens.iter())
+	}
+}
+
+impl fmt::Display for SyntaxTree<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for token in self.tokens() {
+			f.write_str(token.text)?;
+		}
+		Ok(())
+	}
+}
+
+/// Split a string into the tokens of the datetime microsyntaxes
+///
+/// This never fails: the longest valid sequence of components at the start
+/// of the string is split into its tokens, and any text after it ends up in
+/// a trailing [`TokenKind::Unknown`] token, so the tokens always cover the
+/// whole string. For values that are still being typed, see
+/// [`parse_partial`](crate::parse_partial).
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::syntax::{tokenize, TokenKind};
+///
+/// let tokens = tokenize("2011-11-18T14:54+0530");
+/// let kinds: Vec<TokenKind> = tokens.iter().map(|token| token.kind()).collect();
+///
+/// assert_eq!(
+///     kinds,
+///     [
+///         TokenKind::Year,
+///         TokenKind::Hyphen,
+///         TokenKind::Month,
+///         TokenKind::Hyphen,
+///         TokenKind::Day,
+///         TokenKind::DateTimeSeparator,
+///         TokenKind::Hour,
+///         TokenKind::Colon,
+///         TokenKind::Minute,
+///         TokenKind::Sign,
+///         TokenKind::OffsetHour,
+///         TokenKind::OffsetMinute,
+///     ]
+/// );
+/// assert_eq!(tokens[11].span(), 19..21);
+///
+/// let tokens = tokenize("2011-11-18 at noon");
+/// assert_eq!(tokens.last().unwrap().kind(), TokenKind::Unknown);
+/// assert_eq!(tokens.last().unwrap().text(), " at noon");
+/// ```
+pub fn tokenize(s: &str) -> Vec<Token<'_>> {
+	scan_components(s)
+		.into_iter()
+		.flat_map(|component| component.tokens)
+		.collect()
+}
+
+/// Parse a [datetime value][whatwg-html-datetime-value] into its concrete syntax tree
+///
+/// This returns `None` if the string is not valid, in which case
+/// [`tokenize`] can still be used to inspect it.
+///
+/// # Examples
+/// ```
+/// use whatwg_datetime::syntax::{parse_spanned, ComponentKind};
+///
+/// let tree = parse_spanned("2011-11-18 14:54:39.929").unwrap();
+/// let kinds: Vec<ComponentKind> = tree.components().iter().map(|c| c.kind()).collect();
+///
+/// assert_eq!(
+///     kinds,
+///     [ComponentKind::Date, ComponentKind::DateTimeSeparator, ComponentKind::Time]
+/// );
+/// assert_eq!(tree.components()[2].span(), 11..23);
+/// assert_eq!(tree.to_string(), "2011-11-18 14:54:39.929");
+/// ```
+///
+/// [whatwg-html-datetime-value]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#concept-time-datetime
+pub fn parse_spanned(s: &str) -> Option<SyntaxTree<'_>> {
+	let value = parse_datetime_value(s, ParseMode::Attribute)?;
+	Some(SyntaxTree {
+		value,
+		components: scan_components(s),
+	})
+}
+
+/// Reads the longest sequence of components at the start of a string that
+/// the component parsers accept, such as [`parse_date_component`] followed
+/// by a `T` and [`parse_time_component`]. Whatever the parsers do not
+/// accept ends up in a trailing [`ComponentKind::Unknown`] component.
+pub(crate) fn scan_components(s: &str) -> Vec<Component<'_>> {
+	// Only ASCII can be part of a datetime string, and within ASCII,
+	// the positions of the component parsers are also byte offsets
+	let ascii_len = s.bytes().take_while(u8::is_ascii).count();
+	let prefix = &s[..ascii_len];
+
+	let mut best: Vec<(ComponentKind, usize)> = Vec::new();
+	let mut keep = |read: &[(ComponentKind, usize)]| {
+		let end = |read: &[(ComponentKind, usize)]| read.last().map_or(0, |(_, end)| *end);
+		if end(read) > end(&best) {
+			best = read.to_vec();
+		}
+	};
+
+	let mut position = 0usize;
+	if parse_date_component(prefix, &mut position).is_some() {
+		let mut read = vec![(ComponentKind::Date, position)];
+		keep(&read);
+
+		if matches!(
+			prefix[position..].chars().next(),
+			Some(TOKEN_T | TOKEN_SPACE)
+		) {
+			position += 1;
+			read.push((ComponentKind::DateTimeSeparator, position));
+			if parse_time_component(prefix, &mut position).is_some() {
+				read.push((ComponentKind::Time, position));
+				keep(&read);
+				if parse_timezone_offset_component(prefix, &mut position).is_some()
+				{
+					read.push((ComponentKind::TimeZoneOffset, position));
+					keep(&read);
+				}
+			}
+		}
+	}
+
+	let mut read_one = |kind: ComponentKind, parse_fn: &ParseComponentFn<()>| {
+		let mut position = 0usize;
+		if parse_fn(prefix, &mut position).is_some() {
+			keep(&[(kind, position)]);
+		}
+	};
+	read_one(ComponentKind::Year, &|s, position| {
+		parse_year_component(s, position).map(drop)
+	});
+	read_one(ComponentKind::Month, &|s, position| {
+		parse_month_component(s, position).map(drop)
+	});
+	read_one(ComponentKind::Week, &|s, position| {
+		parse_week_component(s, position).map(drop)
+	});
+	read_one(ComponentKind::YearlessDate, &|s, position| {
+		parse_yearless_date_component(s, position).map(drop)
+	});
+	read_one(ComponentKind::Time, &|s, position| {
+		parse_time_component(s, position).map(drop)
+	});
+	read_one(ComponentKind::TimeZoneOffset, &|s, position| {
+		parse_timezone_offset_component(s, position).map(drop)
+	});
+
+	let mut components = Vec::new();
+	let mut start = 0usize;
+	for (kind, end) in best {
+		components.push(split_component(s, kind, start, end));
+		start = end;
+	}
+	if start < s.len() {
+		components.push(Component {
+			kind: ComponentKind::Unknown,
+			tokens: vec![token(s, TokenKind::Unknown, start, s.len())],
+		});
+	}
+
+	components
+}
+
+#[inline]
+/// Whether every field of a string, such as the month or the second,
+/// is written with exactly two digits. The component parsers accept seconds
+/// written with a single digit, so parsing alone does not make a string valid.
+pub(crate) fn has_two_digit_fields(s: &str) -> bool {
+	scan_components(s)
+		.iter()
+		.flat_map(|component| component.tokens())
+		.all(|token| !is_field(token.kind()) || token.text().len() == 2)
+}
+
+#[inline]
+fn is_field(kind: TokenKind) -> bool {
+	matches!(
+		kind,
+		TokenKind::Month
+			| TokenKind::Day | TokenKind::Week
+			| TokenKind::Hour | TokenKind::Minute
+			| TokenKind::Second | TokenKind::OffsetHour
+			| TokenKind::OffsetMinute
+	)
+}
+
+fn token(s: &str, kind: TokenKind, start: usize, end: usize) -> Token<'_> {
+	Token {
+		kind,
+		span: (start, end),
+		text: &s[start..end],
+	}
+}
+
+/// Splits a component that a component parser has accepted into its tokens:
+/// each run of digits is a field, and each other character is a designator
+/// or a separator.
+fn split_component(s: &str, kind: ComponentKind, start: usize, end: usize) -> Component<'_> {
+	let bytes = s.as_bytes();
+	let mut pieces = Vec::new();
+	let mut position = start;
+	while position < end {
+		let piece_start = position;
+		if bytes[position].is_ascii_digit() {
+			while position < end && bytes[position].is_ascii_digit() {
+				position += 1;
+			}
+		} else if kind == ComponentKind::YearlessDate && s[position..end].starts_with("--")
+		{
+			position += 2;
+		} else {
+			position += 1;
+		}
+		pieces.push((piece_start, position));
+	}
+
+	let kinds: &[TokenKind] = match kind {
+		ComponentKind::Year => &[TokenKind::Year],
+		ComponentKind::Month => &[TokenKind::Year, TokenKind::Hyphen, TokenKind::Month],
+		ComponentKind::Date => &[
+			TokenKind::Year,
+			TokenKind::Hyphen,
+			TokenKind::Month,
+			TokenKind::Hyphen,
+			TokenKind::Day,
+		],
+		ComponentKind::Week => &[
+			TokenKind::Year,
+			TokenKind::Hyphen,
+			TokenKind::WeekDesignator,
+			TokenKind::Week,
+		],
+		ComponentKind::YearlessDate if pieces.len() == 4 => &[
+			TokenKind::Hyphen,
+			TokenKind::Month,
+			TokenKind::Hyphen,
+			TokenKind::Day,
+		],
+		ComponentKind::YearlessDate => {
+			&[TokenKind::Month, TokenKind::Hyphen, TokenKind::Day]
+		}
+		ComponentKind::Time => &[
+			TokenKind::Hour,
+			TokenKind::Colon,
+			TokenKind::Minute,
+			TokenKind::Colon,
+			TokenKind::Second,
+			TokenKind::DecimalPoint,
+			TokenKind::Fraction,
+		],
+		ComponentKind::TimeZoneOffset if pieces.len() == 1 => &[TokenKind::Utc],
+		ComponentKind::TimeZoneOffset if pieces.len() == 2 => {
+			// The `hhmm` form, without a colon
+			let (sign_start, digits_start) = (pieces[0].0, pieces[1].0);
+			return Component {
+				kind,
+				tokens: vec![
+					token(s, TokenKind::Sign, sign_start, digits_start),
+					token(
+						s,
+						TokenKind::OffsetHour,
+						digits_start,
+						digits_start + 2,
+					),
+					token(s, TokenKind::OffsetMinute, digits_start + 2, end),
+				],
+			};
+		}
+		ComponentKind::TimeZoneOffset => &[
+			TokenKind::Sign,
+			TokenKind::OffsetHour,
+			TokenKind::Colon,
+			TokenKind::OffsetMinute,
+		],
+		ComponentKind::DateTimeSeparator => &[TokenKind::DateTimeSeparator],
+		ComponentKind::Unknown => &[TokenKind::Unknown],
+	};
+
+	Component {
+		kind,
+		tokens: pieces
+			.into_iter()
+			.zip(kinds)
+			.map(|((start, end), kind)| token(s, *kind, start, end))
+			.collect(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_spanned, tokenize, ComponentKind, TokenKind};
+
+	#[test]
+	fn test_parse_spanned_round_trips() {
+		for s in [
+			"2011-11",
+			"2011-11-18",
+			"11-18",
+			"--11-18",
+			"14:54:39.929",
+			"2011-11-18T14:54",
+			"2011-11-18 14:54:39",
+			"+05:30",
+			"-0800",
+			"Z",
+			"2011-11-18T14:54:39.9Z",
+			"2011-11-18T14:54-0800",
+			"2011-W47",
+			"0011",
+		] {
+			let tree = parse_spanned(s).unwrap();
+			assert_eq!(tree.to_string(), s);
+			assert!(tree.tokens().all(|token| &s[token.span()] == token.text()
+				&& token.kind() != TokenKind::Unknown));
+		}
+	}
+
+	#[test]
+	fn test_parse_spanned_components() {
+		let tree = parse_spanned("2011-11-18T14:54Z").unwrap();
+		let kinds: Vec<ComponentKind> =
+			tree.components().iter().map(|c| c.kind()).collect();
+		assert_eq!(
+			kinds,
+			[
+				ComponentKind::Date,
+				ComponentKind::DateTimeSeparator,
+				ComponentKind::Time,
+				ComponentKind::TimeZoneOffset
+			]
+		);
+		assert_eq!(tree.components()[0].span(), 0..10);
+		assert_eq!(tree.components()[3].span(), 16..17);
+	}
+
+	#[test]
+	fn test_parse_spanned_week() {
+		let tree = parse_spanned("2011-W47").unwrap();
+		let tokens: Vec<(TokenKind, &str)> = tree
+			.tokens()
+			.map(|token| (token.kind(), token.text()))
+			.collect();
+		assert_eq!(
+			tokens,
+			[
+				(TokenKind::Year, "2011"),
+				(TokenKind::Hyphen, "-"),
+				(TokenKind::WeekDesignator, "W"),
+				(TokenKind::Week, "47"),
+			]
+		);
+	}
+
+	#[test]
+	fn test_parse_spanned_fails() {
+		assert_eq!(parse_spanned("2011-13"), None);
+		assert_eq!(parse_spanned("next tuesday"), None);
+	}
+
+	#[test]
+	fn test_tokenize_invalid_covers_whole_string() {
+		for s in [
+			"",
+			"next tuesday",
+			"2011-1",
+			"2011-11-18x",
+			"14:5",
+			"2011-13-45T99:99",
+			"2011-11-18T",
+		] {
+			let text: String = tokenize(s).iter().map(|token| token.text()).collect();
+			assert_eq!(text, s);
+		}
+	}
+
+	#[test]
+	fn test_tokenize_reads_longest_valid_prefix() {
+		let tokens = tokenize("2011-1");
+		assert_eq!(tokens.len(), 2);
+		assert_eq!(tokens[0].kind(), TokenKind::Year);
+		assert_eq!(tokens[1].kind(), TokenKind::Unknown);
+		assert_eq!(tokens[1].span(), 4..6);
+
+		let tokens = tokenize("2011-11-18T25:00");
+		assert_eq!(tokens.len(), 6);
+		assert_eq!(tokens[4].kind(), TokenKind::Day);
+		assert_eq!(tokens[5].text(), "T25:00");
+	}
+
+	#[test]
+	fn test_tokenize_global_datetime_with_fraction() {
+		let tokens: Vec<(TokenKind, &str)> = tokenize("2011-11-18 14:54:39.929-08:00")
+			.iter()
+			.map(|token| (token.kind(), token.text()))
+			.collect();
+		assert_eq!(
+			tokens[5..],
+			[
+				(TokenKind::DateTimeSeparator, " "),
+				(TokenKind::Hour, "14"),
+				(TokenKind::Colon, ":"),
+				(TokenKind::Minute, "54"),
+				(TokenKind::Colon, ":"),
+				(TokenKind::Second, "39"),
+				(TokenKind::DecimalPoint, "."),
+				(TokenKind::Fraction, "929"),
+				(TokenKind::Sign, "-"),
+				(TokenKind::OffsetHour, "08"),
+				(TokenKind::Colon, ":"),
+				(TokenKind::OffsetMinute, "00"),
+			]
+		);
+	}
+
+	#[test]
+	fn test_tokenize_non_ascii() {
+		let tokens = tokenize("2011-11-18\u{2009}14:54");
+		assert_eq!(tokens.len(), 6);
+		assert_eq!(tokens[5].kind(), TokenKind::Unknown);
+		assert_eq!(tokens[5].text(), "\u{2009}14:54");
+	}
+}
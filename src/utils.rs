@@ -6,6 +6,17 @@ pub(crate) fn is_valid_month(month: &u32) -> bool {
 	(1..=12).contains(month)
 }
 
+/// Whether a year is greater than 0, and no later than the last year that a
+/// [`NaiveDate`] can represent, so that every month and week of it has dates.
+///
+/// The dates of a valid month or week are built one by one rather than with
+/// [`NaiveDate::iter_days()`], which stops before [`NaiveDate::MAX`] and would
+/// drop the last day of the last representable year.
+#[inline]
+pub(crate) fn is_valid_year(year: i32) -> bool {
+	year > 0 && year <= NaiveDate::MAX.year()
+}
+
 #[inline]
 pub(crate) fn is_valid_hour(hour: &u32) -> bool {
 	(0..=23).contains(hour)
@@ -37,10 +48,10 @@ pub const fn max_days_in_month_year(month: u32, year: u32) -> Option<u32> {
 }
 
 // https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#weeks
+//
+// This returns `None` for years that `NaiveDate` can not represent.
 pub fn week_number_of_year(year: i32) -> Option<u32> {
-	// We call unwrap() here since `NaiveDate::from_ymd_opt` returns `None` only
-	// if the month/day are out-of-range, which is not possible here since they're hardcoded.
-	let naive_date = NaiveDate::from_ymd_opt(year, 1u32, 1u32).unwrap();
+	let naive_date = NaiveDate::from_ymd_opt(year, 1u32, 1u32)?;
 	let weekday = naive_date.weekday();
 
 	match weekday {
@@ -128,4 +139,10 @@ mod tests {
 		assert_eq!(week_number_of_year(2014), Some(52));
 		assert_eq!(week_number_of_year(2025), Some(52));
 	}
+
+	#[test]
+	fn test_week_number_of_year_out_of_range_is_none() {
+		assert_eq!(week_number_of_year(999_999), None);
+		assert_eq!(week_number_of_year(i32::MAX), None);
+	}
 }